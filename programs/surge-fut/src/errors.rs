@@ -28,4 +28,124 @@ pub enum ContractError {
     
     #[msg("Insufficient tokens to redeem")]
     InsufficientTokens,
+    
+    #[msg("LP vault is already initialized for this token")]
+    LpVaultAlreadyInitialized,
+    
+    #[msg("LP vault accounts are missing or do not match the token config")]
+    LpVaultRequired,
+    
+    #[msg("Invalid utilization limit, must be between 1 and 10000")]
+    InvalidUtilizationLimit,
+    
+    #[msg("LP vault utilization limit exceeded")]
+    UtilizationLimitExceeded,
+    
+    #[msg("LP withdrawal cooldown has not elapsed")]
+    WithdrawalCooldownActive,
+    
+    #[msg("Insufficient LP vault liquidity")]
+    InsufficientLiquidity,
+    
+    #[msg("Total USDC required exceeds max_usdc_in")]
+    SlippageMaxInExceeded,
+    
+    #[msg("USDC received is below min_usdc_out")]
+    SlippageMinOutNotMet,
+    
+    #[msg("No authority transfer is pending")]
+    NoPendingAuthority,
+    
+    #[msg("Minting is paused")]
+    MintsPaused,
+    
+    #[msg("Redemptions are paused")]
+    RedeemsPaused,
+    
+    #[msg("Configuration updates are paused")]
+    UpdatesPaused,
+    
+    #[msg("Invalid pause flags")]
+    InvalidPauseFlags,
+    
+    #[msg("Invalid expiry timestamp")]
+    InvalidExpiry,
+    
+    #[msg("Token has expired")]
+    TokenExpired,
+    
+    #[msg("Token has not expired yet")]
+    TokenNotExpired,
+    
+    #[msg("Token is already settled")]
+    AlreadySettled,
+    
+    #[msg("Expired token has not been settled yet")]
+    NotSettled,
+    
+    #[msg("Invalid TWAP window")]
+    InvalidTwapWindow,
+    
+    #[msg("Oracle history does not cover the TWAP window")]
+    InsufficientOracleHistory,
+    
+    #[msg("Mint would exceed the open interest cap")]
+    OpenInterestCapExceeded,
+    
+    #[msg("Mint would exceed the maximum position size")]
+    PositionLimitExceeded,
+    
+    #[msg("Mint would exceed the per-slot mint limit")]
+    MintRateLimitExceeded,
+    
+    #[msg("Position still holds tokens")]
+    PositionNotEmpty,
+    
+    #[msg("Recipient must differ from the sender")]
+    InvalidRecipient,
+    
+    #[msg("Invalid volatility cap")]
+    InvalidVolCap,
+    
+    #[msg("Instruction is not supported in this token mode")]
+    InvalidTokenMode,
+    
+    #[msg("Referrer account is invalid or belongs to the trader")]
+    InvalidReferrer,
+    
+    #[msg("Insurance fund account is missing or does not match the token config")]
+    InsuranceFundRequired,
+    
+    #[msg("Fee tiers must be ascending by volume with discounts of at most 10000 bps")]
+    InvalidFeeTiers,
+    
+    #[msg("Fee shares exceed the collected fee")]
+    InvalidFeeSplit,
+    
+    #[msg("Fee withdrawal destination is not on the allowlist")]
+    FeeDestinationNotAllowed,
+    
+    #[msg("Fee withdrawal timelock has not elapsed")]
+    FeeTimelockActive,
+    
+    #[msg("Invalid fee withdrawal policy")]
+    InvalidFeeWithdrawalPolicy,
+    
+    #[msg("Invalid order parameters")]
+    InvalidOrder,
+    
+    #[msg("Oracle volatility has not reached the order trigger")]
+    OrderNotTriggered,
+    
+    #[msg("Invalid collateral asset parameters")]
+    InvalidCollateralAsset,
+    
+    #[msg("Collateral price feed is missing or invalid")]
+    InvalidCollateralPrice,
+    
+    #[msg("Collateral asset account is required for this market")]
+    CollateralAssetRequired,
+    
+    #[msg("Account already has the current layout")]
+    AlreadyMigrated,
 }
\ No newline at end of file
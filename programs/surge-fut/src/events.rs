@@ -0,0 +1,379 @@
+use anchor_lang::prelude::*;
+
+use crate::state::{FeeTier, OrderSide, TriggerCondition, MAX_FEE_DESTINATIONS, MAX_FEE_TIERS};
+
+#[event]
+pub struct TokenConfigInitialized {
+    pub token_config: Pubkey,
+    pub authority: Pubkey,
+    pub token_mint: Pubkey,
+    pub usdc_mint: Pubkey,
+    pub collateral_asset: Pubkey,
+    pub fee_vault: Pubkey,
+    pub collateral_pool: Pubkey,
+    pub oracle: Pubkey,
+    pub token_name: String,
+    pub token_symbol: String,
+    pub fee_bps: u16,
+    pub usdc_per_vol_point: u64,
+    pub start_volatility: f64,
+    pub expiry_timestamp: i64,
+    pub vol_cap: f64,
+    pub token_uri: String,
+}
+
+#[event]
+pub struct TokensMinted {
+    pub token_mint: Pubkey,
+    pub user: Pubkey,
+    pub amount: u64,
+    pub volatility: f64,
+    pub execution_price: u64, // USDC base units per token base unit
+    pub usdc_paid: u64,
+    pub fee_amount: u64,
+    pub collateral_delta: u64,
+    pub position_tokens: u64,
+    pub position_collateral: u64,
+    pub total_tokens_outstanding: u64,
+}
+
+#[event]
+pub struct TokensRedeemed {
+    pub token_mint: Pubkey,
+    pub user: Pubkey,
+    pub amount: u64,
+    pub entry_volatility: f64,
+    pub volatility: f64,
+    pub execution_price: u64, // USDC base units per token base unit
+    pub usdc_received: u64,
+    pub fee_amount: u64,
+    pub collateral_delta: u64,
+    pub pnl: i64,             // Redemption value minus released collateral
+    pub position_tokens: u64,
+    pub position_collateral: u64,
+    pub total_tokens_outstanding: u64,
+}
+
+#[event]
+pub struct FeeUpdated {
+    pub token_mint: Pubkey,
+    pub authority: Pubkey,
+    pub old_mint_fee_bps: u16,
+    pub new_mint_fee_bps: u16,
+    pub old_redeem_fee_bps: u16,
+    pub new_redeem_fee_bps: u16,
+}
+
+#[event]
+pub struct LpVaultInitialized {
+    pub token_mint: Pubkey,
+    pub lp_vault: Pubkey,
+    pub lp_mint: Pubkey,
+    pub usdc_vault: Pubkey,
+    pub fee_share_bps: u16,
+    pub max_utilization_bps: u16,
+    pub withdrawal_cooldown: i64,
+}
+
+#[event]
+pub struct LiquidityDeposited {
+    pub token_mint: Pubkey,
+    pub provider: Pubkey,
+    pub amount: u64,
+    pub shares: u64,
+    pub vault_balance: u64,
+    pub net_assets: u64,
+    pub share_supply: u64,
+}
+
+#[event]
+pub struct LiquidityWithdrawn {
+    pub token_mint: Pubkey,
+    pub provider: Pubkey,
+    pub shares: u64,
+    pub amount: u64,
+    pub vault_balance: u64,
+    pub net_assets: u64,
+    pub share_supply: u64,
+}
+
+#[event]
+pub struct UsdcPerVolPointUpdated {
+    pub token_mint: Pubkey,
+    pub authority: Pubkey,
+    pub old_usdc_per_vol_point: u64,
+    pub new_usdc_per_vol_point: u64,
+}
+
+#[event]
+pub struct OracleUpdated {
+    pub token_mint: Pubkey,
+    pub authority: Pubkey,
+    pub old_oracle: Pubkey,
+    pub new_oracle: Pubkey,
+    pub volatility: f64,
+}
+
+#[event]
+pub struct AuthorityProposed {
+    pub token_mint: Pubkey,
+    pub authority: Pubkey,
+    pub pending_authority: Pubkey,
+}
+
+#[event]
+pub struct AuthorityTransferred {
+    pub token_mint: Pubkey,
+    pub old_authority: Pubkey,
+    pub new_authority: Pubkey,
+}
+
+#[event]
+pub struct GuardianUpdated {
+    pub token_mint: Pubkey,
+    pub authority: Pubkey,
+    pub old_guardian: Pubkey,
+    pub new_guardian: Pubkey,
+}
+
+#[event]
+pub struct PauseFlagsUpdated {
+    pub token_mint: Pubkey,
+    pub signer: Pubkey,
+    pub old_pause_flags: u8,
+    pub new_pause_flags: u8,
+}
+
+#[event]
+pub struct TokenSettled {
+    pub token_mint: Pubkey,
+    pub settler: Pubkey,
+    pub expiry_timestamp: i64,
+    pub settlement_volatility: f64,
+    pub settlement_price: u64, // USDC base units per token base unit
+}
+
+#[event]
+pub struct TwapWindowUpdated {
+    pub token_mint: Pubkey,
+    pub authority: Pubkey,
+    pub old_twap_window: i64,
+    pub new_twap_window: i64,
+}
+
+#[event]
+pub struct RiskLimitsUpdated {
+    pub token_mint: Pubkey,
+    pub authority: Pubkey,
+    pub max_open_interest: u64,
+    pub max_position_size: u64,
+    pub max_mint_per_slot: u64,
+}
+
+#[event]
+pub struct PositionClosed {
+    pub token_mint: Pubkey,
+    pub owner: Pubkey,
+    pub rent_returned: u64,
+    pub token_account_closed: bool,
+}
+
+#[event]
+pub struct PositionTransferred {
+    pub token_mint: Pubkey,
+    pub from: Pubkey,
+    pub to: Pubkey,
+    pub amount: u64,
+    pub entry_volatility: f64,
+    pub collateral_moved: u64,
+    pub recipient_entry_volatility: f64,
+}
+
+#[event]
+pub struct DelegateUpdated {
+    pub token_mint: Pubkey,
+    pub owner: Pubkey,
+    pub delegate: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+pub struct IndexTokensMinted {
+    pub token_mint: Pubkey,
+    pub user: Pubkey,
+    pub amount: u64,
+    pub volatility: f64,
+    pub execution_price: u64, // USDC base units per token base unit
+    pub usdc_paid: u64,
+    pub fee_amount: u64,
+    pub vault_collateral: u64, // Collateral locked by the LP vault up to the cap
+    pub total_tokens_outstanding: u64,
+}
+
+#[event]
+pub struct IndexTokensRedeemed {
+    pub token_mint: Pubkey,
+    pub user: Pubkey,
+    pub amount: u64,
+    pub volatility: f64,
+    pub execution_price: u64, // USDC base units per token base unit
+    pub usdc_received: u64,
+    pub fee_amount: u64,
+    pub vault_collateral: u64, // Collateral released back to the LP vault
+    pub total_tokens_outstanding: u64,
+}
+
+#[event]
+pub struct FeeTiersUpdated {
+    pub token_mint: Pubkey,
+    pub authority: Pubkey,
+    pub fee_tiers: [FeeTier; MAX_FEE_TIERS],
+}
+
+#[event]
+pub struct FeeSplitUpdated {
+    pub token_mint: Pubkey,
+    pub authority: Pubkey,
+    pub referral_share_bps: u16,
+    pub insurance_fund: Pubkey,
+    pub insurance_share_bps: u16,
+}
+
+#[event]
+pub struct FeeSplitQueued {
+    pub token_mint: Pubkey,
+    pub authority: Pubkey,
+    pub referral_share_bps: u16,
+    pub insurance_fund: Pubkey,
+    pub insurance_share_bps: u16,
+    pub unlock_timestamp: i64,
+}
+
+#[event]
+pub struct FeesDistributed {
+    pub token_mint: Pubkey,
+    pub trader: Pubkey,
+    pub referrer: Pubkey, // Default if the trade had no referrer
+    pub total_fee: u64,
+    pub discount_bps: u16,
+    pub referrer_rebate: u64,
+    pub lp_fee: u64,
+    pub insurance_fee: u64,
+    pub protocol_fee: u64,
+}
+
+#[event]
+pub struct FeeWithdrawalQueued {
+    pub token_mint: Pubkey,
+    pub authority: Pubkey,
+    pub destination: Pubkey,
+    pub amount: u64,
+    pub unlock_timestamp: i64,
+}
+
+#[event]
+pub struct FeesWithdrawn {
+    pub token_mint: Pubkey,
+    pub authority: Pubkey,
+    pub destination: Pubkey,
+    pub amount: u64,
+    pub remaining_balance: u64,
+}
+
+#[event]
+pub struct FeeWithdrawalPolicyQueued {
+    pub token_mint: Pubkey,
+    pub authority: Pubkey,
+    pub timelock: i64,
+    pub allowlist: [Pubkey; MAX_FEE_DESTINATIONS],
+    pub unlock_timestamp: i64,
+}
+
+#[event]
+pub struct FeeWithdrawalPolicyUpdated {
+    pub token_mint: Pubkey,
+    pub authority: Pubkey,
+    pub timelock: i64,
+    pub allowlist: [Pubkey; MAX_FEE_DESTINATIONS],
+}
+
+#[event]
+pub struct OrderPlaced {
+    pub token_mint: Pubkey,
+    pub owner: Pubkey,
+    pub order: Pubkey,
+    pub order_id: u64,
+    pub side: OrderSide,
+    pub trigger: TriggerCondition,
+    pub trigger_volatility: f64,
+    pub amount: u64,
+    pub limit_usdc: u64,
+    pub keeper_fee: u64,
+    pub escrow_amount: u64,
+}
+
+#[event]
+pub struct OrderExecuted {
+    pub token_mint: Pubkey,
+    pub owner: Pubkey,
+    pub order: Pubkey,
+    pub order_id: u64,
+    pub keeper: Pubkey,
+    pub side: OrderSide,
+    pub amount: u64,
+    pub volatility: f64,
+    pub usdc_amount: u64, // USDC paid for buys, received by the owner for sells
+    pub fee_amount: u64,
+    pub keeper_fee: u64,
+    pub position_tokens: u64,
+    pub position_collateral: u64,
+    pub total_tokens_outstanding: u64,
+}
+
+#[event]
+pub struct OrderCancelled {
+    pub token_mint: Pubkey,
+    pub owner: Pubkey,
+    pub order: Pubkey,
+    pub order_id: u64,
+    pub refunded: u64,
+}
+
+#[event]
+pub struct CollateralAssetAdded {
+    pub collateral_asset: Pubkey,
+    pub authority: Pubkey,
+    pub mint: Pubkey,
+    pub decimals: u8,
+    pub haircut_bps: u16,
+    pub price_feed: Pubkey,
+    pub max_price_age: i64,
+}
+
+#[event]
+pub struct CollateralAssetUpdated {
+    pub collateral_asset: Pubkey,
+    pub mint: Pubkey,
+    pub old_haircut_bps: u16,
+    pub new_haircut_bps: u16,
+    pub old_price_feed: Pubkey,
+    pub new_price_feed: Pubkey,
+    pub max_price_age: i64,
+}
+
+#[event]
+pub struct ReferrerRegistered {
+    pub token_mint: Pubkey,
+    pub referrer: Pubkey,
+    pub owner: Pubkey,
+    pub usdc_account: Pubkey,
+}
+
+#[event]
+pub struct TokenConfigMigrated {
+    pub token_mint: Pubkey,
+    pub authority: Pubkey,
+    pub fee_vault: Pubkey,
+    pub old_size: u64,
+    pub new_size: u64,
+}
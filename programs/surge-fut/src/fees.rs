@@ -0,0 +1,306 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TransferChecked};
+
+use crate::{state::*, errors::ContractError, events::FeesDistributed};
+
+/// Breakdown of a trading fee between its recipients
+pub struct FeeSplit {
+    pub total: u64,                  // Fee charged after the volume discount
+    pub discount_bps: u16,           // Volume tier discount applied to the base fee
+    pub referrer: u64,               // Rebate paid to the referrer
+    pub lp: u64,                     // Share credited to the LP vault
+    pub insurance: u64,              // Share sent to the insurance fund
+    pub protocol: u64,               // Remainder sent to the fee vault
+}
+
+/// Accounts receiving the shares of a FeeSplit
+pub struct FeeRecipients<'info> {
+    pub fee_vault: AccountInfo<'info>,
+    pub lp_usdc_vault: Option<AccountInfo<'info>>,
+    pub insurance_fund: Option<AccountInfo<'info>>,
+    pub referrer: Option<AccountInfo<'info>>,
+}
+
+fn bps_of(amount: u64, bps: u16) -> Result<u64> {
+    amount
+        .checked_mul(bps as u64)
+        .ok_or(ContractError::MathOverflow)?
+        .checked_div(10000)
+        .ok_or(ContractError::MathOverflow.into())
+}
+
+/// Fee on `notional` at `fee_bps` for a trader with `volume` of prior volume.
+/// The referrer rebate comes off the top, the LP and insurance shares are
+/// taken from what remains and the protocol keeps the rest.
+pub fn split_fee(
+    token_config: &TokenConfig,
+    notional: u64,
+    fee_bps: u16,
+    volume: u64,
+    lp_vault: Option<&LpVault>,
+    has_referrer: bool,
+) -> Result<FeeSplit> {
+    let discount_bps = token_config.fee_discount_bps(volume);
+    let base_fee = bps_of(notional, fee_bps)?;
+    let total = base_fee
+        .checked_sub(bps_of(base_fee, discount_bps)?)
+        .ok_or(ContractError::MathOverflow)?;
+
+    let referrer = if has_referrer {
+        bps_of(total, token_config.referral_share_bps)?
+    } else {
+        0
+    };
+    let remaining = total
+        .checked_sub(referrer)
+        .ok_or(ContractError::MathOverflow)?;
+
+    let lp = match lp_vault {
+        Some(lp_vault) => lp_vault.fee_share(remaining)?,
+        None => 0,
+    };
+    let insurance = if token_config.insurance_fund != Pubkey::default() {
+        bps_of(remaining, token_config.insurance_share_bps)?
+    } else {
+        0
+    };
+    let protocol = remaining
+        .checked_sub(lp)
+        .and_then(|rest| rest.checked_sub(insurance))
+        .ok_or(ContractError::InvalidFeeSplit)?;
+
+    Ok(FeeSplit {
+        total,
+        discount_bps,
+        referrer,
+        lp,
+        insurance,
+        protocol,
+    })
+}
+
+/// Validates the optional referrer passed to a trade and returns its rebate account.
+/// Referrers are registered by the market authority and cannot be the trader.
+/// The first referred trade binds the referrer to the trader, later trades can
+/// only rebate that same referrer.
+pub fn resolve_referrer<'a, 'info>(
+    token_config: &TokenConfig,
+    referrer: &Option<Account<'info, Referrer>>,
+    referrer_usdc_account: &'a Option<InterfaceAccount<'info, TokenAccount>>,
+    user_volume: &mut UserVolume,
+    trader: Pubkey,
+) -> Result<Option<&'a InterfaceAccount<'info, TokenAccount>>> {
+    let (referrer, referrer_usdc_account) = match (referrer, referrer_usdc_account) {
+        (Some(referrer), Some(referrer_usdc_account)) => (referrer, referrer_usdc_account),
+        (None, None) => return Ok(None),
+        _ => return err!(ContractError::InvalidReferrer),
+    };
+    require!(
+        referrer.token_mint == token_config.token_mint
+            && referrer.owner != trader
+            && referrer_usdc_account.key() == referrer.usdc_account,
+        ContractError::InvalidReferrer
+    );
+
+    if user_volume.referrer == Pubkey::default() {
+        user_volume.referrer = referrer.owner;
+    }
+    require!(
+        user_volume.referrer == referrer.owner,
+        ContractError::InvalidReferrer
+    );
+
+    Ok(Some(referrer_usdc_account))
+}
+
+/// Resolves the optional insurance fund account passed to a trade.
+/// Once an insurance fund has been set on the TokenConfig it must be supplied.
+pub fn resolve_insurance_fund<'a, 'info>(
+    token_config: &TokenConfig,
+    insurance_fund: &'a Option<InterfaceAccount<'info, TokenAccount>>,
+) -> Result<Option<&'a InterfaceAccount<'info, TokenAccount>>> {
+    if token_config.insurance_fund == Pubkey::default() {
+        return Ok(None);
+    }
+
+    match insurance_fund {
+        Some(insurance_fund) if insurance_fund.key() == token_config.insurance_fund => {
+            Ok(Some(insurance_fund))
+        }
+        _ => Err(ContractError::InsuranceFundRequired.into()),
+    }
+}
+
+/// Pays every non-zero share of `split` out of `from`
+#[allow(clippy::too_many_arguments)]
+pub fn distribute_fees<'info>(
+    token_program: &AccountInfo<'info>,
+    from: &AccountInfo<'info>,
+    mint: &InterfaceAccount<'info, Mint>,
+    authority: &AccountInfo<'info>,
+    signer_seeds: &[&[&[u8]]],
+    recipients: FeeRecipients<'info>,
+    split: &FeeSplit,
+    token_mint: Pubkey,
+    trader: Pubkey,
+) -> Result<()> {
+    let referrer_key = recipients
+        .referrer
+        .as_ref()
+        .map(|referrer| referrer.key())
+        .unwrap_or_default();
+
+    let payouts = [
+        (Some(recipients.fee_vault), split.protocol),
+        (recipients.lp_usdc_vault, split.lp),
+        (recipients.insurance_fund, split.insurance),
+        (recipients.referrer, split.referrer),
+    ];
+
+    for (recipient, amount) in payouts {
+        if amount == 0 {
+            continue;
+        }
+        let recipient = recipient.ok_or(ContractError::InvalidFeeSplit)?;
+
+        token_interface::transfer_checked(
+            CpiContext::new_with_signer(
+                token_program.clone(),
+                TransferChecked {
+                    from: from.clone(),
+                    mint: mint.to_account_info(),
+                    to: recipient,
+                    authority: authority.clone(),
+                },
+                signer_seeds,
+            ),
+            amount,
+            mint.decimals,
+        )?;
+    }
+
+    emit!(FeesDistributed {
+        token_mint,
+        trader,
+        referrer: referrer_key,
+        total_fee: split.total,
+        discount_bps: split.discount_bps,
+        referrer_rebate: split.referrer,
+        lp_fee: split.lp,
+        insurance_fee: split.insurance,
+        protocol_fee: split.protocol,
+    });
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn token_config() -> TokenConfig {
+        TokenConfig::deserialize(&mut &[0u8; 2048][..]).unwrap()
+    }
+
+    fn lp_vault(fee_share_bps: u16) -> LpVault {
+        LpVault {
+            token_config: Pubkey::default(),
+            lp_mint: Pubkey::default(),
+            usdc_vault: Pubkey::default(),
+            fee_share_bps,
+            max_utilization_bps: 10000,
+            withdrawal_cooldown: 0,
+            total_fees_earned: 0,
+            usdc_vault_bump: 0,
+            lp_mint_bump: 0,
+            bump: 0,
+            total_assets: 0,
+            trader_collateral: 0,
+        }
+    }
+
+    fn assert_sums(split: &FeeSplit) {
+        assert_eq!(
+            split.referrer + split.lp + split.insurance + split.protocol,
+            split.total
+        );
+    }
+
+    #[test]
+    fn protocol_keeps_the_whole_fee_by_default() {
+        let split = split_fee(&token_config(), 1_000_000, 30, 0, None, false).unwrap();
+        assert_eq!(split.total, 3_000);
+        assert_eq!(split.protocol, 3_000);
+        assert_sums(&split);
+    }
+
+    #[test]
+    fn volume_tier_discounts_the_base_fee() {
+        let mut config = token_config();
+        config.fee_tiers[0] = FeeTier { min_volume: 1_000, discount_bps: 1_000 };
+        config.fee_tiers[1] = FeeTier { min_volume: 10_000, discount_bps: 5_000 };
+
+        let below = split_fee(&config, 1_000_000, 30, 999, None, false).unwrap();
+        assert_eq!((below.total, below.discount_bps), (3_000, 0));
+
+        let first = split_fee(&config, 1_000_000, 30, 1_000, None, false).unwrap();
+        assert_eq!((first.total, first.discount_bps), (2_700, 1_000));
+
+        let best = split_fee(&config, 1_000_000, 30, 50_000, None, false).unwrap();
+        assert_eq!((best.total, best.discount_bps), (1_500, 5_000));
+        assert_sums(&best);
+    }
+
+    #[test]
+    fn referrer_is_paid_before_lp_and_insurance_shares() {
+        let mut config = token_config();
+        config.referral_share_bps = 2_000;
+        config.insurance_fund = Pubkey::new_unique();
+        config.insurance_share_bps = 1_000;
+        let vault = lp_vault(5_000);
+
+        let split = split_fee(&config, 1_000_000, 100, 0, Some(&vault), true).unwrap();
+        assert_eq!(split.total, 10_000);
+        assert_eq!(split.referrer, 2_000);
+        assert_eq!(split.lp, 4_000);
+        assert_eq!(split.insurance, 800);
+        assert_eq!(split.protocol, 3_200);
+        assert_sums(&split);
+
+        // Without a referrer the shares come out of the whole fee
+        let split = split_fee(&config, 1_000_000, 100, 0, Some(&vault), false).unwrap();
+        assert_eq!((split.referrer, split.lp, split.insurance, split.protocol), (0, 5_000, 1_000, 4_000));
+        assert_sums(&split);
+    }
+
+    #[test]
+    fn insurance_share_needs_an_insurance_fund() {
+        let mut config = token_config();
+        config.insurance_share_bps = 1_000;
+        let split = split_fee(&config, 1_000_000, 100, 0, None, false).unwrap();
+        assert_eq!((split.insurance, split.protocol), (0, 10_000));
+    }
+
+    #[test]
+    fn rounding_never_overpays_the_fee() {
+        let mut config = token_config();
+        config.referral_share_bps = 3_333;
+        config.insurance_fund = Pubkey::new_unique();
+        config.insurance_share_bps = 3_333;
+        let vault = lp_vault(3_333);
+
+        for notional in [1, 7, 299, 3_331, 1_000_003] {
+            let split = split_fee(&config, notional, 77, 0, Some(&vault), true).unwrap();
+            assert_sums(&split);
+        }
+    }
+
+    #[test]
+    fn shares_above_the_fee_are_rejected() {
+        let mut config = token_config();
+        config.insurance_fund = Pubkey::new_unique();
+        config.insurance_share_bps = 6_000;
+        let vault = lp_vault(6_000);
+        assert!(split_fee(&config, 1_000_000, 100, 0, Some(&vault), false).is_err());
+    }
+}
@@ -0,0 +1,43 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::Mint;
+
+use crate::{state::*, errors::ContractError, events::*};
+
+#[derive(Accounts)]
+pub struct AcceptAuthority<'info> {
+    #[account(
+        constraint = token_config.pending_authority != Pubkey::default() @ ContractError::NoPendingAuthority,
+        constraint = new_authority.key() == token_config.pending_authority @ ContractError::Unauthorized,
+    )]
+    pub new_authority: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"token_config", token_mint.key().as_ref()],
+        bump = token_config.bump,
+    )]
+    pub token_config: Account<'info, TokenConfig>,
+    
+    /// Volatility token mint
+    #[account(
+        constraint = token_mint.key() == token_config.token_mint @ ContractError::InvalidOracleData,
+    )]
+    pub token_mint: InterfaceAccount<'info, Mint>,
+}
+
+pub fn accept_authority(ctx: Context<AcceptAuthority>) -> Result<()> {
+    // Complete the two-step transfer
+    let old_authority = ctx.accounts.token_config.authority;
+    ctx.accounts.token_config.authority = ctx.accounts.new_authority.key();
+    ctx.accounts.token_config.pending_authority = Pubkey::default();
+    
+    msg!("Authority transferred to: {}", ctx.accounts.new_authority.key());
+    
+    emit!(AuthorityTransferred {
+        token_mint: ctx.accounts.token_mint.key(),
+        old_authority,
+        new_authority: ctx.accounts.new_authority.key(),
+    });
+    
+    Ok(())
+}
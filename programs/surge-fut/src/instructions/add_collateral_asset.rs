@@ -0,0 +1,73 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::Mint;
+
+use crate::{state::*, errors::ContractError, events::*};
+
+#[derive(Accounts)]
+#[instruction(haircut_bps: u16, feed_id: [u8; 32], max_price_age: i64)]
+pub struct AddCollateralAsset<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    /// Mint accepted as collateral
+    pub mint: InterfaceAccount<'info, Mint>,
+    
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + 32 + 32 + 1 + 2 + 32 + 32 + 8 + 1,
+        seeds = [b"collateral_asset", authority.key().as_ref(), mint.key().as_ref()],
+        bump
+    )]
+    pub collateral_asset: Account<'info, CollateralAsset>,
+    
+    /// Pyth price update account, omit for a stablecoin pegged at $1
+    /// CHECK: Validated through load_pyth_price
+    pub price_feed: Option<AccountInfo<'info>>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+pub fn add_collateral_asset(
+    ctx: Context<AddCollateralAsset>,
+    haircut_bps: u16,
+    feed_id: [u8; 32],
+    max_price_age: i64,
+) -> Result<()> {
+    // Validate haircut and price age
+    require!(haircut_bps < 10000, ContractError::InvalidCollateralAsset);
+    require!(max_price_age > 0, ContractError::InvalidCollateralAsset);
+    
+    let price_feed = ctx
+        .accounts
+        .price_feed
+        .as_ref()
+        .map(|price_feed| price_feed.key())
+        .unwrap_or_default();
+    
+    let collateral_asset = &mut ctx.accounts.collateral_asset;
+    collateral_asset.authority = ctx.accounts.authority.key();
+    collateral_asset.mint = ctx.accounts.mint.key();
+    collateral_asset.decimals = ctx.accounts.mint.decimals;
+    collateral_asset.haircut_bps = haircut_bps;
+    collateral_asset.price_feed = price_feed;
+    collateral_asset.feed_id = feed_id;
+    collateral_asset.max_price_age = max_price_age;
+    collateral_asset.bump = ctx.bumps.collateral_asset;
+    
+    // Make sure the feed can actually be read before markets rely on it
+    let price = collateral_asset.load_price(ctx.accounts.price_feed.as_ref())?;
+    msg!("Collateral price: {}", price.price);
+    
+    emit!(CollateralAssetAdded {
+        collateral_asset: collateral_asset.key(),
+        authority: collateral_asset.authority,
+        mint: collateral_asset.mint,
+        decimals: collateral_asset.decimals,
+        haircut_bps,
+        price_feed,
+        max_price_age,
+    });
+    
+    Ok(())
+}
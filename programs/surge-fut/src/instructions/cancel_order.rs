@@ -0,0 +1,105 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{self, CloseAccount, Mint, TokenAccount, TokenInterface, TransferChecked};
+
+use crate::{state::*, errors::ContractError, events::*};
+
+#[derive(Accounts)]
+pub struct CancelOrder<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    
+    /// Volatility token mint
+    pub token_mint: InterfaceAccount<'info, Mint>,
+    
+    /// Order account, rent is returned to the owner
+    #[account(
+        mut,
+        close = owner,
+        seeds = [b"order", owner.key().as_ref(), token_mint.key().as_ref(), &order.order_id.to_le_bytes()],
+        bump = order.bump,
+        constraint = order.owner == owner.key() @ ContractError::Unauthorized,
+    )]
+    pub order: Account<'info, Order>,
+    
+    /// Escrow holding the order's USDC or tokens
+    #[account(
+        mut,
+        seeds = [b"order_escrow", order.key().as_ref()],
+        bump = order.escrow_bump,
+    )]
+    pub order_escrow: InterfaceAccount<'info, TokenAccount>,
+    
+    /// Mint of the escrowed funds
+    #[account(
+        constraint = escrow_mint.key() == order_escrow.mint @ ContractError::InvalidOracleData,
+    )]
+    pub escrow_mint: InterfaceAccount<'info, Mint>,
+    
+    /// Account receiving the escrowed funds back
+    #[account(
+        mut,
+        constraint = owner_refund_account.owner == owner.key() @ ContractError::Unauthorized,
+        constraint = owner_refund_account.mint == order_escrow.mint @ ContractError::InvalidOracleData,
+    )]
+    pub owner_refund_account: InterfaceAccount<'info, TokenAccount>,
+    
+    /// Token program owning the escrow mint
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+pub fn cancel_order(ctx: Context<CancelOrder>) -> Result<()> {
+    let refunded = ctx.accounts.order_escrow.amount;
+    
+    let owner_key = ctx.accounts.owner.key();
+    let token_mint_key = ctx.accounts.token_mint.key();
+    let order_id_bytes = ctx.accounts.order.order_id.to_le_bytes();
+    let order_seeds = &[
+        b"order",
+        owner_key.as_ref(),
+        token_mint_key.as_ref(),
+        order_id_bytes.as_ref(),
+        &[ctx.accounts.order.bump]
+    ];
+    let signer = &[&order_seeds[..]];
+    
+    // Return the escrowed funds
+    if refunded > 0 {
+        token_interface::transfer_checked(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                TransferChecked {
+                    from: ctx.accounts.order_escrow.to_account_info(),
+                    mint: ctx.accounts.escrow_mint.to_account_info(),
+                    to: ctx.accounts.owner_refund_account.to_account_info(),
+                    authority: ctx.accounts.order.to_account_info(),
+                },
+                signer,
+            ),
+            refunded,
+            ctx.accounts.escrow_mint.decimals,
+        )?;
+    }
+    
+    // Close the escrow, rent goes back to the owner
+    token_interface::close_account(CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        CloseAccount {
+            account: ctx.accounts.order_escrow.to_account_info(),
+            destination: ctx.accounts.owner.to_account_info(),
+            authority: ctx.accounts.order.to_account_info(),
+        },
+        signer,
+    ))?;
+    
+    msg!("Order {} cancelled", ctx.accounts.order.order_id);
+    
+    emit!(OrderCancelled {
+        token_mint: token_mint_key,
+        owner: owner_key,
+        order: ctx.accounts.order.key(),
+        order_id: ctx.accounts.order.order_id,
+        refunded,
+    });
+    
+    Ok(())
+}
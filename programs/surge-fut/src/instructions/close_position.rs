@@ -0,0 +1,66 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{self, CloseAccount, Mint, TokenAccount, TokenInterface};
+
+use crate::{state::*, errors::ContractError, events::*};
+
+#[derive(Accounts)]
+pub struct ClosePosition<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+    
+    /// Volatility token mint
+    pub token_mint: InterfaceAccount<'info, Mint>,
+    
+    /// User Position Account, rent is returned to the user
+    #[account(
+        mut,
+        close = user,
+        seeds = [b"user_position", user.key().as_ref(), token_mint.key().as_ref()],
+        bump = user_position.bump,
+        constraint = user_position.owner == user.key() @ ContractError::Unauthorized,
+        constraint = user_position.tokens_minted == 0 @ ContractError::PositionNotEmpty,
+    )]
+    pub user_position: Account<'info, UserPosition>,
+    
+    /// Empty volatility token account to close as well, if desired
+    #[account(
+        mut,
+        constraint = user_token_account.owner == user.key() @ ContractError::Unauthorized,
+        constraint = user_token_account.mint == token_mint.key() @ ContractError::InvalidOracleData,
+        constraint = user_token_account.amount == 0 @ ContractError::PositionNotEmpty,
+    )]
+    pub user_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+pub fn close_position(ctx: Context<ClosePosition>) -> Result<()> {
+    let rent_returned = ctx.accounts.user_position.to_account_info().lamports();
+    
+    // Close the empty token account, rent goes back to the user
+    let token_account_closed = match &ctx.accounts.user_token_account {
+        Some(user_token_account) => {
+            token_interface::close_account(CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                CloseAccount {
+                    account: user_token_account.to_account_info(),
+                    destination: ctx.accounts.user.to_account_info(),
+                    authority: ctx.accounts.user.to_account_info(),
+                },
+            ))?;
+            true
+        }
+        None => false,
+    };
+    
+    msg!("Position closed for: {}", ctx.accounts.user.key());
+    
+    emit!(PositionClosed {
+        token_mint: ctx.accounts.token_mint.key(),
+        owner: ctx.accounts.user.key(),
+        rent_returned,
+        token_account_closed,
+    });
+    
+    Ok(())
+}
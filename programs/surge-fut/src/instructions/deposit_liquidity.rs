@@ -0,0 +1,208 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked};
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token_2022::Token2022;
+
+use crate::{state::*, errors::ContractError, events::*, trade::*};
+
+#[derive(Accounts)]
+#[instruction(amount: u64)]
+pub struct DepositLiquidity<'info> {
+    #[account(mut)]
+    pub provider: Signer<'info>,
+
+    /// USDC token account of the liquidity provider
+    #[account(
+        mut,
+        constraint = provider_usdc_account.owner == provider.key() @ ContractError::Unauthorized,
+        constraint = provider_usdc_account.mint == token_config.usdc_mint @ ContractError::InvalidOracleData,
+    )]
+    pub provider_usdc_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// LP share token account of the liquidity provider
+    #[account(
+        init_if_needed,
+        payer = provider,
+        associated_token::mint = lp_mint,
+        associated_token::authority = provider,
+        associated_token::token_program = lp_token_program,
+    )]
+    pub provider_lp_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// Volatility token mint
+    #[account(
+        constraint = token_mint.key() == token_config.token_mint @ ContractError::InvalidOracleData,
+    )]
+    pub token_mint: InterfaceAccount<'info, Mint>,
+
+    /// Token Config
+    #[account(
+        seeds = [b"token_config", token_mint.key().as_ref()],
+        bump = token_config.bump,
+        constraint = !token_config.is_paused(PAUSE_MINTS) @ ContractError::MintsPaused,
+    )]
+    pub token_config: Account<'info, TokenConfig>,
+
+    /// Oracle account with volatility data, used to mark open trader positions
+    /// CHECK: Account is validated through VolatilityStats::load_from_account_info
+    #[account(
+        constraint = oracle.key() == token_config.oracle @ ContractError::InvalidOracleData,
+    )]
+    pub oracle: AccountInfo<'info>,
+
+    /// LP vault state
+    #[account(
+        mut,
+        seeds = [b"lp_vault", token_mint.key().as_ref()],
+        bump = lp_vault.bump,
+        constraint = lp_vault.key() == token_config.lp_vault @ ContractError::LpVaultRequired,
+    )]
+    pub lp_vault: Account<'info, LpVault>,
+
+    /// LP share token mint
+    #[account(
+        mut,
+        seeds = [b"lp_mint", token_mint.key().as_ref()],
+        bump = lp_vault.lp_mint_bump,
+    )]
+    pub lp_mint: InterfaceAccount<'info, Mint>,
+
+    /// USDC liquidity held by the vault
+    #[account(
+        mut,
+        seeds = [b"lp_usdc_vault", token_mint.key().as_ref()],
+        bump = lp_vault.usdc_vault_bump,
+    )]
+    pub lp_usdc_vault: InterfaceAccount<'info, TokenAccount>,
+
+    /// Liquidity provider position, used for the withdrawal cooldown
+    #[account(
+        init_if_needed,
+        payer = provider,
+        space = 8 + 32 + 32 + 8 + 1,
+        seeds = [b"lp_position", provider.key().as_ref(), token_mint.key().as_ref()],
+        bump,
+    )]
+    pub lp_position: Account<'info, LpPosition>,
+
+    /// Collateral registry entry, required once the market has one
+    pub collateral_asset: Option<Account<'info, CollateralAsset>>,
+
+    /// Pyth price update for the collateral asset
+    /// CHECK: Validated through CollateralAsset::load_price
+    pub collateral_price_feed: Option<AccountInfo<'info>>,
+
+    /// USDC mint
+    #[account(
+        constraint = usdc_mint.key() == token_config.usdc_mint @ ContractError::InvalidOracleData,
+    )]
+    pub usdc_mint: InterfaceAccount<'info, Mint>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    /// Token-2022 program owning the LP mint
+    pub lp_token_program: Program<'info, Token2022>,
+    pub system_program: Program<'info, System>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+}
+
+pub fn deposit_liquidity(ctx: Context<DepositLiquidity>, amount: u64) -> Result<()> {
+    // Validate amount
+    require!(amount > 0, ContractError::InvalidAmount);
+
+    // Check if provider has enough USDC
+    require!(
+        ctx.accounts.provider_usdc_account.amount >= amount,
+        ContractError::InsufficientBalance
+    );
+
+    // Calculate LP shares against the vault's net assets, with open trader PnL marked to the oracle
+    let collateral_price = resolve_collateral_price(
+        &ctx.accounts.token_config,
+        &ctx.accounts.collateral_asset,
+        &ctx.accounts.collateral_price_feed,
+    )?;
+    let net_assets = lp_net_assets(
+        &ctx.accounts.token_config,
+        &ctx.accounts.lp_vault,
+        &ctx.accounts.oracle,
+        &collateral_price,
+    )?;
+    let share_supply = ctx.accounts.lp_mint.supply;
+    let shares = LpVault::shares_for_deposit(amount, share_supply, net_assets)?;
+    require!(shares > 0, ContractError::InvalidAmount);
+
+    msg!("LP shares to mint: {}", shares);
+
+    // Transfer USDC into the vault
+    token_interface::transfer_checked(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            TransferChecked {
+                from: ctx.accounts.provider_usdc_account.to_account_info(),
+                mint: ctx.accounts.usdc_mint.to_account_info(),
+                to: ctx.accounts.lp_usdc_vault.to_account_info(),
+                authority: ctx.accounts.provider.to_account_info(),
+            },
+        ),
+        amount,
+        ctx.accounts.usdc_mint.decimals,
+    )?;
+
+    // Mint LP shares to the provider
+    let token_mint_key = ctx.accounts.token_mint.key();
+    let lp_vault_seeds = &[
+        b"lp_vault",
+        token_mint_key.as_ref(),
+        &[ctx.accounts.lp_vault.bump]
+    ];
+    let signer = &[&lp_vault_seeds[..]];
+
+    token_interface::mint_to(
+        CpiContext::new_with_signer(
+            ctx.accounts.lp_token_program.to_account_info(),
+            token_interface::MintTo {
+                mint: ctx.accounts.lp_mint.to_account_info(),
+                to: ctx.accounts.provider_lp_account.to_account_info(),
+                authority: ctx.accounts.lp_vault.to_account_info(),
+            },
+            signer,
+        ),
+        shares,
+    )?;
+
+    ctx.accounts.lp_vault.credit(amount)?;
+
+    // Update or create LP position
+    let lp_position = &mut ctx.accounts.lp_position;
+
+    // If the position is being created for the first time
+    if lp_position.owner == Pubkey::default() {
+        lp_position.owner = ctx.accounts.provider.key();
+        lp_position.lp_vault = ctx.accounts.lp_vault.key();
+        lp_position.bump = ctx.bumps.lp_position;
+    }
+
+    // Every deposit restarts the withdrawal cooldown
+    lp_position.last_deposit_timestamp = Clock::get()?.unix_timestamp;
+
+    emit!(LiquidityDeposited {
+        token_mint: ctx.accounts.token_mint.key(),
+        provider: ctx.accounts.provider.key(),
+        amount,
+        shares,
+        vault_balance: ctx
+            .accounts
+            .lp_usdc_vault
+            .amount
+            .checked_add(amount)
+            .ok_or(ContractError::MathOverflow)?,
+        net_assets: net_assets
+            .checked_add(amount)
+            .ok_or(ContractError::MathOverflow)?,
+        share_supply: share_supply
+            .checked_add(shares)
+            .ok_or(ContractError::MathOverflow)?,
+    });
+
+    Ok(())
+}
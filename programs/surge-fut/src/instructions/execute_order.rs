@@ -0,0 +1,527 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{self, Burn, CloseAccount, Mint, TokenAccount, TokenInterface};
+
+use crate::{state::*, errors::ContractError, events::*, fees::*, trade::*};
+
+#[derive(Accounts)]
+pub struct ExecuteOrder<'info> {
+    /// Anyone may execute a triggered order and collect its keeper fee
+    #[account(mut)]
+    pub keeper: Signer<'info>,
+
+    /// USDC account receiving the keeper fee
+    #[account(
+        mut,
+        constraint = keeper_usdc_account.mint == token_config.usdc_mint @ ContractError::InvalidOracleData,
+    )]
+    pub keeper_usdc_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// Owner of the order, receives the order and escrow rent
+    /// CHECK: Validated against order.owner
+    #[account(
+        mut,
+        constraint = owner.key() == order.owner @ ContractError::Unauthorized,
+    )]
+    pub owner: AccountInfo<'info>,
+
+    /// USDC account of the owner receiving proceeds or the unused escrow
+    #[account(
+        mut,
+        constraint = owner_usdc_account.owner == owner.key() @ ContractError::Unauthorized,
+        constraint = owner_usdc_account.mint == token_config.usdc_mint @ ContractError::InvalidOracleData,
+    )]
+    pub owner_usdc_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// Volatility token account of the owner receiving minted tokens
+    #[account(
+        mut,
+        constraint = owner_token_account.owner == owner.key() @ ContractError::Unauthorized,
+        constraint = owner_token_account.mint == token_mint.key() @ ContractError::InvalidOracleData,
+    )]
+    pub owner_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// Order account, rent is returned to the owner
+    #[account(
+        mut,
+        close = owner,
+        seeds = [b"order", owner.key().as_ref(), token_mint.key().as_ref(), &order.order_id.to_le_bytes()],
+        bump = order.bump,
+    )]
+    pub order: Account<'info, Order>,
+
+    /// Escrow holding the order's USDC or tokens
+    #[account(
+        mut,
+        seeds = [b"order_escrow", order.key().as_ref()],
+        bump = order.escrow_bump,
+    )]
+    pub order_escrow: InterfaceAccount<'info, TokenAccount>,
+
+    /// Fee vault USDC account
+    #[account(
+        mut,
+        constraint = fee_vault.key() == token_config.fee_vault @ ContractError::Unauthorized,
+    )]
+    pub fee_vault: InterfaceAccount<'info, TokenAccount>,
+
+    /// Collateral pool USDC account
+    #[account(
+        mut,
+        seeds = [b"collateral_pool", token_mint.key().as_ref()],
+        bump = token_config.collateral_pool_bump,
+        constraint = collateral_pool.key() == token_config.collateral_pool @ ContractError::Unauthorized,
+    )]
+    pub collateral_pool: InterfaceAccount<'info, TokenAccount>,
+
+    /// Volatility token mint
+    #[account(
+        mut,
+        constraint = token_mint.key() == token_config.token_mint @ ContractError::InvalidOracleData,
+    )]
+    pub token_mint: InterfaceAccount<'info, Mint>,
+
+    /// Token Config
+    #[account(
+        mut,
+        seeds = [b"token_config", token_mint.key().as_ref()],
+        bump = token_config.bump,
+        constraint = !token_config.is_index_mode() @ ContractError::InvalidTokenMode,
+    )]
+    pub token_config: Account<'info, TokenConfig>,
+
+    /// User Position Account of the owner, reopened by a buy if it was closed
+    /// after the order was placed. The keeper is reimbursed from the order's reserve.
+    #[account(
+        init_if_needed,
+        payer = keeper,
+        space = USER_POSITION_SPACE,
+        seeds = [b"user_position", owner.key().as_ref(), token_mint.key().as_ref()],
+        bump,
+        constraint = user_position.owner == owner.key()
+            || user_position.owner == Pubkey::default() @ ContractError::Unauthorized,
+    )]
+    pub user_position: Account<'info, UserPosition>,
+
+    /// Cumulative trading volume of the owner, used for fee tiers
+    #[account(
+        mut,
+        seeds = [b"user_volume", owner.key().as_ref(), token_mint.key().as_ref()],
+        bump = user_volume.bump,
+    )]
+    pub user_volume: Account<'info, UserVolume>,
+
+    /// Oracle account with volatility data
+    /// CHECK: Account is validated through VolatilityStats::load_from_account_info
+    #[account(
+        constraint = oracle.key() == token_config.oracle @ ContractError::InvalidOracleData,
+    )]
+    pub oracle: AccountInfo<'info>,
+
+    /// LP vault acting as counterparty, required once the market has one
+    #[account(mut)]
+    pub lp_vault: Option<Account<'info, LpVault>>,
+
+    /// USDC liquidity held by the LP vault
+    #[account(mut)]
+    pub lp_usdc_vault: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Insurance fund USDC account, required once the market has one
+    #[account(mut)]
+    pub insurance_fund: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Collateral registry entry, required once the market has one
+    pub collateral_asset: Option<Account<'info, CollateralAsset>>,
+
+    /// Pyth price update for the collateral asset
+    /// CHECK: Validated through CollateralAsset::load_price
+    pub collateral_price_feed: Option<AccountInfo<'info>>,
+
+    /// USDC mint
+    #[account(
+        constraint = usdc_mint.key() == token_config.usdc_mint @ ContractError::InvalidOracleData,
+    )]
+    pub usdc_mint: InterfaceAccount<'info, Mint>,
+
+    /// Token program owning the volatility token mint
+    pub token_program: Interface<'info, TokenInterface>,
+    /// Token program owning the USDC mint
+    pub usdc_token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn execute_order(ctx: Context<ExecuteOrder>, max_oracle_age: Option<i64>) -> Result<()> {
+    // Orders only trade before expiry, expired positions redeem at settlement instead
+    require!(
+        !ctx.accounts.token_config.is_expired(Clock::get()?.unix_timestamp),
+        ContractError::TokenExpired
+    );
+
+    // Reject stale oracle data if the keeper asked for it
+    VolatilityStats::check_oracle_age(&ctx.accounts.oracle, max_oracle_age)?;
+
+    // Get current mark volatility from oracle and check the trigger
+    let current_volatility = VolatilityStats::load_mark_from_account_info(
+        &ctx.accounts.oracle,
+        ctx.accounts.token_config.twap_window,
+    )?;
+    msg!("Current volatility: {}", current_volatility);
+    require!(
+        ctx.accounts.order.is_triggered(current_volatility),
+        ContractError::OrderNotTriggered
+    );
+
+    // Reopen a position closed since the order was placed
+    if ctx.accounts.user_position.owner == Pubkey::default() {
+        reopen_position(ctx.accounts, ctx.bumps.user_position)?;
+    }
+
+    let (usdc_amount, fee_amount) = match ctx.accounts.order.side {
+        OrderSide::Buy => execute_buy(ctx.accounts, current_volatility)?,
+        OrderSide::Sell => execute_sell(ctx.accounts, current_volatility)?,
+    };
+
+    let position_tokens = ctx.accounts.user_position.tokens_minted;
+    let position_collateral = ctx.accounts.user_position.usdc_collateral;
+
+    // Return rent to the owner once a sell empties the position
+    if ctx.accounts.order.side == OrderSide::Sell {
+        close_if_empty(
+            &mut ctx.accounts.user_position,
+            ctx.accounts.owner.to_account_info(),
+            ctx.accounts.token_mint.key(),
+        )?;
+    }
+
+    // Close the escrow, rent goes back to the owner
+    let accounts = &ctx.accounts;
+    let owner_key = accounts.owner.key();
+    let token_mint_key = accounts.token_mint.key();
+    let order_id_bytes = accounts.order.order_id.to_le_bytes();
+    let order_seeds = &[
+        b"order",
+        owner_key.as_ref(),
+        token_mint_key.as_ref(),
+        order_id_bytes.as_ref(),
+        &[accounts.order.bump]
+    ];
+
+    let escrow_token_program = match accounts.order.side {
+        OrderSide::Buy => accounts.usdc_token_program.to_account_info(),
+        OrderSide::Sell => accounts.token_program.to_account_info(),
+    };
+
+    token_interface::close_account(CpiContext::new_with_signer(
+        escrow_token_program,
+        CloseAccount {
+            account: accounts.order_escrow.to_account_info(),
+            destination: accounts.owner.to_account_info(),
+            authority: accounts.order.to_account_info(),
+        },
+        &[&order_seeds[..]],
+    ))?;
+
+    msg!("Order {} executed", accounts.order.order_id);
+
+    emit!(OrderExecuted {
+        token_mint: token_mint_key,
+        owner: owner_key,
+        order: accounts.order.key(),
+        order_id: accounts.order.order_id,
+        keeper: accounts.keeper.key(),
+        side: accounts.order.side,
+        amount: accounts.order.amount,
+        volatility: current_volatility,
+        usdc_amount,
+        fee_amount,
+        keeper_fee: accounts.order.keeper_fee,
+        position_tokens,
+        position_collateral,
+        total_tokens_outstanding: accounts.token_config.total_tokens_outstanding,
+    });
+
+    Ok(())
+}
+
+/// Fills in a position the keeper just paid for, and reimburses the keeper
+/// out of the rent the order reserved for it at placement.
+fn reopen_position(accounts: &mut ExecuteOrder, bump: u8) -> Result<()> {
+    let user_position = &mut accounts.user_position;
+    user_position.owner = accounts.owner.key();
+    user_position.bump = bump;
+
+    let order_info = accounts.order.to_account_info();
+    let order_rent = Rent::get()?.minimum_balance(order_info.data_len());
+    let reserve = order_info
+        .lamports()
+        .saturating_sub(order_rent)
+        .min(user_position.to_account_info().lamports());
+    if reserve > 0 {
+        **order_info.try_borrow_mut_lamports()? -= reserve;
+        **accounts.keeper.to_account_info().try_borrow_mut_lamports()? += reserve;
+    }
+
+    Ok(())
+}
+
+/// Mints the order's tokens out of the escrowed USDC and refunds the rest.
+/// Returns the USDC paid including fees, and the fee.
+fn execute_buy(accounts: &mut ExecuteOrder, current_volatility: f64) -> Result<(u64, u64)> {
+    require!(
+        !accounts.token_config.is_paused(PAUSE_MINTS),
+        ContractError::MintsPaused
+    );
+
+    let amount = accounts.order.amount;
+
+    // Enforce open interest, position size and per-slot mint limits
+    let token_config = &accounts.token_config;
+    let limits = check_mint_limits(token_config, accounts.user_position.tokens_minted, amount)?;
+
+    // Price the mint exactly as a direct mint would
+    let collateral_price = resolve_collateral_price(
+        token_config,
+        &accounts.collateral_asset,
+        &accounts.collateral_price_feed,
+    )?;
+    let quote = quote_mint(
+        token_config,
+        &collateral_price,
+        &accounts.lp_vault,
+        &accounts.lp_usdc_vault,
+        &accounts.collateral_pool,
+        amount,
+        current_volatility,
+        accounts.user_volume.volume,
+        false,
+    )?;
+    let insurance_fund = resolve_insurance_fund(token_config, &accounts.insurance_fund)?;
+
+    // The order's limit caps what the escrow pays for the mint
+    require!(
+        quote.total_payment <= accounts.order.limit_usdc,
+        ContractError::SlippageMaxInExceeded
+    );
+
+    let owner_key = accounts.owner.key();
+    let token_mint_key = accounts.token_mint.key();
+    let order_id_bytes = accounts.order.order_id.to_le_bytes();
+    let order_seeds = &[
+        b"order",
+        owner_key.as_ref(),
+        token_mint_key.as_ref(),
+        order_id_bytes.as_ref(),
+        &[accounts.order.bump]
+    ];
+    let order_signer = &[&order_seeds[..]];
+
+    // Pay the fee out of escrow
+    distribute_fees(
+        &accounts.usdc_token_program.to_account_info(),
+        &accounts.order_escrow.to_account_info(),
+        &accounts.usdc_mint,
+        &accounts.order.to_account_info(),
+        order_signer,
+        FeeRecipients {
+            fee_vault: accounts.fee_vault.to_account_info(),
+            lp_usdc_vault: accounts.lp_usdc_vault.as_ref().map(|a| a.to_account_info()),
+            insurance_fund: insurance_fund.map(|a| a.to_account_info()),
+            referrer: None,
+        },
+        &quote.fee,
+        token_mint_key,
+        owner_key,
+    )?;
+
+    let escrow_transfers = [
+        (accounts.collateral_pool.to_account_info(), quote.collateral),
+        (accounts.keeper_usdc_account.to_account_info(), accounts.order.keeper_fee),
+    ];
+    for (to, amount) in escrow_transfers {
+        transfer_usdc(
+            accounts.usdc_token_program.to_account_info(),
+            &accounts.usdc_mint,
+            accounts.order_escrow.to_account_info(),
+            to,
+            accounts.order.to_account_info(),
+            order_signer,
+            amount,
+        )?;
+    }
+
+    // Refund whatever the escrow holds beyond the execution cost
+    accounts.order_escrow.reload()?;
+    transfer_usdc(
+        accounts.usdc_token_program.to_account_info(),
+        &accounts.usdc_mint,
+        accounts.order_escrow.to_account_info(),
+        accounts.owner_usdc_account.to_account_info(),
+        accounts.order.to_account_info(),
+        order_signer,
+        accounts.order_escrow.amount,
+    )?;
+
+    // Mint volatility tokens to the owner
+    mint_volatility_tokens(
+        accounts.token_program.to_account_info(),
+        &accounts.token_mint,
+        accounts.owner_token_account.to_account_info(),
+        &accounts.token_config,
+        amount,
+    )?;
+
+    let volume_bump = accounts.user_volume.bump;
+    record_mint_fill(
+        &mut accounts.token_config,
+        &mut accounts.lp_vault,
+        &mut accounts.user_position,
+        &mut accounts.user_volume,
+        owner_key,
+        volume_bump,
+        &limits,
+        &quote,
+        amount,
+        current_volatility,
+    )?;
+
+    Ok((quote.total_payment, quote.fee.total))
+}
+
+/// Redeems the escrowed tokens against the owner's position.
+/// Returns the USDC paid to the owner, and the fee.
+fn execute_sell(accounts: &mut ExecuteOrder, current_volatility: f64) -> Result<(u64, u64)> {
+    require!(
+        !accounts.token_config.is_paused(PAUSE_REDEEMS),
+        ContractError::RedeemsPaused
+    );
+
+    let amount = accounts.order.amount;
+    require!(
+        accounts.user_position.tokens_minted >= amount,
+        ContractError::InsufficientTokens
+    );
+
+    // Price the redemption exactly as a direct redeem would
+    let collateral_price = resolve_collateral_price(
+        &accounts.token_config,
+        &accounts.collateral_asset,
+        &accounts.collateral_price_feed,
+    )?;
+    let quote = quote_redeem(
+        &accounts.token_config,
+        &collateral_price,
+        &accounts.lp_vault,
+        &accounts.lp_usdc_vault,
+        amount,
+        accounts.user_position.entry_volatility,
+        current_volatility,
+        accounts.user_volume.volume,
+        false,
+    )?;
+    let insurance_fund = resolve_insurance_fund(&accounts.token_config, &accounts.insurance_fund)?;
+
+    // The keeper fee comes out of the proceeds, the rest must meet the order's limit
+    let final_amount = quote
+        .collateral
+        .checked_sub(quote.fee.total)
+        .and_then(|rest| rest.checked_sub(accounts.order.keeper_fee))
+        .ok_or(ContractError::SlippageMinOutNotMet)?;
+    require!(
+        final_amount >= accounts.order.limit_usdc,
+        ContractError::SlippageMinOutNotMet
+    );
+
+    // Portion of the position's collateral backing the redeemed tokens
+    let collateral_reduction = accounts.user_position.collateral_for(amount)?;
+
+    fund_redemption(
+        accounts.usdc_token_program.to_account_info(),
+        &accounts.usdc_mint,
+        &accounts.token_config,
+        &mut accounts.collateral_pool,
+        &mut accounts.lp_vault,
+        &accounts.lp_usdc_vault,
+        quote.collateral,
+        collateral_reduction,
+    )?;
+
+    // Burn the escrowed volatility tokens
+    let owner_key = accounts.owner.key();
+    let token_mint_key = accounts.token_mint.key();
+    let order_id_bytes = accounts.order.order_id.to_le_bytes();
+    let order_seeds = &[
+        b"order",
+        owner_key.as_ref(),
+        token_mint_key.as_ref(),
+        order_id_bytes.as_ref(),
+        &[accounts.order.bump]
+    ];
+
+    token_interface::burn(
+        CpiContext::new_with_signer(
+            accounts.token_program.to_account_info(),
+            Burn {
+                mint: accounts.token_mint.to_account_info(),
+                from: accounts.order_escrow.to_account_info(),
+                authority: accounts.order.to_account_info(),
+            },
+            &[&order_seeds[..]],
+        ),
+        amount,
+    )?;
+
+    // Pay the fee, the keeper and the owner out of the collateral pool
+    let token_config_seeds = &[
+        b"token_config",
+        token_mint_key.as_ref(),
+        &[accounts.token_config.bump]
+    ];
+    let signer = &[&token_config_seeds[..]];
+
+    distribute_fees(
+        &accounts.usdc_token_program.to_account_info(),
+        &accounts.collateral_pool.to_account_info(),
+        &accounts.usdc_mint,
+        &accounts.token_config.to_account_info(),
+        signer,
+        FeeRecipients {
+            fee_vault: accounts.fee_vault.to_account_info(),
+            lp_usdc_vault: accounts.lp_usdc_vault.as_ref().map(|a| a.to_account_info()),
+            insurance_fund: insurance_fund.map(|a| a.to_account_info()),
+            referrer: None,
+        },
+        &quote.fee,
+        token_mint_key,
+        owner_key,
+    )?;
+
+    let pool_transfers = [
+        (accounts.keeper_usdc_account.to_account_info(), accounts.order.keeper_fee),
+        (accounts.owner_usdc_account.to_account_info(), final_amount),
+    ];
+    for (to, amount) in pool_transfers {
+        transfer_usdc(
+            accounts.usdc_token_program.to_account_info(),
+            &accounts.usdc_mint,
+            accounts.collateral_pool.to_account_info(),
+            to,
+            accounts.token_config.to_account_info(),
+            signer,
+            amount,
+        )?;
+    }
+
+    let volume_bump = accounts.user_volume.bump;
+    record_redeem_fill(
+        &mut accounts.token_config,
+        &mut accounts.lp_vault,
+        &mut accounts.user_position,
+        &mut accounts.user_volume,
+        owner_key,
+        volume_bump,
+        &quote,
+        amount,
+        collateral_reduction,
+    )?;
+
+    Ok((final_amount, quote.fee.total))
+}
@@ -1,11 +1,15 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::{Mint, Token, TokenAccount};
+use anchor_lang::system_program::{self, Transfer};
+use anchor_spl::token_interface::{
+    self, spl_token_metadata_interface::state::TokenMetadata, Mint, Token2022,
+    TokenAccount, TokenInterface, TokenMetadataInitialize,
+};
 use anchor_spl::associated_token::AssociatedToken;
 
-use crate::{state::*, errors::ContractError};
+use crate::{state::*, errors::ContractError, events::*};
 
 #[derive(Accounts)]
-#[instruction(token_name: String, token_symbol: String, fee_bps: u16)]
+#[instruction(token_name: String, token_symbol: String, fee_bps: u16, expiry_timestamp: i64, vol_cap: f64, token_uri: String)]
 pub struct Initialize<'info> {
     #[account(mut)]
     pub authority: Signer<'info>,
@@ -15,22 +19,37 @@ pub struct Initialize<'info> {
         payer = authority,
         mint::decimals = 6,
         mint::authority = token_config,
+        mint::token_program = token_program,
+        extensions::metadata_pointer::authority = token_config,
+        extensions::metadata_pointer::metadata_address = token_mint,
     )]
-    pub token_mint: Account<'info, Mint>,
+    pub token_mint: InterfaceAccount<'info, Mint>,
     
-    /// USDC mint
+    /// Collateral mint, 6-decimal USDC unless a collateral asset is supplied.
+    /// May belong to either the legacy token program or Token-2022.
     #[account(
-        constraint = usdc_mint.decimals == 6 @ ContractError::InvalidOracleData,
+        mint::token_program = usdc_token_program,
     )]
-    pub usdc_mint: Account<'info, Mint>,
+    pub usdc_mint: InterfaceAccount<'info, Mint>,
     
-    /// Fee destination account - must be an existing USDC token account owned by authority
+    /// Collateral registry entry pricing usdc_mint, omit for USDC
     #[account(
-        mut,
-        constraint = fee_destination.mint == usdc_mint.key() @ ContractError::InvalidOracleData,
-        constraint = fee_destination.owner == authority.key() @ ContractError::Unauthorized,
+        constraint = collateral_asset.mint == usdc_mint.key() @ ContractError::InvalidCollateralAsset,
+        constraint = collateral_asset.authority == authority.key() @ ContractError::Unauthorized,
     )]
-    pub fee_destination: Account<'info, TokenAccount>,
+    pub collateral_asset: Option<Account<'info, CollateralAsset>>,
+    
+    /// Fee vault account, protocol fees are only released through withdraw_fees
+    #[account(
+        init,
+        payer = authority,
+        seeds = [b"fee_vault", token_mint.key().as_ref()],
+        bump,
+        token::mint = usdc_mint,
+        token::authority = token_config,
+        token::token_program = usdc_token_program,
+    )]
+    pub fee_vault: InterfaceAccount<'info, TokenAccount>,
     
     /// Collateral pool account
     #[account(
@@ -40,8 +59,9 @@ pub struct Initialize<'info> {
         bump,
         token::mint = usdc_mint,
         token::authority = token_config,
+        token::token_program = usdc_token_program,
     )]
-    pub collateral_pool: Account<'info, TokenAccount>,
+    pub collateral_pool: InterfaceAccount<'info, TokenAccount>,
     
     /// Oracle account with volatility data
     /// CHECK: This account is validated in the handler
@@ -50,13 +70,16 @@ pub struct Initialize<'info> {
     #[account(
         init,
         payer = authority,
-        space = 8 + 32 + 32 + 32 + 32 + 32 + 32 + 2 + 32 + 8 + 8 + 1 + 1 + 200, // Extra space for name/symbol and collateral_pool_bump
+        space = TOKEN_CONFIG_SPACE,
         seeds = [b"token_config", token_mint.key().as_ref()],
         bump
     )]
     pub token_config: Account<'info, TokenConfig>,
     
-    pub token_program: Program<'info, Token>,
+    /// Token-2022 program owning the volatility token mint
+    pub token_program: Program<'info, Token2022>,
+    /// Token program owning the USDC mint
+    pub usdc_token_program: Interface<'info, TokenInterface>,
     pub associated_token_program: Program<'info, AssociatedToken>,
     pub system_program: Program<'info, System>,
     pub rent: Sysvar<'info, Rent>,
@@ -67,10 +90,31 @@ pub fn initialize(
     token_name: String,
     token_symbol: String,
     fee_bps: u16,
+    expiry_timestamp: i64,
+    vol_cap: f64,
+    token_uri: String,
 ) -> Result<()> {
     // Validate fee percentage
     require!(fee_bps <= 10000, ContractError::InvalidFeePercentage);
     
+    // Dated tokens must expire in the future, 0 creates a perpetual token
+    require!(
+        expiry_timestamp == 0 || expiry_timestamp > Clock::get()?.unix_timestamp,
+        ContractError::InvalidExpiry
+    );
+    
+    // A positive cap creates a fungible index token, 0 keeps per-user positions
+    require!(vol_cap.is_finite() && vol_cap >= 0.0, ContractError::InvalidVolCap);
+    
+    // Without a registry entry the collateral must be 6-decimal USDC
+    let collateral_asset = match &ctx.accounts.collateral_asset {
+        Some(collateral_asset) => collateral_asset.key(),
+        None => {
+            require!(ctx.accounts.usdc_mint.decimals == 6, ContractError::InvalidOracleData);
+            Pubkey::default()
+        }
+    };
+    
     // Try to get the current volatility from oracle
     let volatility = VolatilityStats::load_from_account_info(&ctx.accounts.oracle)?;
     msg!("Current volatility: {}", volatility);
@@ -83,18 +127,113 @@ pub fn initialize(
     token_config.authority = ctx.accounts.authority.key();
     token_config.token_mint = ctx.accounts.token_mint.key();
     token_config.usdc_mint = ctx.accounts.usdc_mint.key();
-    token_config.fee_destination = ctx.accounts.fee_destination.key();
+    token_config.fee_vault = ctx.accounts.fee_vault.key();
+    token_config.fee_vault_bump = ctx.bumps.fee_vault;
     token_config.collateral_pool = ctx.accounts.collateral_pool.key();
     token_config.collateral_pool_bump = ctx.bumps.collateral_pool;
     token_config.token_name = token_name;
     token_config.token_symbol = token_symbol;
-    token_config.fee_bps = fee_bps;
+    token_config.mint_fee_bps = fee_bps;
+    token_config.redeem_fee_bps = fee_bps;
     token_config.oracle = ctx.accounts.oracle.key();
     token_config.total_tokens_outstanding = 0;
     token_config.usdc_per_vol_point = 100_000; // 0.1 USDC per 0.001 volatility point (adjustable)
     token_config.bump = ctx.bumps.token_config;
+    token_config.lp_vault = Pubkey::default();
+    token_config.pending_authority = Pubkey::default();
+    token_config.guardian = ctx.accounts.authority.key();
+    token_config.pause_flags = 0;
+    token_config.expiry_timestamp = expiry_timestamp;
+    token_config.settlement_volatility = 0.0;
+    token_config.is_settled = false;
+    token_config.twap_window = 0;
+    token_config.max_open_interest = 0;
+    token_config.max_position_size = 0;
+    token_config.max_mint_per_slot = 0;
+    token_config.last_mint_slot = 0;
+    token_config.minted_in_slot = 0;
+    token_config.vol_cap = vol_cap;
+    token_config.fee_tiers = [FeeTier::default(); MAX_FEE_TIERS];
+    token_config.referral_share_bps = 0;
+    token_config.insurance_fund = Pubkey::default();
+    token_config.insurance_share_bps = 0;
+    token_config.fee_withdrawal_policy = FeeWithdrawalPolicy::default();
+    token_config.pending_fee_withdrawal = PendingFeeWithdrawal::default();
+    token_config.pending_fee_withdrawal_policy = FeeWithdrawalPolicy::default();
+    token_config.pending_fee_withdrawal_policy_unlock = 0;
+    token_config.collateral_asset = collateral_asset;
+    token_config.pending_fee_split = PendingFeeSplit::default();
     
     msg!("Token config initialized successfully: {}", token_config.token_name);
     
+    // Write name, symbol and URI into the mint so wallets can display the token
+    let token_metadata = TokenMetadata {
+        name: token_config.token_name.clone(),
+        symbol: token_config.token_symbol.clone(),
+        uri: token_uri.clone(),
+        ..Default::default()
+    };
+    let mint_info = ctx.accounts.token_mint.to_account_info();
+    let required_lamports = Rent::get()?
+        .minimum_balance(mint_info.data_len() + token_metadata.tlv_size_of()?)
+        .saturating_sub(mint_info.lamports());
+    if required_lamports > 0 {
+        system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.authority.to_account_info(),
+                    to: mint_info.clone(),
+                },
+            ),
+            required_lamports,
+        )?;
+    }
+    
+    let token_mint_key = ctx.accounts.token_mint.key();
+    let token_config_seeds = &[
+        b"token_config",
+        token_mint_key.as_ref(),
+        &[ctx.bumps.token_config]
+    ];
+    
+    token_interface::token_metadata_initialize(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            TokenMetadataInitialize {
+                program_id: ctx.accounts.token_program.to_account_info(),
+                metadata: mint_info.clone(),
+                update_authority: ctx.accounts.token_config.to_account_info(),
+                mint_authority: ctx.accounts.token_config.to_account_info(),
+                mint: mint_info,
+            },
+            &[&token_config_seeds[..]],
+        ),
+        token_metadata.name,
+        token_metadata.symbol,
+        token_metadata.uri,
+    )?;
+    
+    let token_config = &ctx.accounts.token_config;
+    
+    emit!(TokenConfigInitialized {
+        token_config: token_config.key(),
+        authority: token_config.authority,
+        token_mint: token_config.token_mint,
+        usdc_mint: token_config.usdc_mint,
+        collateral_asset,
+        fee_vault: token_config.fee_vault,
+        collateral_pool: token_config.collateral_pool,
+        oracle: token_config.oracle,
+        token_name: token_config.token_name.clone(),
+        token_symbol: token_config.token_symbol.clone(),
+        fee_bps,
+        usdc_per_vol_point: token_config.usdc_per_vol_point,
+        start_volatility: volatility,
+        expiry_timestamp,
+        vol_cap,
+        token_uri,
+    });
+    
     Ok(())
 }
\ No newline at end of file
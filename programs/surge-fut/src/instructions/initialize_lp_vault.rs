@@ -0,0 +1,190 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{self, CreateAccount};
+use anchor_spl::token_2022::{spl_token_2022, Token2022};
+use anchor_spl::token_2022::spl_token_2022::extension::ExtensionType;
+use anchor_spl::token_interface::{
+    self, InitializeMint2, Mint, NonTransferableMintInitialize, TokenAccount, TokenInterface,
+};
+
+use crate::{state::*, errors::ContractError, events::*};
+
+#[derive(Accounts)]
+#[instruction(fee_share_bps: u16, max_utilization_bps: u16, withdrawal_cooldown: i64)]
+pub struct InitializeLpVault<'info> {
+    #[account(
+        mut,
+        constraint = authority.key() == token_config.authority @ ContractError::Unauthorized,
+    )]
+    pub authority: Signer<'info>,
+
+    /// Token Config
+    #[account(
+        mut,
+        seeds = [b"token_config", token_mint.key().as_ref()],
+        bump = token_config.bump,
+        constraint = token_config.lp_vault == Pubkey::default() @ ContractError::LpVaultAlreadyInitialized,
+    )]
+    pub token_config: Account<'info, TokenConfig>,
+
+    /// Volatility token mint
+    #[account(
+        constraint = token_mint.key() == token_config.token_mint @ ContractError::InvalidOracleData,
+    )]
+    pub token_mint: InterfaceAccount<'info, Mint>,
+
+    /// USDC mint
+    #[account(
+        constraint = usdc_mint.key() == token_config.usdc_mint @ ContractError::InvalidOracleData,
+    )]
+    pub usdc_mint: InterfaceAccount<'info, Mint>,
+
+    /// Collateral pool USDC account, its balance is the trader collateral the vault takes on
+    #[account(
+        seeds = [b"collateral_pool", token_mint.key().as_ref()],
+        bump = token_config.collateral_pool_bump,
+        constraint = collateral_pool.key() == token_config.collateral_pool @ ContractError::Unauthorized,
+    )]
+    pub collateral_pool: InterfaceAccount<'info, TokenAccount>,
+
+    /// LP vault state
+    #[account(
+        init,
+        payer = authority,
+        space = LP_VAULT_SPACE,
+        seeds = [b"lp_vault", token_mint.key().as_ref()],
+        bump,
+    )]
+    pub lp_vault: Account<'info, LpVault>,
+
+    /// LP share token mint, created non-transferable under Token-2022 so shares
+    /// cannot be moved to a fresh wallet to skip the withdrawal cooldown
+    /// CHECK: Created and initialized in the handler
+    #[account(
+        mut,
+        seeds = [b"lp_mint", token_mint.key().as_ref()],
+        bump,
+    )]
+    pub lp_mint: AccountInfo<'info>,
+
+    /// USDC liquidity held by the vault
+    #[account(
+        init,
+        payer = authority,
+        seeds = [b"lp_usdc_vault", token_mint.key().as_ref()],
+        bump,
+        token::mint = usdc_mint,
+        token::authority = lp_vault,
+        token::token_program = token_program,
+    )]
+    pub lp_usdc_vault: InterfaceAccount<'info, TokenAccount>,
+
+    /// Token program owning the USDC mint
+    pub token_program: Interface<'info, TokenInterface>,
+    /// Token-2022 program owning the LP mint
+    pub lp_token_program: Program<'info, Token2022>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+pub fn initialize_lp_vault(
+    ctx: Context<InitializeLpVault>,
+    fee_share_bps: u16,
+    max_utilization_bps: u16,
+    withdrawal_cooldown: i64,
+) -> Result<()> {
+    // Validate vault parameters
+    require!(fee_share_bps <= 10000, ContractError::InvalidFeePercentage);
+    require!(
+        max_utilization_bps > 0 && max_utilization_bps <= 10000,
+        ContractError::InvalidUtilizationLimit
+    );
+    require!(withdrawal_cooldown >= 0, ContractError::InvalidAmount);
+    if ctx.accounts.token_config.insurance_fund != Pubkey::default() {
+        require!(
+            fee_share_bps as u32 + ctx.accounts.token_config.insurance_share_bps as u32 <= 10000,
+            ContractError::InvalidFeeSplit
+        );
+    }
+
+    create_lp_mint(&ctx)?;
+
+    let lp_vault = &mut ctx.accounts.lp_vault;
+    lp_vault.token_config = ctx.accounts.token_config.key();
+    lp_vault.lp_mint = ctx.accounts.lp_mint.key();
+    lp_vault.usdc_vault = ctx.accounts.lp_usdc_vault.key();
+    lp_vault.fee_share_bps = fee_share_bps;
+    lp_vault.max_utilization_bps = max_utilization_bps;
+    lp_vault.withdrawal_cooldown = withdrawal_cooldown;
+    lp_vault.total_fees_earned = 0;
+    lp_vault.usdc_vault_bump = ctx.bumps.lp_usdc_vault;
+    lp_vault.lp_mint_bump = ctx.bumps.lp_mint;
+    lp_vault.bump = ctx.bumps.lp_vault;
+    lp_vault.total_assets = 0;
+    lp_vault.trader_collateral = ctx.accounts.collateral_pool.amount;
+
+    // From now on mints and redeems settle against the vault
+    ctx.accounts.token_config.lp_vault = lp_vault.key();
+
+    msg!("LP vault initialized for: {}", ctx.accounts.token_config.token_name);
+
+    emit!(LpVaultInitialized {
+        token_mint: ctx.accounts.token_mint.key(),
+        lp_vault: lp_vault.key(),
+        lp_mint: lp_vault.lp_mint,
+        usdc_vault: lp_vault.usdc_vault,
+        fee_share_bps,
+        max_utilization_bps,
+        withdrawal_cooldown,
+    });
+
+    Ok(())
+}
+
+/// Creates the LP mint with the non-transferable extension, which Anchor has no
+/// account constraint for
+fn create_lp_mint(ctx: &Context<InitializeLpVault>) -> Result<()> {
+    let space = ExtensionType::try_calculate_account_len::<spl_token_2022::state::Mint>(&[
+        ExtensionType::NonTransferable,
+    ])?;
+
+    let token_mint_key = ctx.accounts.token_mint.key();
+    let lp_mint_seeds = &[
+        b"lp_mint",
+        token_mint_key.as_ref(),
+        &[ctx.bumps.lp_mint]
+    ];
+
+    system_program::create_account(
+        CpiContext::new_with_signer(
+            ctx.accounts.system_program.to_account_info(),
+            CreateAccount {
+                from: ctx.accounts.authority.to_account_info(),
+                to: ctx.accounts.lp_mint.to_account_info(),
+            },
+            &[&lp_mint_seeds[..]],
+        ),
+        ctx.accounts.rent.minimum_balance(space),
+        space as u64,
+        &ctx.accounts.lp_token_program.key(),
+    )?;
+
+    token_interface::non_transferable_mint_initialize(CpiContext::new(
+        ctx.accounts.lp_token_program.to_account_info(),
+        NonTransferableMintInitialize {
+            token_program_id: ctx.accounts.lp_token_program.to_account_info(),
+            mint: ctx.accounts.lp_mint.to_account_info(),
+        },
+    ))?;
+
+    token_interface::initialize_mint2(
+        CpiContext::new(
+            ctx.accounts.lp_token_program.to_account_info(),
+            InitializeMint2 {
+                mint: ctx.accounts.lp_mint.to_account_info(),
+            },
+        ),
+        6,
+        &ctx.accounts.lp_vault.key(),
+        None,
+    )
+}
@@ -0,0 +1,163 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{self, Transfer};
+use anchor_lang::Discriminator;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::{state::*, errors::ContractError, events::*};
+
+/// TokenConfig as laid out by the first deployment of the program
+#[derive(AnchorDeserialize)]
+struct LegacyTokenConfig {
+    authority: Pubkey,
+    token_mint: Pubkey,
+    usdc_mint: Pubkey,
+    _fee_destination: Pubkey,        // Fees were paid straight to this account, nothing is left to move
+    collateral_pool: Pubkey,
+    token_name: String,
+    token_symbol: String,
+    fee_bps: u16,
+    oracle: Pubkey,
+    total_tokens_outstanding: u64,
+    usdc_per_vol_point: u64,
+    collateral_pool_bump: u8,
+    bump: u8,
+}
+
+#[derive(Accounts)]
+pub struct MigrateTokenConfig<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    /// Volatility token mint
+    pub token_mint: InterfaceAccount<'info, Mint>,
+
+    /// Token Config in the legacy layout
+    /// CHECK: Owner, discriminator and authority are checked before the account is
+    /// resized, it cannot be deserialized as a TokenConfig until then
+    #[account(
+        mut,
+        seeds = [b"token_config", token_mint.key().as_ref()],
+        bump,
+        owner = crate::ID,
+    )]
+    pub token_config: UncheckedAccount<'info>,
+
+    /// USDC mint, checked against the legacy config
+    #[account(
+        mint::token_program = usdc_token_program,
+    )]
+    pub usdc_mint: InterfaceAccount<'info, Mint>,
+
+    /// Fee vault replacing the legacy fee destination
+    #[account(
+        init,
+        payer = authority,
+        seeds = [b"fee_vault", token_mint.key().as_ref()],
+        bump,
+        token::mint = usdc_mint,
+        token::authority = token_config,
+        token::token_program = usdc_token_program,
+    )]
+    pub fee_vault: InterfaceAccount<'info, TokenAccount>,
+
+    /// Token program owning the USDC mint
+    pub usdc_token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Rewrites a token config created by the first deployment in the current layout.
+/// Added fields start out as `initialize` sets them, with the legacy fee applying
+/// to both mints and redemptions.
+pub fn migrate_token_config(ctx: Context<MigrateTokenConfig>) -> Result<()> {
+    let info = ctx.accounts.token_config.to_account_info();
+    let old_size = info.data_len();
+    require!(old_size < TOKEN_CONFIG_SPACE, ContractError::AlreadyMigrated);
+
+    let legacy = {
+        let data = info.try_borrow_data()?;
+        require!(
+            data.len() >= 8 && data[..8] == *TokenConfig::DISCRIMINATOR,
+            ErrorCode::AccountDiscriminatorMismatch
+        );
+        LegacyTokenConfig::deserialize(&mut &data[8..])?
+    };
+    require!(legacy.authority == ctx.accounts.authority.key(), ContractError::Unauthorized);
+    require!(
+        legacy.token_mint == ctx.accounts.token_mint.key()
+            && legacy.usdc_mint == ctx.accounts.usdc_mint.key(),
+        ContractError::InvalidOracleData
+    );
+
+    let token_config = TokenConfig {
+        authority: legacy.authority,
+        token_mint: legacy.token_mint,
+        usdc_mint: legacy.usdc_mint,
+        fee_vault: ctx.accounts.fee_vault.key(),
+        collateral_pool: legacy.collateral_pool,
+        token_name: legacy.token_name,
+        token_symbol: legacy.token_symbol,
+        mint_fee_bps: legacy.fee_bps,
+        oracle: legacy.oracle,
+        total_tokens_outstanding: legacy.total_tokens_outstanding,
+        usdc_per_vol_point: legacy.usdc_per_vol_point,
+        collateral_pool_bump: legacy.collateral_pool_bump,
+        bump: legacy.bump,
+        lp_vault: Pubkey::default(),
+        pending_authority: Pubkey::default(),
+        guardian: legacy.authority,
+        pause_flags: 0,
+        expiry_timestamp: 0,
+        settlement_volatility: 0.0,
+        is_settled: false,
+        twap_window: 0,
+        max_open_interest: 0,
+        max_position_size: 0,
+        max_mint_per_slot: 0,
+        last_mint_slot: 0,
+        minted_in_slot: 0,
+        vol_cap: 0.0,
+        redeem_fee_bps: legacy.fee_bps,
+        fee_tiers: [FeeTier::default(); MAX_FEE_TIERS],
+        referral_share_bps: 0,
+        insurance_fund: Pubkey::default(),
+        insurance_share_bps: 0,
+        fee_vault_bump: ctx.bumps.fee_vault,
+        fee_withdrawal_policy: FeeWithdrawalPolicy::default(),
+        pending_fee_withdrawal: PendingFeeWithdrawal::default(),
+        pending_fee_withdrawal_policy: FeeWithdrawalPolicy::default(),
+        pending_fee_withdrawal_policy_unlock: 0,
+        collateral_asset: Pubkey::default(),
+        pending_fee_split: PendingFeeSplit::default(),
+    };
+
+    // Pay the rent for the added space and grow the account
+    let required_lamports = Rent::get()?
+        .minimum_balance(TOKEN_CONFIG_SPACE)
+        .saturating_sub(info.lamports());
+    if required_lamports > 0 {
+        system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.authority.to_account_info(),
+                    to: info.clone(),
+                },
+            ),
+            required_lamports,
+        )?;
+    }
+    info.realloc(TOKEN_CONFIG_SPACE, true)?;
+    token_config.try_serialize(&mut &mut info.try_borrow_mut_data()?[..])?;
+
+    msg!("Token config migrated from {} to {} bytes", old_size, TOKEN_CONFIG_SPACE);
+
+    emit!(TokenConfigMigrated {
+        token_mint: token_config.token_mint,
+        authority: token_config.authority,
+        fee_vault: token_config.fee_vault,
+        old_size: old_size as u64,
+        new_size: TOKEN_CONFIG_SPACE as u64,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,328 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked};
+use anchor_spl::associated_token::AssociatedToken;
+
+use crate::{state::*, errors::ContractError, events::*, fees::*, trade::*};
+
+#[derive(Accounts)]
+#[instruction(amount: u64, max_usdc_in: u64, max_oracle_age: Option<i64>)]
+pub struct MintIndexTokens<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    /// USDC token account of the user
+    #[account(
+        mut,
+        constraint = user_usdc_account.owner == user.key() @ ContractError::Unauthorized,
+        constraint = user_usdc_account.mint == token_config.usdc_mint @ ContractError::InvalidOracleData,
+    )]
+    pub user_usdc_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// Fee vault USDC account
+    #[account(
+        mut,
+        constraint = fee_vault.key() == token_config.fee_vault @ ContractError::Unauthorized,
+    )]
+    pub fee_vault: InterfaceAccount<'info, TokenAccount>,
+
+    /// Collateral pool USDC account
+    #[account(
+        mut,
+        seeds = [b"collateral_pool", token_mint.key().as_ref()],
+        bump = token_config.collateral_pool_bump,
+        constraint = collateral_pool.key() == token_config.collateral_pool @ ContractError::Unauthorized,
+    )]
+    pub collateral_pool: InterfaceAccount<'info, TokenAccount>,
+
+    /// Volatility token account of the user
+    #[account(
+        init_if_needed,
+        payer = user,
+        associated_token::mint = token_mint,
+        associated_token::authority = user,
+        associated_token::token_program = token_program,
+    )]
+    pub user_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// Volatility token mint
+    #[account(
+        mut,
+        constraint = token_mint.key() == token_config.token_mint @ ContractError::InvalidOracleData,
+    )]
+    pub token_mint: InterfaceAccount<'info, Mint>,
+
+    /// Token Config
+    #[account(
+        mut,
+        seeds = [b"token_config", token_mint.key().as_ref()],
+        bump = token_config.bump,
+        constraint = token_config.is_index_mode() @ ContractError::InvalidTokenMode,
+        constraint = !token_config.is_paused(PAUSE_MINTS) @ ContractError::MintsPaused,
+    )]
+    pub token_config: Account<'info, TokenConfig>,
+
+    /// Oracle account with volatility data
+    /// CHECK: Account is validated through VolatilityStats::load_from_account_info
+    #[account(
+        constraint = oracle.key() == token_config.oracle @ ContractError::InvalidOracleData,
+    )]
+    pub oracle: AccountInfo<'info>,
+
+    /// LP vault collateralizing index tokens up to the cap
+    #[account(
+        mut,
+        seeds = [b"lp_vault", token_mint.key().as_ref()],
+        bump = lp_vault.bump,
+        constraint = lp_vault.key() == token_config.lp_vault @ ContractError::LpVaultRequired,
+    )]
+    pub lp_vault: Account<'info, LpVault>,
+
+    /// USDC liquidity held by the LP vault
+    #[account(
+        mut,
+        seeds = [b"lp_usdc_vault", token_mint.key().as_ref()],
+        bump = lp_vault.usdc_vault_bump,
+    )]
+    pub lp_usdc_vault: InterfaceAccount<'info, TokenAccount>,
+
+    /// Cumulative trading volume of the user, used for fee tiers
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = USER_VOLUME_SPACE,
+        seeds = [b"user_volume", user.key().as_ref(), token_mint.key().as_ref()],
+        bump,
+    )]
+    pub user_volume: Account<'info, UserVolume>,
+
+    /// Registered referrer, bound to the trader on its first referred trade
+    pub referrer: Option<Account<'info, Referrer>>,
+
+    /// USDC account of the referrer receiving a fee rebate
+    #[account(mut)]
+    pub referrer_usdc_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Insurance fund USDC account, required once the market has one
+    #[account(mut)]
+    pub insurance_fund: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Collateral registry entry, required once the market has one
+    pub collateral_asset: Option<Account<'info, CollateralAsset>>,
+
+    /// Pyth price update for the collateral asset
+    /// CHECK: Validated through CollateralAsset::load_price
+    pub collateral_price_feed: Option<AccountInfo<'info>>,
+
+    /// USDC mint
+    #[account(
+        constraint = usdc_mint.key() == token_config.usdc_mint @ ContractError::InvalidOracleData,
+    )]
+    pub usdc_mint: InterfaceAccount<'info, Mint>,
+
+    /// Token program owning the volatility token mint
+    pub token_program: Interface<'info, TokenInterface>,
+    /// Token program owning the USDC mint
+    pub usdc_token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+}
+
+pub fn mint_index_tokens(
+    ctx: Context<MintIndexTokens>,
+    amount: u64,
+    max_usdc_in: u64,
+    max_oracle_age: Option<i64>,
+) -> Result<()> {
+    // Validate amount
+    require!(amount > 0, ContractError::InvalidAmount);
+
+    // Dated tokens cannot be minted after expiry
+    require!(
+        !ctx.accounts.token_config.is_expired(Clock::get()?.unix_timestamp),
+        ContractError::TokenExpired
+    );
+
+    // Enforce open interest, holding size and per-slot mint limits
+    let token_config = &ctx.accounts.token_config;
+    let limits = check_mint_limits(token_config, ctx.accounts.user_token_account.amount, amount)?;
+
+    // Reject stale oracle data if the caller asked for it
+    VolatilityStats::check_oracle_age(&ctx.accounts.oracle, max_oracle_age)?;
+
+    // Get current mark volatility from oracle
+    let current_volatility = VolatilityStats::load_mark_from_account_info(
+        &ctx.accounts.oracle,
+        token_config.twap_window,
+    )?;
+    msg!("Current volatility: {}", current_volatility);
+
+    // The user pays the capped index value, the vault locks the rest up to the cap
+    let collateral_price = resolve_collateral_price(
+        token_config,
+        &ctx.accounts.collateral_asset,
+        &ctx.accounts.collateral_price_feed,
+    )?;
+    let usd_value = token_config.index_value(amount, current_volatility)?;
+    let usdc_required = collateral_price.usd_to_collateral_in(usd_value)?;
+    let full_collateral = collateral_price
+        .usd_to_collateral_in(token_config.index_value(amount, token_config.vol_cap)?)?;
+    let vault_collateral = full_collateral
+        .checked_sub(usdc_required)
+        .ok_or(ContractError::MathOverflow)?;
+
+    msg!("USDC required: {}", usdc_required);
+
+    require!(
+        ctx.accounts.lp_usdc_vault.amount >= vault_collateral,
+        ContractError::InsufficientLiquidity
+    );
+
+    // Calculate fee, discounted by the user's volume tier
+    let referrer = resolve_referrer(
+        token_config,
+        &ctx.accounts.referrer,
+        &ctx.accounts.referrer_usdc_account,
+        &mut ctx.accounts.user_volume,
+        ctx.accounts.user.key(),
+    )?;
+    let insurance_fund = resolve_insurance_fund(token_config, &ctx.accounts.insurance_fund)?;
+    let fee = split_fee(
+        token_config,
+        usdc_required,
+        token_config.mint_fee_bps,
+        ctx.accounts.user_volume.volume,
+        Some(&ctx.accounts.lp_vault),
+        referrer.is_some(),
+    )?;
+    let fee_amount = fee.total;
+
+    msg!("Fee amount: {}", fee_amount);
+
+    // Total amount user needs to pay
+    let total_payment = usdc_required
+        .checked_add(fee_amount)
+        .ok_or(ContractError::MathOverflow)?;
+
+    // Slippage protection
+    require!(total_payment <= max_usdc_in, ContractError::SlippageMaxInExceeded);
+
+    // Check if user has enough USDC
+    require!(
+        ctx.accounts.user_usdc_account.amount >= total_payment,
+        ContractError::InsufficientBalance
+    );
+
+    // Pay the fee out to the protocol, LP vault, insurance fund and referrer
+    distribute_fees(
+        &ctx.accounts.usdc_token_program.to_account_info(),
+        &ctx.accounts.user_usdc_account.to_account_info(),
+        &ctx.accounts.usdc_mint,
+        &ctx.accounts.user.to_account_info(),
+        &[],
+        FeeRecipients {
+            fee_vault: ctx.accounts.fee_vault.to_account_info(),
+            lp_usdc_vault: Some(ctx.accounts.lp_usdc_vault.to_account_info()),
+            insurance_fund: insurance_fund.map(|a| a.to_account_info()),
+            referrer: referrer.map(|a| a.to_account_info()),
+        },
+        &fee,
+        ctx.accounts.token_mint.key(),
+        ctx.accounts.user.key(),
+    )?;
+
+    // Transfer collateral to the collateral pool
+    token_interface::transfer_checked(
+        CpiContext::new(
+            ctx.accounts.usdc_token_program.to_account_info(),
+            TransferChecked {
+                from: ctx.accounts.user_usdc_account.to_account_info(),
+                mint: ctx.accounts.usdc_mint.to_account_info(),
+                to: ctx.accounts.collateral_pool.to_account_info(),
+                authority: ctx.accounts.user.to_account_info(),
+            },
+        ),
+        usdc_required,
+        ctx.accounts.usdc_mint.decimals,
+    )?;
+
+    // Top the pool up to full collateralization from the vault
+    let token_mint_key = ctx.accounts.token_mint.key();
+    if vault_collateral > 0 {
+        let lp_vault_seeds = &[
+            b"lp_vault",
+            token_mint_key.as_ref(),
+            &[ctx.accounts.lp_vault.bump]
+        ];
+
+        token_interface::transfer_checked(
+            CpiContext::new_with_signer(
+                ctx.accounts.usdc_token_program.to_account_info(),
+                TransferChecked {
+                    from: ctx.accounts.lp_usdc_vault.to_account_info(),
+                    mint: ctx.accounts.usdc_mint.to_account_info(),
+                    to: ctx.accounts.collateral_pool.to_account_info(),
+                    authority: ctx.accounts.lp_vault.to_account_info(),
+                },
+                &[&lp_vault_seeds[..]],
+            ),
+            vault_collateral,
+            ctx.accounts.usdc_mint.decimals,
+        )?;
+    }
+
+    // Mint index tokens to user
+    let token_config_seeds = &[
+        b"token_config",
+        token_mint_key.as_ref(),
+        &[ctx.accounts.token_config.bump]
+    ];
+    let signer = &[&token_config_seeds[..]];
+
+    token_interface::mint_to(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            token_interface::MintTo {
+                mint: ctx.accounts.token_mint.to_account_info(),
+                to: ctx.accounts.user_token_account.to_account_info(),
+                authority: ctx.accounts.token_config.to_account_info(),
+            },
+            signer,
+        ),
+        amount,
+    )?;
+
+    // Update vault and token config state, the vault's locked collateral now backs the tokens
+    let lp_vault = &mut ctx.accounts.lp_vault;
+    lp_vault.total_fees_earned = lp_vault
+        .total_fees_earned
+        .checked_add(fee.lp)
+        .ok_or(ContractError::MathOverflow)?;
+    lp_vault.credit(fee.lp)?;
+    lp_vault.debit(vault_collateral)?;
+    lp_vault.lock_collateral(full_collateral)?;
+
+    // Track the user's volume for fee tiers
+    ctx.accounts.user_volume.record(
+        ctx.accounts.user.key(),
+        ctx.accounts.token_mint.key(),
+        ctx.bumps.user_volume,
+        usd_value,
+    );
+
+    let token_config = &mut ctx.accounts.token_config;
+    record_mint(token_config, &limits);
+
+    emit!(IndexTokensMinted {
+        token_mint: ctx.accounts.token_mint.key(),
+        user: ctx.accounts.user.key(),
+        amount,
+        volatility: current_volatility,
+        execution_price: token_config.index_value(1, current_volatility)?,
+        usdc_paid: total_payment,
+        fee_amount,
+        vault_collateral,
+        total_tokens_outstanding: token_config.total_tokens_outstanding,
+    });
+
+    Ok(())
+}
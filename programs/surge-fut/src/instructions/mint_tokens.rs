@@ -1,11 +1,11 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::{self, Mint, Token, TokenAccount, Transfer};
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
 use anchor_spl::associated_token::AssociatedToken;
 
-use crate::{state::*, errors::ContractError};
+use crate::{state::*, errors::ContractError, events::*, fees::*, trade::*};
 
 #[derive(Accounts)]
-#[instruction(amount: u64)]
+#[instruction(amount: u64, max_usdc_in: u64, max_oracle_age: Option<i64>)]
 pub struct MintTokens<'info> {
     #[account(mut)]
     pub user: Signer<'info>,
@@ -16,14 +16,14 @@ pub struct MintTokens<'info> {
         constraint = user_usdc_account.owner == user.key() @ ContractError::Unauthorized,
         constraint = user_usdc_account.mint == token_config.usdc_mint @ ContractError::InvalidOracleData,
     )]
-    pub user_usdc_account: Account<'info, TokenAccount>,
+    pub user_usdc_account: InterfaceAccount<'info, TokenAccount>,
     
-    /// Fee destination USDC account
+    /// Fee vault USDC account
     #[account(
         mut,
-        constraint = fee_destination.key() == token_config.fee_destination @ ContractError::Unauthorized,
+        constraint = fee_vault.key() == token_config.fee_vault @ ContractError::Unauthorized,
     )]
-    pub fee_destination: Account<'info, TokenAccount>,
+    pub fee_vault: InterfaceAccount<'info, TokenAccount>,
     
     /// Collateral pool USDC account
     #[account(
@@ -32,7 +32,7 @@ pub struct MintTokens<'info> {
         bump = token_config.collateral_pool_bump,
         constraint = collateral_pool.key() == token_config.collateral_pool @ ContractError::Unauthorized,
     )]
-    pub collateral_pool: Account<'info, TokenAccount>,
+    pub collateral_pool: InterfaceAccount<'info, TokenAccount>,
     
     /// Volatility token account of the user
     #[account(
@@ -40,21 +40,24 @@ pub struct MintTokens<'info> {
         payer = user,
         associated_token::mint = token_mint,
         associated_token::authority = user,
+        associated_token::token_program = token_program,
     )]
-    pub user_token_account: Account<'info, TokenAccount>,
+    pub user_token_account: InterfaceAccount<'info, TokenAccount>,
     
     /// Volatility token mint
     #[account(
         mut,
         constraint = token_mint.key() == token_config.token_mint @ ContractError::InvalidOracleData,
     )]
-    pub token_mint: Account<'info, Mint>,
+    pub token_mint: InterfaceAccount<'info, Mint>,
     
     /// Token Config
     #[account(
         mut,
         seeds = [b"token_config", token_mint.key().as_ref()],
         bump = token_config.bump,
+        constraint = !token_config.is_index_mode() @ ContractError::InvalidTokenMode,
+        constraint = !token_config.is_paused(PAUSE_MINTS) @ ContractError::MintsPaused,
     )]
     pub token_config: Account<'info, TokenConfig>,
     
@@ -62,7 +65,7 @@ pub struct MintTokens<'info> {
     #[account(
         init_if_needed,
         payer = user,
-        space = 8 + 32 + 8 + 8 + 8 + 8 + 1,
+        space = USER_POSITION_SPACE,
         seeds = [b"user_position", user.key().as_ref(), token_mint.key().as_ref()],
         bump,
     )]
@@ -70,52 +73,134 @@ pub struct MintTokens<'info> {
     
     /// Oracle account with volatility data
     /// CHECK: Account is validated through VolatilityStats::load_from_account_info
+    #[account(
+        constraint = oracle.key() == token_config.oracle @ ContractError::InvalidOracleData,
+    )]
     pub oracle: AccountInfo<'info>,
     
-    pub token_program: Program<'info, Token>,
+    /// LP vault acting as counterparty, required once the market has one
+    #[account(mut)]
+    pub lp_vault: Option<Account<'info, LpVault>>,
+    
+    /// USDC liquidity held by the LP vault
+    #[account(mut)]
+    pub lp_usdc_vault: Option<InterfaceAccount<'info, TokenAccount>>,
+    
+    /// Cumulative trading volume of the user, used for fee tiers
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = USER_VOLUME_SPACE,
+        seeds = [b"user_volume", user.key().as_ref(), token_mint.key().as_ref()],
+        bump,
+    )]
+    pub user_volume: Account<'info, UserVolume>,
+    
+    /// Registered referrer, bound to the trader on its first referred trade
+    pub referrer: Option<Account<'info, Referrer>>,
+    
+    /// USDC account of the referrer receiving a fee rebate
+    #[account(mut)]
+    pub referrer_usdc_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    
+    /// Insurance fund USDC account, required once the market has one
+    #[account(mut)]
+    pub insurance_fund: Option<InterfaceAccount<'info, TokenAccount>>,
+    
+    /// Collateral registry entry, required once the market has one
+    pub collateral_asset: Option<Account<'info, CollateralAsset>>,
+    
+    /// Pyth price update for the collateral asset
+    /// CHECK: Validated through CollateralAsset::load_price
+    pub collateral_price_feed: Option<AccountInfo<'info>>,
+    
+    /// USDC mint
+    #[account(
+        constraint = usdc_mint.key() == token_config.usdc_mint @ ContractError::InvalidOracleData,
+    )]
+    pub usdc_mint: InterfaceAccount<'info, Mint>,
+    
+    /// Token program owning the volatility token mint
+    pub token_program: Interface<'info, TokenInterface>,
+    /// Token program owning the USDC mint
+    pub usdc_token_program: Interface<'info, TokenInterface>,
     pub system_program: Program<'info, System>,
     pub associated_token_program: Program<'info, AssociatedToken>,
     pub rent: Sysvar<'info, Rent>,
 }
 
-pub fn mint_tokens(ctx: Context<MintTokens>, amount: u64) -> Result<()> {
+pub fn mint_tokens(
+    ctx: Context<MintTokens>,
+    amount: u64,
+    max_usdc_in: u64,
+    max_oracle_age: Option<i64>,
+) -> Result<()> {
     // Validate amount
     require!(amount > 0, ContractError::InvalidAmount);
     
-    // Get current volatility from oracle
-    let current_volatility = VolatilityStats::load_from_account_info(&ctx.accounts.oracle)?;
+    // Dated tokens cannot be minted after expiry
+    require!(
+        !ctx.accounts.token_config.is_expired(Clock::get()?.unix_timestamp),
+        ContractError::TokenExpired
+    );
+    
+    // Enforce open interest, position size and per-slot mint limits
+    let limits = check_mint_limits(
+        &ctx.accounts.token_config,
+        ctx.accounts.user_position.tokens_minted,
+        amount,
+    )?;
+    
+    // Reject stale oracle data if the caller asked for it
+    VolatilityStats::check_oracle_age(&ctx.accounts.oracle, max_oracle_age)?;
+    
+    // Get current mark volatility from oracle
+    let current_volatility = VolatilityStats::load_mark_from_account_info(
+        &ctx.accounts.oracle,
+        ctx.accounts.token_config.twap_window,
+    )?;
     msg!("Current volatility: {}", current_volatility);
     
-    // Calculate USDC required based on token amount and current volatility
-    let usdc_per_vol = ctx.accounts.token_config.usdc_per_vol_point;
-    
-    // Convert f64 volatility to u64 representation (multiplied by 1000 to preserve precision)
-    let vol_points = (current_volatility * 1000.0) as u64;
-    
-    // Calculate required USDC amount: amount * volatility * usdc_per_vol_point
-    let usdc_required = amount
-        .checked_mul(vol_points)
-        .ok_or(ContractError::MathOverflow)?
-        .checked_mul(usdc_per_vol)
-        .ok_or(ContractError::MathOverflow)?
-        .checked_div(1000) // Adjust for the volatility scaling
-        .ok_or(ContractError::MathOverflow)?;
-        
-    msg!("USDC required: {}", usdc_required);
+    // Convert to collateral units, grossed up by the asset's haircut
+    let collateral_price = resolve_collateral_price(
+        &ctx.accounts.token_config,
+        &ctx.accounts.collateral_asset,
+        &ctx.accounts.collateral_price_feed,
+    )?;
+    let referrer = resolve_referrer(
+        &ctx.accounts.token_config,
+        &ctx.accounts.referrer,
+        &ctx.accounts.referrer_usdc_account,
+        &mut ctx.accounts.user_volume,
+        ctx.accounts.user.key(),
+    )?;
+    let insurance_fund = resolve_insurance_fund(
+        &ctx.accounts.token_config,
+        &ctx.accounts.insurance_fund,
+    )?;
     
-    // Calculate fee
-    let fee_amount = usdc_required
-        .checked_mul(ctx.accounts.token_config.fee_bps as u64)
-        .ok_or(ContractError::MathOverflow)?
-        .checked_div(10000)
-        .ok_or(ContractError::MathOverflow)?;
+    // Price the mint, check the LP vault can back it and split the fee,
+    // discounted by the user's volume tier
+    let quote = quote_mint(
+        &ctx.accounts.token_config,
+        &collateral_price,
+        &ctx.accounts.lp_vault,
+        &ctx.accounts.lp_usdc_vault,
+        &ctx.accounts.collateral_pool,
+        amount,
+        current_volatility,
+        ctx.accounts.user_volume.volume,
+        referrer.is_some(),
+    )?;
+    let usdc_required = quote.collateral;
+    let fee_amount = quote.fee.total;
+    let total_payment = quote.total_payment;
     
+    msg!("USDC required: {}", usdc_required);
     msg!("Fee amount: {}", fee_amount);
     
-    // Total amount user needs to pay
-    let total_payment = usdc_required
-        .checked_add(fee_amount)
-        .ok_or(ContractError::MathOverflow)?;
+    // Slippage protection
+    require!(total_payment <= max_usdc_in, ContractError::SlippageMaxInExceeded);
     
     // Check if user has enough USDC
     require!(
@@ -123,80 +208,82 @@ pub fn mint_tokens(ctx: Context<MintTokens>, amount: u64) -> Result<()> {
         ContractError::InsufficientBalance
     );
     
-    // Transfer fee to fee destination
-    token::transfer(
-        CpiContext::new(
-            ctx.accounts.token_program.to_account_info(),
-            Transfer {
-                from: ctx.accounts.user_usdc_account.to_account_info(),
-                to: ctx.accounts.fee_destination.to_account_info(),
-                authority: ctx.accounts.user.to_account_info(),
-            },
-        ),
-        fee_amount,
+    // Pay the fee out to the protocol, LP vault, insurance fund and referrer
+    distribute_fees(
+        &ctx.accounts.usdc_token_program.to_account_info(),
+        &ctx.accounts.user_usdc_account.to_account_info(),
+        &ctx.accounts.usdc_mint,
+        &ctx.accounts.user.to_account_info(),
+        &[],
+        FeeRecipients {
+            fee_vault: ctx.accounts.fee_vault.to_account_info(),
+            lp_usdc_vault: ctx.accounts.lp_usdc_vault.as_ref().map(|a| a.to_account_info()),
+            insurance_fund: insurance_fund.map(|a| a.to_account_info()),
+            referrer: referrer.map(|a| a.to_account_info()),
+        },
+        &quote.fee,
+        ctx.accounts.token_mint.key(),
+        ctx.accounts.user.key(),
     )?;
     
     // Transfer collateral to the collateral pool
-    token::transfer(
-        CpiContext::new(
-            ctx.accounts.token_program.to_account_info(),
-            Transfer {
-                from: ctx.accounts.user_usdc_account.to_account_info(),
-                to: ctx.accounts.collateral_pool.to_account_info(),
-                authority: ctx.accounts.user.to_account_info(),
-            },
-        ),
+    transfer_usdc(
+        ctx.accounts.usdc_token_program.to_account_info(),
+        &ctx.accounts.usdc_mint,
+        ctx.accounts.user_usdc_account.to_account_info(),
+        ctx.accounts.collateral_pool.to_account_info(),
+        ctx.accounts.user.to_account_info(),
+        &[],
         usdc_required,
     )?;
     
     // Mint volatility tokens to user
-    let token_mint_key = ctx.accounts.token_mint.key();
-    let token_config_seeds = &[
-        b"token_config", 
-        token_mint_key.as_ref(),
-        &[ctx.accounts.token_config.bump]
-    ];
-    let signer = &[&token_config_seeds[..]];
-    
-    token::mint_to(
-        CpiContext::new_with_signer(
-            ctx.accounts.token_program.to_account_info(),
-            token::MintTo {
-                mint: ctx.accounts.token_mint.to_account_info(),
-                to: ctx.accounts.user_token_account.to_account_info(),
-                authority: ctx.accounts.token_config.to_account_info(),
-            },
-            signer,
-        ),
+    mint_volatility_tokens(
+        ctx.accounts.token_program.to_account_info(),
+        &ctx.accounts.token_mint,
+        ctx.accounts.user_token_account.to_account_info(),
+        &ctx.accounts.token_config,
         amount,
     )?;
     
-    // Update token config state
-    ctx.accounts.token_config.total_tokens_outstanding = ctx
-        .accounts
-        .token_config
-        .total_tokens_outstanding
-        .checked_add(amount)
-        .ok_or(ContractError::MathOverflow)?;
-    
-    // Update or create user position
-    let user_position = &mut ctx.accounts.user_position;
-    
     // If the position is being created for the first time
+    let user_position = &mut ctx.accounts.user_position;
     if user_position.owner == Pubkey::default() {
         user_position.owner = ctx.accounts.user.key();
         user_position.bump = ctx.bumps.user_position;
     }
     
-    // Update position details
-    user_position.entry_volatility = current_volatility;
-    user_position.tokens_minted = user_position.tokens_minted
-        .checked_add(amount)
-        .ok_or(ContractError::MathOverflow)?;
-    user_position.usdc_collateral = user_position.usdc_collateral
-        .checked_add(usdc_required)
-        .ok_or(ContractError::MathOverflow)?;
-    user_position.mint_timestamp = Clock::get()?.unix_timestamp;
+    // Update the token config, LP vault, volume and position
+    record_mint_fill(
+        &mut ctx.accounts.token_config,
+        &mut ctx.accounts.lp_vault,
+        &mut ctx.accounts.user_position,
+        &mut ctx.accounts.user_volume,
+        ctx.accounts.user.key(),
+        ctx.bumps.user_volume,
+        &limits,
+        &quote,
+        amount,
+        current_volatility,
+    )?;
+    let user_position = &ctx.accounts.user_position;
+    
+    emit!(TokensMinted {
+        token_mint: ctx.accounts.token_mint.key(),
+        user: ctx.accounts.user.key(),
+        amount,
+        volatility: current_volatility,
+        execution_price: vol_points(current_volatility)
+            .checked_mul(ctx.accounts.token_config.usdc_per_vol_point)
+            .ok_or(ContractError::MathOverflow)?
+            / 1000,
+        usdc_paid: total_payment,
+        fee_amount,
+        collateral_delta: usdc_required,
+        position_tokens: user_position.tokens_minted,
+        position_collateral: user_position.usdc_collateral,
+        total_tokens_outstanding: ctx.accounts.token_config.total_tokens_outstanding,
+    });
     
     Ok(())
 }
\ No newline at end of file
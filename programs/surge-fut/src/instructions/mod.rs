@@ -2,9 +2,65 @@ pub mod initialize;
 pub mod mint_tokens;
 pub mod redeem_tokens;
 pub mod update_fee;
+pub mod initialize_lp_vault;
+pub mod deposit_liquidity;
+pub mod withdraw_liquidity;
+pub mod update_usdc_per_vol_point;
+pub mod update_oracle;
+pub mod propose_authority;
+pub mod accept_authority;
+pub mod set_guardian;
+pub mod set_pause_flags;
+pub mod settle;
+pub mod update_twap_window;
+pub mod update_risk_limits;
+pub mod close_position;
+pub mod transfer_position;
+pub mod set_delegate;
+pub mod mint_index_tokens;
+pub mod redeem_index_tokens;
+pub mod update_fee_tiers;
+pub mod update_fee_split;
+pub mod withdraw_fees;
+pub mod update_fee_withdrawal_policy;
+pub mod place_order;
+pub mod execute_order;
+pub mod cancel_order;
+pub mod add_collateral_asset;
+pub mod update_collateral_asset;
+pub mod register_referrer;
+pub mod migrate_token_config;
 
 
 pub use initialize::*;
 pub use mint_tokens::*;
 pub use redeem_tokens::*;
-pub use update_fee::*;
\ No newline at end of file
+pub use update_fee::*;
+pub use initialize_lp_vault::*;
+pub use deposit_liquidity::*;
+pub use withdraw_liquidity::*;
+pub use update_usdc_per_vol_point::*;
+pub use update_oracle::*;
+pub use propose_authority::*;
+pub use accept_authority::*;
+pub use set_guardian::*;
+pub use set_pause_flags::*;
+pub use settle::*;
+pub use update_twap_window::*;
+pub use update_risk_limits::*;
+pub use close_position::*;
+pub use transfer_position::*;
+pub use set_delegate::*;
+pub use mint_index_tokens::*;
+pub use redeem_index_tokens::*;
+pub use update_fee_tiers::*;
+pub use update_fee_split::*;
+pub use withdraw_fees::*;
+pub use update_fee_withdrawal_policy::*;
+pub use place_order::*;
+pub use execute_order::*;
+pub use cancel_order::*;
+pub use add_collateral_asset::*;
+pub use update_collateral_asset::*;
+pub use register_referrer::*;
+pub use migrate_token_config::*;
\ No newline at end of file
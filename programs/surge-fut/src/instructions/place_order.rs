@@ -0,0 +1,216 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{self, Transfer};
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked};
+
+use crate::{state::*, errors::ContractError, events::*};
+
+#[derive(Accounts)]
+#[instruction(
+    order_id: u64,
+    side: OrderSide,
+    trigger: TriggerCondition,
+    trigger_volatility: f64,
+    amount: u64,
+    limit_usdc: u64,
+    keeper_fee: u64,
+)]
+pub struct PlaceOrder<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    
+    /// USDC account for buys or volatility token account for sells, funds the escrow
+    #[account(
+        mut,
+        constraint = owner_source_account.owner == owner.key() @ ContractError::Unauthorized,
+        constraint = owner_source_account.mint == escrow_mint.key() @ ContractError::InvalidOracleData,
+    )]
+    pub owner_source_account: InterfaceAccount<'info, TokenAccount>,
+    
+    /// Volatility token mint
+    #[account(
+        constraint = token_mint.key() == token_config.token_mint @ ContractError::InvalidOracleData,
+    )]
+    pub token_mint: InterfaceAccount<'info, Mint>,
+    
+    /// Token Config
+    #[account(
+        seeds = [b"token_config", token_mint.key().as_ref()],
+        bump = token_config.bump,
+        constraint = !token_config.is_index_mode() @ ContractError::InvalidTokenMode,
+        constraint = side != OrderSide::Buy || !token_config.is_paused(PAUSE_MINTS) @ ContractError::MintsPaused,
+        constraint = side != OrderSide::Sell || !token_config.is_paused(PAUSE_REDEEMS) @ ContractError::RedeemsPaused,
+    )]
+    pub token_config: Account<'info, TokenConfig>,
+    
+    /// USDC mint for buys, volatility token mint for sells
+    #[account(
+        constraint = escrow_mint.key() == match side {
+            OrderSide::Buy => token_config.usdc_mint,
+            OrderSide::Sell => token_config.token_mint,
+        } @ ContractError::InvalidOracleData,
+    )]
+    pub escrow_mint: InterfaceAccount<'info, Mint>,
+    
+    /// Order account
+    #[account(
+        init,
+        payer = owner,
+        space = 8 + 32 + 32 + 8 + 1 + 1 + 8 + 8 + 8 + 8 + 32 + 8 + 1 + 1,
+        seeds = [b"order", owner.key().as_ref(), token_mint.key().as_ref(), &order_id.to_le_bytes()],
+        bump,
+    )]
+    pub order: Account<'info, Order>,
+    
+    /// Escrow holding the order's USDC or tokens until it executes or is cancelled
+    #[account(
+        init,
+        payer = owner,
+        seeds = [b"order_escrow", order.key().as_ref()],
+        bump,
+        token::mint = escrow_mint,
+        token::authority = order,
+        token::token_program = token_program,
+    )]
+    pub order_escrow: InterfaceAccount<'info, TokenAccount>,
+    
+    /// User Position Account, required for sells. Buys reserve its rent on the order
+    /// instead, since the position may be closed before the order fills.
+    #[account(
+        seeds = [b"user_position", owner.key().as_ref(), token_mint.key().as_ref()],
+        bump = user_position.bump,
+        constraint = user_position.owner == owner.key() @ ContractError::Unauthorized,
+    )]
+    pub user_position: Option<Account<'info, UserPosition>>,
+    
+    /// Cumulative trading volume of the owner, used for fee tiers
+    #[account(
+        init_if_needed,
+        payer = owner,
+        space = USER_VOLUME_SPACE,
+        seeds = [b"user_volume", owner.key().as_ref(), token_mint.key().as_ref()],
+        bump,
+    )]
+    pub user_volume: Account<'info, UserVolume>,
+    
+    /// Token program owning the escrow mint
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn place_order(
+    ctx: Context<PlaceOrder>,
+    order_id: u64,
+    side: OrderSide,
+    trigger: TriggerCondition,
+    trigger_volatility: f64,
+    amount: u64,
+    limit_usdc: u64,
+    keeper_fee: u64,
+) -> Result<()> {
+    // Validate order parameters
+    require!(amount > 0, ContractError::InvalidAmount);
+    require!(
+        trigger_volatility.is_finite() && trigger_volatility > 0.0,
+        ContractError::InvalidOrder
+    );
+    require!(
+        !ctx.accounts.token_config.is_expired(Clock::get()?.unix_timestamp),
+        ContractError::TokenExpired
+    );
+    
+    // Buys escrow the max USDC in plus the keeper fee, sells escrow the tokens
+    let escrow_amount = match side {
+        OrderSide::Buy => limit_usdc
+            .checked_add(keeper_fee)
+            .ok_or(ContractError::MathOverflow)?,
+        OrderSide::Sell => {
+            let user_position = ctx
+                .accounts
+                .user_position
+                .as_ref()
+                .ok_or(ContractError::InsufficientTokens)?;
+            require!(
+                user_position.tokens_minted >= amount,
+                ContractError::InsufficientTokens
+            );
+            amount
+        }
+    };
+    require!(escrow_amount > 0, ContractError::InvalidAmount);
+    require!(
+        ctx.accounts.owner_source_account.amount >= escrow_amount,
+        ContractError::InsufficientBalance
+    );
+    
+    // Move the funds into escrow
+    token_interface::transfer_checked(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            TransferChecked {
+                from: ctx.accounts.owner_source_account.to_account_info(),
+                mint: ctx.accounts.escrow_mint.to_account_info(),
+                to: ctx.accounts.order_escrow.to_account_info(),
+                authority: ctx.accounts.owner.to_account_info(),
+            },
+        ),
+        escrow_amount,
+        ctx.accounts.escrow_mint.decimals,
+    )?;
+    
+    // Buys hold the rent of the owner's position, the keeper is reimbursed from it
+    // if the fill has to open the position, otherwise it goes back with the order rent
+    if side == OrderSide::Buy {
+        system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.owner.to_account_info(),
+                    to: ctx.accounts.order.to_account_info(),
+                },
+            ),
+            ctx.accounts.rent.minimum_balance(USER_POSITION_SPACE),
+        )?;
+    }
+    
+    let user_volume = &mut ctx.accounts.user_volume;
+    if user_volume.owner == Pubkey::default() {
+        user_volume.owner = ctx.accounts.owner.key();
+        user_volume.token_mint = ctx.accounts.token_mint.key();
+        user_volume.bump = ctx.bumps.user_volume;
+    }
+    
+    let order = &mut ctx.accounts.order;
+    order.owner = ctx.accounts.owner.key();
+    order.token_mint = ctx.accounts.token_mint.key();
+    order.order_id = order_id;
+    order.side = side;
+    order.trigger = trigger;
+    order.trigger_volatility = trigger_volatility;
+    order.amount = amount;
+    order.limit_usdc = limit_usdc;
+    order.keeper_fee = keeper_fee;
+    order.escrow = ctx.accounts.order_escrow.key();
+    order.created_at = Clock::get()?.unix_timestamp;
+    order.escrow_bump = ctx.bumps.order_escrow;
+    order.bump = ctx.bumps.order;
+    
+    msg!("Order {} placed at trigger volatility: {}", order_id, trigger_volatility);
+    
+    emit!(OrderPlaced {
+        token_mint: order.token_mint,
+        owner: order.owner,
+        order: order.key(),
+        order_id,
+        side,
+        trigger,
+        trigger_volatility,
+        amount,
+        limit_usdc,
+        keeper_fee,
+        escrow_amount,
+    });
+    
+    Ok(())
+}
@@ -0,0 +1,41 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::Mint;
+
+use crate::{state::*, errors::ContractError, events::*};
+
+#[derive(Accounts)]
+#[instruction(new_authority: Pubkey)]
+pub struct ProposeAuthority<'info> {
+    #[account(
+        constraint = authority.key() == token_config.authority @ ContractError::Unauthorized,
+    )]
+    pub authority: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"token_config", token_mint.key().as_ref()],
+        bump = token_config.bump,
+    )]
+    pub token_config: Account<'info, TokenConfig>,
+    
+    /// Volatility token mint
+    #[account(
+        constraint = token_mint.key() == token_config.token_mint @ ContractError::InvalidOracleData,
+    )]
+    pub token_mint: InterfaceAccount<'info, Mint>,
+}
+
+pub fn propose_authority(ctx: Context<ProposeAuthority>, new_authority: Pubkey) -> Result<()> {
+    // Proposing the default pubkey cancels a pending transfer
+    ctx.accounts.token_config.pending_authority = new_authority;
+    
+    msg!("Authority transfer proposed to: {}", new_authority);
+    
+    emit!(AuthorityProposed {
+        token_mint: ctx.accounts.token_mint.key(),
+        authority: ctx.accounts.authority.key(),
+        pending_authority: new_authority,
+    });
+    
+    Ok(())
+}
@@ -0,0 +1,314 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{self, Burn, Mint, TokenAccount, TokenInterface, TransferChecked};
+
+use crate::{state::*, errors::ContractError, events::*, fees::*};
+
+#[derive(Accounts)]
+#[instruction(amount: u64, min_usdc_out: u64, max_oracle_age: Option<i64>)]
+pub struct RedeemIndexTokens<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    /// USDC token account of the user to receive funds
+    #[account(
+        mut,
+        constraint = user_usdc_account.owner == user.key() @ ContractError::Unauthorized,
+        constraint = user_usdc_account.mint == token_config.usdc_mint @ ContractError::InvalidOracleData,
+    )]
+    pub user_usdc_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// Volatility token account of the user
+    #[account(
+        mut,
+        constraint = user_token_account.owner == user.key() @ ContractError::Unauthorized,
+        constraint = user_token_account.mint == token_mint.key() @ ContractError::InvalidOracleData,
+    )]
+    pub user_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// Fee vault USDC account
+    #[account(
+        mut,
+        constraint = fee_vault.key() == token_config.fee_vault @ ContractError::Unauthorized,
+    )]
+    pub fee_vault: InterfaceAccount<'info, TokenAccount>,
+
+    /// Collateral pool USDC account
+    #[account(
+        mut,
+        seeds = [b"collateral_pool", token_mint.key().as_ref()],
+        bump = token_config.collateral_pool_bump,
+        constraint = collateral_pool.key() == token_config.collateral_pool @ ContractError::Unauthorized,
+    )]
+    pub collateral_pool: InterfaceAccount<'info, TokenAccount>,
+
+    /// Volatility token mint
+    #[account(
+        mut,
+        constraint = token_mint.key() == token_config.token_mint @ ContractError::InvalidOracleData,
+    )]
+    pub token_mint: InterfaceAccount<'info, Mint>,
+
+    /// Token Config
+    #[account(
+        mut,
+        seeds = [b"token_config", token_mint.key().as_ref()],
+        bump = token_config.bump,
+        constraint = token_config.is_index_mode() @ ContractError::InvalidTokenMode,
+        constraint = !token_config.is_paused(PAUSE_REDEEMS) @ ContractError::RedeemsPaused,
+    )]
+    pub token_config: Account<'info, TokenConfig>,
+
+    /// Oracle account with volatility data
+    /// CHECK: Account is validated through VolatilityStats::load_from_account_info
+    #[account(
+        constraint = oracle.key() == token_config.oracle @ ContractError::InvalidOracleData,
+    )]
+    pub oracle: AccountInfo<'info>,
+
+    /// LP vault receiving back the collateral it locked
+    #[account(
+        mut,
+        seeds = [b"lp_vault", token_mint.key().as_ref()],
+        bump = lp_vault.bump,
+        constraint = lp_vault.key() == token_config.lp_vault @ ContractError::LpVaultRequired,
+    )]
+    pub lp_vault: Account<'info, LpVault>,
+
+    /// USDC liquidity held by the LP vault
+    #[account(
+        mut,
+        seeds = [b"lp_usdc_vault", token_mint.key().as_ref()],
+        bump = lp_vault.usdc_vault_bump,
+    )]
+    pub lp_usdc_vault: InterfaceAccount<'info, TokenAccount>,
+
+    /// Cumulative trading volume of the user, used for fee tiers
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = USER_VOLUME_SPACE,
+        seeds = [b"user_volume", user.key().as_ref(), token_mint.key().as_ref()],
+        bump,
+    )]
+    pub user_volume: Account<'info, UserVolume>,
+
+    /// Registered referrer, bound to the trader on its first referred trade
+    pub referrer: Option<Account<'info, Referrer>>,
+
+    /// USDC account of the referrer receiving a fee rebate
+    #[account(mut)]
+    pub referrer_usdc_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Insurance fund USDC account, required once the market has one
+    #[account(mut)]
+    pub insurance_fund: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Collateral registry entry, required once the market has one
+    pub collateral_asset: Option<Account<'info, CollateralAsset>>,
+
+    /// Pyth price update for the collateral asset
+    /// CHECK: Validated through CollateralAsset::load_price
+    pub collateral_price_feed: Option<AccountInfo<'info>>,
+
+    /// USDC mint
+    #[account(
+        constraint = usdc_mint.key() == token_config.usdc_mint @ ContractError::InvalidOracleData,
+    )]
+    pub usdc_mint: InterfaceAccount<'info, Mint>,
+
+    /// Token program owning the volatility token mint
+    pub token_program: Interface<'info, TokenInterface>,
+    /// Token program owning the USDC mint
+    pub usdc_token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn redeem_index_tokens(
+    ctx: Context<RedeemIndexTokens>,
+    amount: u64,
+    min_usdc_out: u64,
+    max_oracle_age: Option<i64>,
+) -> Result<()> {
+    // Validate amount
+    require!(amount > 0, ContractError::InvalidAmount);
+    require!(
+        ctx.accounts.user_token_account.amount >= amount,
+        ContractError::InsufficientTokens
+    );
+
+    // Expired dated tokens redeem at the fixed settlement volatility
+    let token_config = &ctx.accounts.token_config;
+    let current_volatility = if token_config.is_expired(Clock::get()?.unix_timestamp) {
+        require!(token_config.is_settled, ContractError::NotSettled);
+        token_config.settlement_volatility
+    } else {
+        // Reject stale oracle data if the caller asked for it
+        VolatilityStats::check_oracle_age(&ctx.accounts.oracle, max_oracle_age)?;
+
+        // Get current mark volatility from oracle
+        VolatilityStats::load_mark_from_account_info(
+            &ctx.accounts.oracle,
+            token_config.twap_window,
+        )?
+    };
+    msg!("Current volatility: {}", current_volatility);
+
+    // Holders receive the capped index value, the rest of the locked collateral goes back to LPs
+    let collateral_price = resolve_collateral_price(
+        token_config,
+        &ctx.accounts.collateral_asset,
+        &ctx.accounts.collateral_price_feed,
+    )?;
+    let usd_value = token_config.index_value(amount, current_volatility)?;
+    let redemption_value = collateral_price.usd_to_collateral_out(usd_value)?;
+    let full_collateral = collateral_price
+        .usd_to_collateral_out(token_config.index_value(amount, token_config.vol_cap)?)?;
+    let vault_collateral = full_collateral
+        .checked_sub(redemption_value)
+        .ok_or(ContractError::MathOverflow)?;
+
+    require!(
+        ctx.accounts.collateral_pool.amount >= full_collateral,
+        ContractError::InsufficientBalance
+    );
+
+    // Calculate fee, discounted by the user's volume tier
+    let referrer = resolve_referrer(
+        token_config,
+        &ctx.accounts.referrer,
+        &ctx.accounts.referrer_usdc_account,
+        &mut ctx.accounts.user_volume,
+        ctx.accounts.user.key(),
+    )?;
+    let insurance_fund = resolve_insurance_fund(token_config, &ctx.accounts.insurance_fund)?;
+    let fee = split_fee(
+        token_config,
+        redemption_value,
+        token_config.redeem_fee_bps,
+        ctx.accounts.user_volume.volume,
+        Some(&ctx.accounts.lp_vault),
+        referrer.is_some(),
+    )?;
+    let fee_amount = fee.total;
+
+    let final_amount = redemption_value
+        .checked_sub(fee_amount)
+        .ok_or(ContractError::MathOverflow)?;
+
+    msg!("Redemption value: {}", redemption_value);
+    msg!("Fee amount: {}", fee_amount);
+
+    // Slippage protection
+    require!(final_amount >= min_usdc_out, ContractError::SlippageMinOutNotMet);
+
+    // Burn the index tokens
+    token_interface::burn(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            Burn {
+                mint: ctx.accounts.token_mint.to_account_info(),
+                from: ctx.accounts.user_token_account.to_account_info(),
+                authority: ctx.accounts.user.to_account_info(),
+            },
+        ),
+        amount,
+    )?;
+
+    let token_mint_key = ctx.accounts.token_mint.key();
+    let token_config_seeds = &[
+        b"token_config",
+        token_mint_key.as_ref(),
+        &[ctx.accounts.token_config.bump]
+    ];
+    let signer = &[&token_config_seeds[..]];
+
+    // Pay the fee out to the protocol, LP vault, insurance fund and referrer
+    distribute_fees(
+        &ctx.accounts.usdc_token_program.to_account_info(),
+        &ctx.accounts.collateral_pool.to_account_info(),
+        &ctx.accounts.usdc_mint,
+        &ctx.accounts.token_config.to_account_info(),
+        signer,
+        FeeRecipients {
+            fee_vault: ctx.accounts.fee_vault.to_account_info(),
+            lp_usdc_vault: Some(ctx.accounts.lp_usdc_vault.to_account_info()),
+            insurance_fund: insurance_fund.map(|a| a.to_account_info()),
+            referrer: referrer.map(|a| a.to_account_info()),
+        },
+        &fee,
+        token_mint_key,
+        ctx.accounts.user.key(),
+    )?;
+
+    // Return the unused cap collateral to the vault
+    if vault_collateral > 0 {
+        token_interface::transfer_checked(
+            CpiContext::new_with_signer(
+                ctx.accounts.usdc_token_program.to_account_info(),
+                TransferChecked {
+                    from: ctx.accounts.collateral_pool.to_account_info(),
+                    mint: ctx.accounts.usdc_mint.to_account_info(),
+                    to: ctx.accounts.lp_usdc_vault.to_account_info(),
+                    authority: ctx.accounts.token_config.to_account_info(),
+                },
+                signer,
+            ),
+            vault_collateral,
+            ctx.accounts.usdc_mint.decimals,
+        )?;
+    }
+
+    // Transfer USDC to user
+    token_interface::transfer_checked(
+        CpiContext::new_with_signer(
+            ctx.accounts.usdc_token_program.to_account_info(),
+            TransferChecked {
+                from: ctx.accounts.collateral_pool.to_account_info(),
+                mint: ctx.accounts.usdc_mint.to_account_info(),
+                to: ctx.accounts.user_usdc_account.to_account_info(),
+                authority: ctx.accounts.token_config.to_account_info(),
+            },
+            signer,
+        ),
+        final_amount,
+        ctx.accounts.usdc_mint.decimals,
+    )?;
+
+    // Update vault and token config state, the unused cap collateral is the vault's again
+    let lp_vault = &mut ctx.accounts.lp_vault;
+    lp_vault.total_fees_earned = lp_vault
+        .total_fees_earned
+        .checked_add(fee.lp)
+        .ok_or(ContractError::MathOverflow)?;
+    lp_vault.credit(fee.lp)?;
+    lp_vault.credit(vault_collateral)?;
+    lp_vault.release_collateral(full_collateral);
+
+    // Track the user's volume for fee tiers
+    ctx.accounts.user_volume.record(
+        ctx.accounts.user.key(),
+        token_mint_key,
+        ctx.bumps.user_volume,
+        usd_value,
+    );
+
+    let token_config = &mut ctx.accounts.token_config;
+    token_config.total_tokens_outstanding = token_config
+        .total_tokens_outstanding
+        .checked_sub(amount)
+        .ok_or(ContractError::MathOverflow)?;
+
+    emit!(IndexTokensRedeemed {
+        token_mint: ctx.accounts.token_mint.key(),
+        user: ctx.accounts.user.key(),
+        amount,
+        volatility: current_volatility,
+        execution_price: token_config.index_value(1, current_volatility)?,
+        usdc_received: final_amount,
+        fee_amount,
+        vault_collateral,
+        total_tokens_outstanding: token_config.total_tokens_outstanding,
+    });
+
+    Ok(())
+}
@@ -1,36 +1,42 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::{self, Mint, Token, TokenAccount, Transfer, Burn};
+use anchor_spl::token_interface::{self, Burn, Mint, TokenAccount, TokenInterface};
 
-use crate::{state::*, errors::ContractError};
+use crate::{state::*, errors::ContractError, events::*, fees::*, trade::*};
 
 #[derive(Accounts)]
-#[instruction(amount: u64)]
+#[instruction(amount: u64, min_usdc_out: u64, max_oracle_age: Option<i64>)]
 pub struct RedeemTokens<'info> {
+    /// Position owner or its delegate
     #[account(mut)]
     pub user: Signer<'info>,
     
-    /// USDC token account to receive funds
+    /// Owner of the position, receives the proceeds and any returned rent
+    /// CHECK: Validated against user_position.owner
+    #[account(mut)]
+    pub owner: AccountInfo<'info>,
+    
+    /// USDC token account of the owner to receive funds
     #[account(
         mut,
-        constraint = user_usdc_account.owner == user.key() @ ContractError::Unauthorized,
+        constraint = user_usdc_account.owner == owner.key() @ ContractError::Unauthorized,
         constraint = user_usdc_account.mint == token_config.usdc_mint @ ContractError::InvalidOracleData,
     )]
-    pub user_usdc_account: Account<'info, TokenAccount>,
+    pub user_usdc_account: InterfaceAccount<'info, TokenAccount>,
     
-    /// Volatility token account of the user
+    /// Volatility token account of the owner, a delegate must be approved on it
     #[account(
         mut,
-        constraint = user_token_account.owner == user.key() @ ContractError::Unauthorized,
+        constraint = user_token_account.owner == owner.key() @ ContractError::Unauthorized,
         constraint = user_token_account.mint == token_mint.key() @ ContractError::InvalidOracleData,
     )]
-    pub user_token_account: Account<'info, TokenAccount>,
+    pub user_token_account: InterfaceAccount<'info, TokenAccount>,
     
-    /// Fee destination account
+    /// Fee vault USDC account
     #[account(
         mut,
-        constraint = fee_destination.key() == token_config.fee_destination @ ContractError::Unauthorized,
+        constraint = fee_vault.key() == token_config.fee_vault @ ContractError::Unauthorized,
     )]
-    pub fee_destination: Account<'info, TokenAccount>,
+    pub fee_vault: InterfaceAccount<'info, TokenAccount>,
     
     /// Collateral pool USDC account
     #[account(
@@ -40,41 +46,98 @@ pub struct RedeemTokens<'info> {
         constraint = collateral_pool.key() == token_config.collateral_pool @ ContractError::Unauthorized,
         constraint = collateral_pool.mint == token_config.usdc_mint @ ContractError::InvalidOracleData,
     )]
-    pub collateral_pool: Account<'info, TokenAccount>,
+    pub collateral_pool: InterfaceAccount<'info, TokenAccount>,
     
     /// Volatility token mint
     #[account(
         mut,
         constraint = token_mint.key() == token_config.token_mint @ ContractError::InvalidOracleData,
     )]
-    pub token_mint: Account<'info, Mint>,
+    pub token_mint: InterfaceAccount<'info, Mint>,
     
     /// Token Config
     #[account(
         mut,
         seeds = [b"token_config", token_mint.key().as_ref()],
         bump = token_config.bump,
+        constraint = !token_config.is_index_mode() @ ContractError::InvalidTokenMode,
+        constraint = !token_config.is_paused(PAUSE_REDEEMS) @ ContractError::RedeemsPaused,
     )]
     pub token_config: Account<'info, TokenConfig>,
     
     /// User Position Account
     #[account(
         mut,
-        seeds = [b"user_position", user.key().as_ref(), token_mint.key().as_ref()],
+        seeds = [b"user_position", owner.key().as_ref(), token_mint.key().as_ref()],
         bump = user_position.bump,
-        constraint = user_position.owner == user.key() @ ContractError::Unauthorized,
+        constraint = user_position.owner == owner.key() @ ContractError::Unauthorized,
+        constraint = user.key() == owner.key()
+            || user.key() == user_position.delegate @ ContractError::Unauthorized,
     )]
     pub user_position: Account<'info, UserPosition>,
     
     /// Oracle account with volatility data
     /// CHECK: Account is validated through VolatilityStats::load_from_account_info
+    #[account(
+        constraint = oracle.key() == token_config.oracle @ ContractError::InvalidOracleData,
+    )]
     pub oracle: AccountInfo<'info>,
     
-    pub token_program: Program<'info, Token>,
+    /// LP vault acting as counterparty, required once the market has one
+    #[account(mut)]
+    pub lp_vault: Option<Account<'info, LpVault>>,
+    
+    /// USDC liquidity held by the LP vault
+    #[account(mut)]
+    pub lp_usdc_vault: Option<InterfaceAccount<'info, TokenAccount>>,
+    
+    /// Cumulative trading volume of the owner, used for fee tiers
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = USER_VOLUME_SPACE,
+        seeds = [b"user_volume", owner.key().as_ref(), token_mint.key().as_ref()],
+        bump,
+    )]
+    pub user_volume: Account<'info, UserVolume>,
+    
+    /// Registered referrer, bound to the trader on its first referred trade
+    pub referrer: Option<Account<'info, Referrer>>,
+    
+    /// USDC account of the referrer receiving a fee rebate
+    #[account(mut)]
+    pub referrer_usdc_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    
+    /// Insurance fund USDC account, required once the market has one
+    #[account(mut)]
+    pub insurance_fund: Option<InterfaceAccount<'info, TokenAccount>>,
+    
+    /// Collateral registry entry, required once the market has one
+    pub collateral_asset: Option<Account<'info, CollateralAsset>>,
+    
+    /// Pyth price update for the collateral asset
+    /// CHECK: Validated through CollateralAsset::load_price
+    pub collateral_price_feed: Option<AccountInfo<'info>>,
+    
+    /// USDC mint
+    #[account(
+        constraint = usdc_mint.key() == token_config.usdc_mint @ ContractError::InvalidOracleData,
+    )]
+    pub usdc_mint: InterfaceAccount<'info, Mint>,
+    
+    /// Token program owning the volatility token mint
+    pub token_program: Interface<'info, TokenInterface>,
+    /// Token program owning the USDC mint
+    pub usdc_token_program: Interface<'info, TokenInterface>,
     pub system_program: Program<'info, System>,
 }
 
-pub fn redeem_tokens(ctx: Context<RedeemTokens>, amount: u64) -> Result<()> {
+pub fn redeem_tokens(
+    ctx: Context<RedeemTokens>,
+    amount: u64,
+    min_usdc_out: u64,
+    max_oracle_age: Option<i64>,
+) -> Result<()> {
     // Validate amount is greater than 0
     require!(amount > 0, ContractError::InvalidAmount);
     
@@ -90,37 +153,60 @@ pub fn redeem_tokens(ctx: Context<RedeemTokens>, amount: u64) -> Result<()> {
         ContractError::InsufficientTokens
     );
     
-    // Get current volatility from oracle
-    let current_volatility = VolatilityStats::load_from_account_info(&ctx.accounts.oracle)?;
+    // Expired dated tokens redeem at the fixed settlement volatility
+    let current_volatility = if ctx.accounts.token_config.is_expired(Clock::get()?.unix_timestamp) {
+        require!(ctx.accounts.token_config.is_settled, ContractError::NotSettled);
+        ctx.accounts.token_config.settlement_volatility
+    } else {
+        // Reject stale oracle data if the caller asked for it
+        VolatilityStats::check_oracle_age(&ctx.accounts.oracle, max_oracle_age)?;
+        
+        // Get current mark volatility from oracle
+        VolatilityStats::load_mark_from_account_info(
+            &ctx.accounts.oracle,
+            ctx.accounts.token_config.twap_window,
+        )?
+    };
     let entry_volatility = ctx.accounts.user_position.entry_volatility;
     
     msg!("Entry volatility: {}", entry_volatility);
     msg!("Current volatility: {}", current_volatility);
     
-    // Calculate redemption value based on volatility change and token amount
-    let usdc_per_vol = ctx.accounts.token_config.usdc_per_vol_point;
-    
-    // Convert volatilities to basis points (multiply by 1000 for precision)
-    let entry_vol_points = (entry_volatility * 1000.0) as u64;
-    let current_vol_points = (current_volatility * 1000.0) as u64;
+    // Convert to collateral units at the current collateral price
+    let collateral_price = resolve_collateral_price(
+        &ctx.accounts.token_config,
+        &ctx.accounts.collateral_asset,
+        &ctx.accounts.collateral_price_feed,
+    )?;
+    let referrer = resolve_referrer(
+        &ctx.accounts.token_config,
+        &ctx.accounts.referrer,
+        &ctx.accounts.referrer_usdc_account,
+        &mut ctx.accounts.user_volume,
+        ctx.accounts.owner.key(),
+    )?;
+    let insurance_fund = resolve_insurance_fund(
+        &ctx.accounts.token_config,
+        &ctx.accounts.insurance_fund,
+    )?;
     
-    // Calculate redemption amount
-    let redemption_value = calculate_redemption_value(
+    // Calculate redemption value based on volatility change and token amount,
+    // and the fee discounted by the owner's volume tier
+    let quote = quote_redeem(
+        &ctx.accounts.token_config,
+        &collateral_price,
+        &ctx.accounts.lp_vault,
+        &ctx.accounts.lp_usdc_vault,
         amount,
-        entry_vol_points,
-        current_vol_points,
-        usdc_per_vol,
+        entry_volatility,
+        current_volatility,
+        ctx.accounts.user_volume.volume,
+        referrer.is_some(),
     )?;
+    let redemption_value = quote.collateral;
+    let fee_amount = quote.fee.total;
     
     msg!("Redemption value: {}", redemption_value);
-    
-    // Calculate fee
-    let fee_amount = redemption_value
-        .checked_mul(ctx.accounts.token_config.fee_bps as u64)
-        .ok_or(ContractError::MathOverflow)?
-        .checked_div(10000)
-        .ok_or(ContractError::MathOverflow)?;
-    
     msg!("Fee amount: {}", fee_amount);
     
     // Final amount after fee
@@ -128,26 +214,12 @@ pub fn redeem_tokens(ctx: Context<RedeemTokens>, amount: u64) -> Result<()> {
         .checked_sub(fee_amount)
         .ok_or(ContractError::MathOverflow)?;
     
-    // Ensure pool has enough USDC to pay out
-    require!(
-        ctx.accounts.collateral_pool.amount >= final_amount,
-        ContractError::InsufficientBalance
-    );
+    // Slippage protection
+    require!(final_amount >= min_usdc_out, ContractError::SlippageMinOutNotMet);
     
-    // Burn the volatility tokens
-    token::burn(
-        CpiContext::new(
-            ctx.accounts.token_program.to_account_info(),
-            Burn {
-                mint: ctx.accounts.token_mint.to_account_info(),
-                from: ctx.accounts.user_token_account.to_account_info(),
-                authority: ctx.accounts.user.to_account_info(),
-            },
-        ),
-        amount,
-    )?;
+    // Portion of the position's collateral backing the redeemed tokens
+    let collateral_reduction = ctx.accounts.user_position.collateral_for(amount)?;
     
-    // Transfer fee to fee destination
     let token_mint_key = ctx.accounts.token_mint.key();
     let token_config_seeds = &[
         b"token_config", 
@@ -156,136 +228,126 @@ pub fn redeem_tokens(ctx: Context<RedeemTokens>, amount: u64) -> Result<()> {
     ];
     let signer = &[&token_config_seeds[..]];
     
-    // Only transfer fee if it's greater than 0
-    if fee_amount > 0 {
-        token::transfer(
+    // Settle the trader's PnL against the LP vault and check the pool can pay out
+    fund_redemption(
+        ctx.accounts.usdc_token_program.to_account_info(),
+        &ctx.accounts.usdc_mint,
+        &ctx.accounts.token_config,
+        &mut ctx.accounts.collateral_pool,
+        &mut ctx.accounts.lp_vault,
+        &ctx.accounts.lp_usdc_vault,
+        redemption_value,
+        collateral_reduction,
+    )?;
+    
+    // Burn the volatility tokens
+    if ctx.accounts.user.key() == ctx.accounts.owner.key() {
+        token_interface::burn(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                Burn {
+                    mint: ctx.accounts.token_mint.to_account_info(),
+                    from: ctx.accounts.user_token_account.to_account_info(),
+                    authority: ctx.accounts.user.to_account_info(),
+                },
+            ),
+            amount,
+        )?;
+    } else {
+        // Delegates burn through the allowance granted to the position in set_delegate
+        let owner_key = ctx.accounts.owner.key();
+        let user_position_seeds = &[
+            b"user_position",
+            owner_key.as_ref(),
+            token_mint_key.as_ref(),
+            &[ctx.accounts.user_position.bump]
+        ];
+        
+        token_interface::burn(
             CpiContext::new_with_signer(
                 ctx.accounts.token_program.to_account_info(),
-                Transfer {
-                    from: ctx.accounts.collateral_pool.to_account_info(),
-                    to: ctx.accounts.fee_destination.to_account_info(),
-                    authority: ctx.accounts.token_config.to_account_info(),
+                Burn {
+                    mint: ctx.accounts.token_mint.to_account_info(),
+                    from: ctx.accounts.user_token_account.to_account_info(),
+                    authority: ctx.accounts.user_position.to_account_info(),
                 },
-                signer,
+                &[&user_position_seeds[..]],
             ),
-            fee_amount,
+            amount,
         )?;
     }
     
-    // Transfer USDC to user
-    token::transfer(
-        CpiContext::new_with_signer(
-            ctx.accounts.token_program.to_account_info(),
-            Transfer {
-                from: ctx.accounts.collateral_pool.to_account_info(),
-                to: ctx.accounts.user_usdc_account.to_account_info(),
-                authority: ctx.accounts.token_config.to_account_info(),
-            },
-            signer,
-        ),
+    // Pay the fee out to the protocol, LP vault, insurance fund and referrer
+    distribute_fees(
+        &ctx.accounts.usdc_token_program.to_account_info(),
+        &ctx.accounts.collateral_pool.to_account_info(),
+        &ctx.accounts.usdc_mint,
+        &ctx.accounts.token_config.to_account_info(),
+        signer,
+        FeeRecipients {
+            fee_vault: ctx.accounts.fee_vault.to_account_info(),
+            lp_usdc_vault: ctx.accounts.lp_usdc_vault.as_ref().map(|a| a.to_account_info()),
+            insurance_fund: insurance_fund.map(|a| a.to_account_info()),
+            referrer: referrer.map(|a| a.to_account_info()),
+        },
+        &quote.fee,
+        token_mint_key,
+        ctx.accounts.owner.key(),
+    )?;
+    
+    // Transfer USDC to the owner
+    transfer_usdc(
+        ctx.accounts.usdc_token_program.to_account_info(),
+        &ctx.accounts.usdc_mint,
+        ctx.accounts.collateral_pool.to_account_info(),
+        ctx.accounts.user_usdc_account.to_account_info(),
+        ctx.accounts.token_config.to_account_info(),
+        signer,
         final_amount,
     )?;
     
-    // Update token config state
-    ctx.accounts.token_config.total_tokens_outstanding = ctx
-        .accounts
-        .token_config
-        .total_tokens_outstanding
-        .checked_sub(amount)
-        .ok_or(ContractError::MathOverflow)?;
+    // Update the token config, LP vault, volume and position
+    record_redeem_fill(
+        &mut ctx.accounts.token_config,
+        &mut ctx.accounts.lp_vault,
+        &mut ctx.accounts.user_position,
+        &mut ctx.accounts.user_volume,
+        ctx.accounts.owner.key(),
+        ctx.bumps.user_volume,
+        &quote,
+        amount,
+        collateral_reduction,
+    )?;
     
-    // Update user position
-    ctx.accounts.user_position.tokens_minted = ctx
-        .accounts
-        .user_position
-        .tokens_minted
-        .checked_sub(amount)
+    let pnl = (redemption_value as i128)
+        .checked_sub(collateral_reduction as i128)
         .ok_or(ContractError::MathOverflow)?;
     
-    // Scale down the collateral proportionally
-    let collateral_reduction = ctx
-        .accounts
-        .user_position
-        .usdc_collateral
-        .checked_mul(amount)
-        .ok_or(ContractError::MathOverflow)?
-        .checked_div(ctx.accounts.user_position.tokens_minted + amount) // Add back amount to get original total
-        .unwrap_or(ctx.accounts.user_position.usdc_collateral);
-    
-    ctx.accounts.user_position.usdc_collateral = ctx
-        .accounts
-        .user_position
-        .usdc_collateral
-        .checked_sub(collateral_reduction)
-        .ok_or(ContractError::MathOverflow)?;
+    emit!(TokensRedeemed {
+        token_mint: ctx.accounts.token_mint.key(),
+        user: ctx.accounts.owner.key(),
+        amount,
+        entry_volatility,
+        volatility: current_volatility,
+        execution_price: vol_points(current_volatility)
+            .checked_mul(ctx.accounts.token_config.usdc_per_vol_point)
+            .ok_or(ContractError::MathOverflow)?
+            / 1000,
+        usdc_received: final_amount,
+        fee_amount,
+        collateral_delta: collateral_reduction,
+        pnl: i64::try_from(pnl).map_err(|_| ContractError::MathOverflow)?,
+        position_tokens: ctx.accounts.user_position.tokens_minted,
+        position_collateral: ctx.accounts.user_position.usdc_collateral,
+        total_tokens_outstanding: ctx.accounts.token_config.total_tokens_outstanding,
+    });
     
-    Ok(())
-}
-
-// Helper function to calculate redemption value
-fn calculate_redemption_value(
-    amount: u64,
-    entry_vol_points: u64,
-    current_vol_points: u64,
-    usdc_per_vol: u64,
-) -> Result<u64> {
-    // Base redemption is tokens * entry_volatility * usdc_per_vol_point
-    let base_value = amount
-        .checked_mul(entry_vol_points)
-        .ok_or(ContractError::MathOverflow)?
-        .checked_mul(usdc_per_vol)
-        .ok_or(ContractError::MathOverflow)?
-        .checked_div(1000) // Adjust for the volatility scaling
-        .ok_or(ContractError::MathOverflow)?;
+    // Return rent to the owner once the position is fully redeemed
+    close_if_empty(
+        &mut ctx.accounts.user_position,
+        ctx.accounts.owner.to_account_info(),
+        token_mint_key,
+    )?;
     
-    // Calculate profit/loss based on volatility change
-    if current_vol_points > entry_vol_points {
-        // Volatility increased, user profits
-        let profit_per_vol_point = usdc_per_vol
-            .checked_mul(amount)
-            .ok_or(ContractError::MathOverflow)?;
-        
-        let vol_diff = current_vol_points
-            .checked_sub(entry_vol_points)
-            .ok_or(ContractError::MathOverflow)?;
-        
-        let profit = profit_per_vol_point
-            .checked_mul(vol_diff)
-            .ok_or(ContractError::MathOverflow)?
-            .checked_div(1000) // Adjust for the volatility scaling
-            .ok_or(ContractError::MathOverflow)?;
-        
-        base_value
-            .checked_add(profit)
-            .ok_or(ContractError::MathOverflow.into())
-    } else if current_vol_points < entry_vol_points {
-        // Volatility decreased, user takes a loss
-        let loss_per_vol_point = usdc_per_vol
-            .checked_mul(amount)
-            .ok_or(ContractError::MathOverflow)?;
-        
-        let vol_diff = entry_vol_points
-            .checked_sub(current_vol_points)
-            .ok_or(ContractError::MathOverflow)?;
-        
-        let loss = loss_per_vol_point
-            .checked_mul(vol_diff)
-            .ok_or(ContractError::MathOverflow)?
-            .checked_div(1000) // Adjust for the volatility scaling
-            .ok_or(ContractError::MathOverflow)?;
-        
-        // Ensure loss doesn't exceed base value
-        if loss >= base_value {
-            // Return minimum value (1) to avoid complete loss
-            Ok(1)
-        } else {
-            base_value
-                .checked_sub(loss)
-                .ok_or(ContractError::MathOverflow.into())
-        }
-    } else {
-        // No change in volatility
-        Ok(base_value)
-    }
+    Ok(())
 }
-
@@ -0,0 +1,67 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount};
+
+use crate::{state::*, errors::ContractError, events::*};
+
+#[derive(Accounts)]
+pub struct RegisterReferrer<'info> {
+    #[account(
+        mut,
+        constraint = authority.key() == token_config.authority @ ContractError::Unauthorized,
+    )]
+    pub authority: Signer<'info>,
+    
+    #[account(
+        seeds = [b"token_config", token_mint.key().as_ref()],
+        bump = token_config.bump,
+        constraint = !token_config.is_paused(PAUSE_UPDATES) @ ContractError::UpdatesPaused,
+    )]
+    pub token_config: Account<'info, TokenConfig>,
+    
+    /// Volatility token mint
+    #[account(
+        constraint = token_mint.key() == token_config.token_mint @ ContractError::InvalidOracleData,
+    )]
+    pub token_mint: InterfaceAccount<'info, Mint>,
+    
+    /// Wallet of the referrer
+    /// CHECK: Any wallet can be registered, it only keys the PDA
+    pub referrer_owner: AccountInfo<'info>,
+    
+    /// USDC account of the referrer receiving its rebates
+    #[account(
+        constraint = referrer_usdc_account.owner == referrer_owner.key() @ ContractError::InvalidReferrer,
+        constraint = referrer_usdc_account.mint == token_config.usdc_mint @ ContractError::InvalidReferrer,
+    )]
+    pub referrer_usdc_account: InterfaceAccount<'info, TokenAccount>,
+    
+    #[account(
+        init,
+        payer = authority,
+        space = REFERRER_SPACE,
+        seeds = [b"referrer", token_mint.key().as_ref(), referrer_owner.key().as_ref()],
+        bump
+    )]
+    pub referrer: Account<'info, Referrer>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+pub fn register_referrer(ctx: Context<RegisterReferrer>) -> Result<()> {
+    let referrer = &mut ctx.accounts.referrer;
+    referrer.token_mint = ctx.accounts.token_mint.key();
+    referrer.owner = ctx.accounts.referrer_owner.key();
+    referrer.usdc_account = ctx.accounts.referrer_usdc_account.key();
+    referrer.bump = ctx.bumps.referrer;
+    
+    msg!("Referrer registered: {}", referrer.owner);
+    
+    emit!(ReferrerRegistered {
+        token_mint: referrer.token_mint,
+        referrer: referrer.key(),
+        owner: referrer.owner,
+        usdc_account: referrer.usdc_account,
+    });
+    
+    Ok(())
+}
@@ -0,0 +1,72 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{self, Approve, Mint, Revoke, TokenAccount, TokenInterface};
+
+use crate::{state::*, errors::ContractError, events::*};
+
+#[derive(Accounts)]
+#[instruction(delegate: Pubkey, amount: u64)]
+pub struct SetDelegate<'info> {
+    pub user: Signer<'info>,
+    
+    /// Volatility token mint
+    pub token_mint: InterfaceAccount<'info, Mint>,
+    
+    /// Volatility token account of the user, the position PDA is approved on it
+    /// so delegates can only burn up to `amount` through the program
+    #[account(
+        mut,
+        constraint = user_token_account.owner == user.key() @ ContractError::Unauthorized,
+        constraint = user_token_account.mint == token_mint.key() @ ContractError::InvalidOracleData,
+    )]
+    pub user_token_account: InterfaceAccount<'info, TokenAccount>,
+    
+    /// User Position Account
+    #[account(
+        mut,
+        seeds = [b"user_position", user.key().as_ref(), token_mint.key().as_ref()],
+        bump = user_position.bump,
+        constraint = user_position.owner == user.key() @ ContractError::Unauthorized,
+    )]
+    pub user_position: Account<'info, UserPosition>,
+    
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+pub fn set_delegate(ctx: Context<SetDelegate>, delegate: Pubkey, amount: u64) -> Result<()> {
+    // Setting the default pubkey revokes the delegate
+    if delegate == Pubkey::default() {
+        token_interface::revoke(CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            Revoke {
+                source: ctx.accounts.user_token_account.to_account_info(),
+                authority: ctx.accounts.user.to_account_info(),
+            },
+        ))?;
+    } else {
+        // The position PDA burns on the delegate's behalf when redeeming
+        token_interface::approve(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                Approve {
+                    to: ctx.accounts.user_token_account.to_account_info(),
+                    delegate: ctx.accounts.user_position.to_account_info(),
+                    authority: ctx.accounts.user.to_account_info(),
+                },
+            ),
+            amount,
+        )?;
+    }
+    
+    ctx.accounts.user_position.delegate = delegate;
+    
+    msg!("Position delegate set to: {}", delegate);
+    
+    emit!(DelegateUpdated {
+        token_mint: ctx.accounts.token_mint.key(),
+        owner: ctx.accounts.user.key(),
+        delegate,
+        amount,
+    });
+    
+    Ok(())
+}
@@ -0,0 +1,43 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::Mint;
+
+use crate::{state::*, errors::ContractError, events::*};
+
+#[derive(Accounts)]
+#[instruction(new_guardian: Pubkey)]
+pub struct SetGuardian<'info> {
+    #[account(
+        constraint = authority.key() == token_config.authority @ ContractError::Unauthorized,
+    )]
+    pub authority: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"token_config", token_mint.key().as_ref()],
+        bump = token_config.bump,
+    )]
+    pub token_config: Account<'info, TokenConfig>,
+    
+    /// Volatility token mint
+    #[account(
+        constraint = token_mint.key() == token_config.token_mint @ ContractError::InvalidOracleData,
+    )]
+    pub token_mint: InterfaceAccount<'info, Mint>,
+}
+
+pub fn set_guardian(ctx: Context<SetGuardian>, new_guardian: Pubkey) -> Result<()> {
+    // Update guardian
+    let old_guardian = ctx.accounts.token_config.guardian;
+    ctx.accounts.token_config.guardian = new_guardian;
+    
+    msg!("Guardian updated to: {}", new_guardian);
+    
+    emit!(GuardianUpdated {
+        token_mint: ctx.accounts.token_mint.key(),
+        authority: ctx.accounts.authority.key(),
+        old_guardian,
+        new_guardian,
+    });
+    
+    Ok(())
+}
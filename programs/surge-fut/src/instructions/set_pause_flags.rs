@@ -0,0 +1,48 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::Mint;
+
+use crate::{state::*, errors::ContractError, events::*};
+
+#[derive(Accounts)]
+#[instruction(pause_flags: u8)]
+pub struct SetPauseFlags<'info> {
+    /// Guardian or authority
+    #[account(
+        constraint = signer.key() == token_config.guardian
+            || signer.key() == token_config.authority @ ContractError::Unauthorized,
+    )]
+    pub signer: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"token_config", token_mint.key().as_ref()],
+        bump = token_config.bump,
+    )]
+    pub token_config: Account<'info, TokenConfig>,
+    
+    /// Volatility token mint
+    #[account(
+        constraint = token_mint.key() == token_config.token_mint @ ContractError::InvalidOracleData,
+    )]
+    pub token_mint: InterfaceAccount<'info, Mint>,
+}
+
+pub fn set_pause_flags(ctx: Context<SetPauseFlags>, pause_flags: u8) -> Result<()> {
+    // Validate flags (REDEMPTIONS_ONLY pauses everything but redeems)
+    require!(pause_flags & !PAUSE_ALL == 0, ContractError::InvalidPauseFlags);
+    
+    // Update pause flags
+    let old_pause_flags = ctx.accounts.token_config.pause_flags;
+    ctx.accounts.token_config.pause_flags = pause_flags;
+    
+    msg!("Pause flags updated to: {:#05b}", pause_flags);
+    
+    emit!(PauseFlagsUpdated {
+        token_mint: ctx.accounts.token_mint.key(),
+        signer: ctx.accounts.signer.key(),
+        old_pause_flags,
+        new_pause_flags: pause_flags,
+    });
+    
+    Ok(())
+}
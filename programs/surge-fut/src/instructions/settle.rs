@@ -0,0 +1,75 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::Mint;
+
+use crate::{state::*, errors::ContractError, events::*};
+
+#[derive(Accounts)]
+pub struct Settle<'info> {
+    /// Anyone can settle an expired token
+    pub settler: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"token_config", token_mint.key().as_ref()],
+        bump = token_config.bump,
+        constraint = !token_config.is_paused(PAUSE_UPDATES) @ ContractError::UpdatesPaused,
+    )]
+    pub token_config: Account<'info, TokenConfig>,
+    
+    /// Volatility token mint
+    #[account(
+        constraint = token_mint.key() == token_config.token_mint @ ContractError::InvalidOracleData,
+    )]
+    pub token_mint: InterfaceAccount<'info, Mint>,
+    
+    /// Oracle account with volatility data
+    /// CHECK: Account is validated through VolatilityStats::load_from_account_info
+    #[account(
+        constraint = oracle.key() == token_config.oracle @ ContractError::InvalidOracleData,
+    )]
+    pub oracle: AccountInfo<'info>,
+}
+
+pub fn settle(ctx: Context<Settle>) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    let token_config = &mut ctx.accounts.token_config;
+    
+    // Only dated tokens past expiry can be settled, and only once
+    require!(token_config.is_expired(now), ContractError::TokenNotExpired);
+    require!(!token_config.is_settled, ContractError::AlreadySettled);
+    
+    // The snapshot must come from an oracle update at or after expiry
+    let last_update = VolatilityStats::load_last_update_from_account_info(&ctx.accounts.oracle)?;
+    require!(
+        last_update >= token_config.expiry_timestamp,
+        ContractError::OracleStale
+    );
+    
+    // Settle on the TWAP ending at expiry when a window is configured, otherwise on
+    // the estimate in effect at expiry rather than whatever the oracle reports now
+    let settlement_volatility = VolatilityStats::load_settlement_volatility_from_account_info(
+        &ctx.accounts.oracle,
+        token_config.twap_window,
+        token_config.expiry_timestamp,
+    )?;
+    require!(settlement_volatility > 0.0, ContractError::InvalidOracleData);
+    
+    token_config.settlement_volatility = settlement_volatility;
+    token_config.is_settled = true;
+    
+    msg!("Token settled at volatility: {}", settlement_volatility);
+    
+    let settlement_vol_points = (settlement_volatility * 1000.0) as u64;
+    emit!(TokenSettled {
+        token_mint: ctx.accounts.token_mint.key(),
+        settler: ctx.accounts.settler.key(),
+        expiry_timestamp: token_config.expiry_timestamp,
+        settlement_volatility,
+        settlement_price: settlement_vol_points
+            .checked_mul(token_config.usdc_per_vol_point)
+            .ok_or(ContractError::MathOverflow)?
+            / 1000,
+    });
+    
+    Ok(())
+}
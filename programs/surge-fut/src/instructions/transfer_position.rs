@@ -0,0 +1,160 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked};
+use anchor_spl::associated_token::AssociatedToken;
+
+use crate::{state::*, errors::ContractError, events::*, trade::*};
+
+#[derive(Accounts)]
+#[instruction(amount: u64)]
+pub struct TransferPosition<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+    
+    /// Wallet receiving the tokens and their cost basis
+    /// CHECK: Any wallet can receive a position
+    pub recipient: AccountInfo<'info>,
+    
+    /// Volatility token mint
+    #[account(
+        constraint = token_mint.key() == token_config.token_mint @ ContractError::InvalidOracleData,
+    )]
+    pub token_mint: InterfaceAccount<'info, Mint>,
+    
+    /// Token Config
+    #[account(
+        seeds = [b"token_config", token_mint.key().as_ref()],
+        bump = token_config.bump,
+        constraint = !token_config.is_index_mode() @ ContractError::InvalidTokenMode,
+        constraint = !token_config.is_paused(PAUSE_MINTS) @ ContractError::MintsPaused,
+        constraint = !token_config.is_paused(PAUSE_REDEEMS) @ ContractError::RedeemsPaused,
+    )]
+    pub token_config: Account<'info, TokenConfig>,
+    
+    /// Volatility token account of the user
+    #[account(
+        mut,
+        constraint = user_token_account.owner == user.key() @ ContractError::Unauthorized,
+        constraint = user_token_account.mint == token_mint.key() @ ContractError::InvalidOracleData,
+    )]
+    pub user_token_account: InterfaceAccount<'info, TokenAccount>,
+    
+    /// Volatility token account of the recipient
+    #[account(
+        init_if_needed,
+        payer = user,
+        associated_token::mint = token_mint,
+        associated_token::authority = recipient,
+        associated_token::token_program = token_program,
+    )]
+    pub recipient_token_account: InterfaceAccount<'info, TokenAccount>,
+    
+    /// Sender's User Position Account
+    #[account(
+        mut,
+        seeds = [b"user_position", user.key().as_ref(), token_mint.key().as_ref()],
+        bump = user_position.bump,
+        constraint = user_position.owner == user.key() @ ContractError::Unauthorized,
+    )]
+    pub user_position: Account<'info, UserPosition>,
+    
+    /// Recipient's User Position Account
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = USER_POSITION_SPACE,
+        seeds = [b"user_position", recipient.key().as_ref(), token_mint.key().as_ref()],
+        bump,
+    )]
+    pub recipient_position: Account<'info, UserPosition>,
+    
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+}
+
+pub fn transfer_position(ctx: Context<TransferPosition>, amount: u64) -> Result<()> {
+    // Validate amount
+    require!(amount > 0, ContractError::InvalidAmount);
+    require!(
+        ctx.accounts.recipient.key() != ctx.accounts.user.key(),
+        ContractError::InvalidRecipient
+    );
+    require!(
+        ctx.accounts.user_token_account.amount >= amount,
+        ContractError::InsufficientTokens
+    );
+    require!(
+        ctx.accounts.user_position.tokens_minted >= amount,
+        ContractError::InsufficientTokens
+    );
+    
+    // Recipient position must stay within the position limit
+    let max_position_size = ctx.accounts.token_config.max_position_size;
+    let recipient_tokens = ctx
+        .accounts
+        .recipient_position
+        .tokens_minted
+        .checked_add(amount)
+        .ok_or(ContractError::MathOverflow)?;
+    require!(
+        max_position_size == 0 || recipient_tokens <= max_position_size,
+        ContractError::PositionLimitExceeded
+    );
+    
+    // Collateral moves proportionally with the tokens
+    let collateral_moved = ctx.accounts.user_position.collateral_for(amount)?;
+    let entry_volatility = ctx.accounts.user_position.entry_volatility;
+    
+    // Move the SPL tokens
+    token_interface::transfer_checked(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            TransferChecked {
+                from: ctx.accounts.user_token_account.to_account_info(),
+                mint: ctx.accounts.token_mint.to_account_info(),
+                to: ctx.accounts.recipient_token_account.to_account_info(),
+                authority: ctx.accounts.user.to_account_info(),
+            },
+        ),
+        amount,
+        ctx.accounts.token_mint.decimals,
+    )?;
+    
+    // Update the recipient position, blending entry volatility by token count
+    let recipient_position = &mut ctx.accounts.recipient_position;
+    
+    // If the position is being created for the first time
+    if recipient_position.owner == Pubkey::default() {
+        recipient_position.owner = ctx.accounts.recipient.key();
+        recipient_position.bump = ctx.bumps.recipient_position;
+        recipient_position.mint_timestamp = Clock::get()?.unix_timestamp;
+    }
+    
+    recipient_position.add_tokens(amount, collateral_moved, entry_volatility)?;
+    
+    // Update the sender position
+    ctx.accounts
+        .user_position
+        .remove_tokens(amount, collateral_moved)?;
+    
+    msg!("Transferred {} tokens to: {}", amount, ctx.accounts.recipient.key());
+    
+    emit!(PositionTransferred {
+        token_mint: ctx.accounts.token_mint.key(),
+        from: ctx.accounts.user.key(),
+        to: ctx.accounts.recipient.key(),
+        amount,
+        entry_volatility,
+        collateral_moved,
+        recipient_entry_volatility: ctx.accounts.recipient_position.entry_volatility,
+    });
+    
+    // Return rent to the user once the position is fully transferred
+    close_if_empty(
+        &mut ctx.accounts.user_position,
+        ctx.accounts.user.to_account_info(),
+        ctx.accounts.token_mint.key(),
+    )?;
+    
+    Ok(())
+}
@@ -0,0 +1,63 @@
+use anchor_lang::prelude::*;
+
+use crate::{state::*, errors::ContractError, events::*};
+
+#[derive(Accounts)]
+#[instruction(haircut_bps: u16, feed_id: [u8; 32], max_price_age: i64)]
+pub struct UpdateCollateralAsset<'info> {
+    pub authority: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"collateral_asset", authority.key().as_ref(), collateral_asset.mint.as_ref()],
+        bump = collateral_asset.bump,
+        constraint = collateral_asset.authority == authority.key() @ ContractError::Unauthorized,
+    )]
+    pub collateral_asset: Account<'info, CollateralAsset>,
+    
+    /// Pyth price update account, omit for a stablecoin pegged at $1
+    /// CHECK: Validated through load_pyth_price
+    pub price_feed: Option<AccountInfo<'info>>,
+}
+
+pub fn update_collateral_asset(
+    ctx: Context<UpdateCollateralAsset>,
+    haircut_bps: u16,
+    feed_id: [u8; 32],
+    max_price_age: i64,
+) -> Result<()> {
+    // Validate haircut and price age
+    require!(haircut_bps < 10000, ContractError::InvalidCollateralAsset);
+    require!(max_price_age > 0, ContractError::InvalidCollateralAsset);
+    
+    let price_feed = ctx
+        .accounts
+        .price_feed
+        .as_ref()
+        .map(|price_feed| price_feed.key())
+        .unwrap_or_default();
+    
+    let collateral_asset = &mut ctx.accounts.collateral_asset;
+    let old_haircut_bps = collateral_asset.haircut_bps;
+    let old_price_feed = collateral_asset.price_feed;
+    
+    collateral_asset.haircut_bps = haircut_bps;
+    collateral_asset.price_feed = price_feed;
+    collateral_asset.feed_id = feed_id;
+    collateral_asset.max_price_age = max_price_age;
+    
+    // Make sure the new feed can be read
+    collateral_asset.load_price(ctx.accounts.price_feed.as_ref())?;
+    
+    emit!(CollateralAssetUpdated {
+        collateral_asset: collateral_asset.key(),
+        mint: collateral_asset.mint,
+        old_haircut_bps,
+        new_haircut_bps: haircut_bps,
+        old_price_feed,
+        new_price_feed: price_feed,
+        max_price_age,
+    });
+    
+    Ok(())
+}
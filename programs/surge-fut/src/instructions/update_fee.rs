@@ -1,10 +1,10 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::Mint;
+use anchor_spl::token_interface::Mint;
 
-use crate::{state::*, errors::ContractError};
+use crate::{state::*, errors::ContractError, events::*};
 
 #[derive(Accounts)]
-#[instruction(new_fee_bps: u16)]
+#[instruction(new_mint_fee_bps: u16, new_redeem_fee_bps: u16)]
 pub struct UpdateFee<'info> {
     #[account(
         constraint = authority.key() == token_config.authority @ ContractError::Unauthorized,
@@ -15,6 +15,7 @@ pub struct UpdateFee<'info> {
         mut,
         seeds = [b"token_config", token_mint.key().as_ref()],
         bump = token_config.bump,
+        constraint = !token_config.is_paused(PAUSE_UPDATES) @ ContractError::UpdatesPaused,
     )]
     pub token_config: Account<'info, TokenConfig>,
     
@@ -22,18 +23,35 @@ pub struct UpdateFee<'info> {
     #[account(
         constraint = token_mint.key() == token_config.token_mint @ ContractError::InvalidOracleData,
     )]
-    pub token_mint: Account<'info, Mint>,
+    pub token_mint: InterfaceAccount<'info, Mint>,
 }
 
-pub fn update_fee(ctx: Context<UpdateFee>, new_fee_bps: u16) -> Result<()> {
-    // Validate fee percentage (maximum 10%)
-    require!(new_fee_bps <= 10000, ContractError::InvalidFeePercentage);
+pub fn update_fee(
+    ctx: Context<UpdateFee>,
+    new_mint_fee_bps: u16,
+    new_redeem_fee_bps: u16,
+) -> Result<()> {
+    // Validate fee percentages (maximum 100%)
+    require!(new_mint_fee_bps <= 10000, ContractError::InvalidFeePercentage);
+    require!(new_redeem_fee_bps <= 10000, ContractError::InvalidFeePercentage);
     
-    // Update fee
-    ctx.accounts.token_config.fee_bps = new_fee_bps;
+    // Update fees
+    let old_mint_fee_bps = ctx.accounts.token_config.mint_fee_bps;
+    let old_redeem_fee_bps = ctx.accounts.token_config.redeem_fee_bps;
+    ctx.accounts.token_config.mint_fee_bps = new_mint_fee_bps;
+    ctx.accounts.token_config.redeem_fee_bps = new_redeem_fee_bps;
     
-    msg!("Fee updated to: {}", new_fee_bps);
+    msg!("Mint fee updated to: {}", new_mint_fee_bps);
+    msg!("Redeem fee updated to: {}", new_redeem_fee_bps);
+    
+    emit!(FeeUpdated {
+        token_mint: ctx.accounts.token_mint.key(),
+        authority: ctx.accounts.authority.key(),
+        old_mint_fee_bps,
+        new_mint_fee_bps,
+        old_redeem_fee_bps,
+        new_redeem_fee_bps,
+    });
     
     Ok(())
 }
-
@@ -0,0 +1,137 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount};
+
+use crate::{state::*, errors::ContractError, events::*};
+
+#[derive(Accounts)]
+#[instruction(referral_share_bps: u16, insurance_share_bps: u16)]
+pub struct UpdateFeeSplit<'info> {
+    #[account(
+        constraint = authority.key() == token_config.authority @ ContractError::Unauthorized,
+    )]
+    pub authority: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"token_config", token_mint.key().as_ref()],
+        bump = token_config.bump,
+        constraint = !token_config.is_paused(PAUSE_UPDATES) @ ContractError::UpdatesPaused,
+    )]
+    pub token_config: Account<'info, TokenConfig>,
+    
+    /// Volatility token mint
+    #[account(
+        constraint = token_mint.key() == token_config.token_mint @ ContractError::InvalidOracleData,
+    )]
+    pub token_mint: InterfaceAccount<'info, Mint>,
+    
+    /// USDC account receiving the insurance share, omit to disable it.
+    /// Must be on the fee withdrawal allowlist, like any other fee destination.
+    #[account(
+        constraint = insurance_fund.mint == token_config.usdc_mint @ ContractError::InvalidOracleData,
+        constraint = token_config.is_allowed_fee_destination(&insurance_fund.key())
+            @ ContractError::FeeDestinationNotAllowed,
+    )]
+    pub insurance_fund: Option<InterfaceAccount<'info, TokenAccount>>,
+    
+    /// LP vault, required once the market has one so the combined shares can be checked
+    pub lp_vault: Option<Account<'info, LpVault>>,
+}
+
+pub fn update_fee_split(
+    ctx: Context<UpdateFeeSplit>,
+    referral_share_bps: u16,
+    insurance_share_bps: u16,
+) -> Result<()> {
+    // Validate shares
+    require!(referral_share_bps <= MAX_REFERRAL_SHARE_BPS, ContractError::InvalidFeePercentage);
+    require!(insurance_share_bps <= MAX_INSURANCE_SHARE_BPS, ContractError::InvalidFeePercentage);
+    
+    let insurance_fund = ctx
+        .accounts
+        .insurance_fund
+        .as_ref()
+        .map(|insurance_fund| insurance_fund.key())
+        .unwrap_or_default();
+    
+    // The LP and insurance shares are both taken from the fee left after rebates
+    let lp_share_bps = if ctx.accounts.token_config.lp_vault == Pubkey::default() {
+        0
+    } else {
+        match &ctx.accounts.lp_vault {
+            Some(lp_vault) if lp_vault.key() == ctx.accounts.token_config.lp_vault => {
+                lp_vault.fee_share_bps
+            }
+            _ => return err!(ContractError::LpVaultRequired),
+        }
+    };
+    if insurance_fund != Pubkey::default() {
+        require!(
+            lp_share_bps as u32 + insurance_share_bps as u32 <= 10000,
+            ContractError::InvalidFeeSplit
+        );
+    }
+    
+    let now = Clock::get()?.unix_timestamp;
+    let token_config = &mut ctx.accounts.token_config;
+    
+    // Fee shares divert protocol fees, so changes wait out the fee withdrawal timelock
+    let timelock = token_config.fee_withdrawal_policy.timelock;
+    if timelock > 0 {
+        let pending = token_config.pending_fee_split;
+        let queued = pending.unlock_timestamp != 0
+            && pending.referral_share_bps == referral_share_bps
+            && pending.insurance_fund == insurance_fund
+            && pending.insurance_share_bps == insurance_share_bps;
+        if !queued {
+            let unlock_timestamp = now
+                .checked_add(timelock)
+                .ok_or(ContractError::MathOverflow)?;
+            token_config.pending_fee_split = PendingFeeSplit {
+                referral_share_bps,
+                insurance_fund,
+                insurance_share_bps,
+                unlock_timestamp,
+            };
+            
+            msg!("Fee split queued until: {}", unlock_timestamp);
+            
+            emit!(FeeSplitQueued {
+                token_mint: ctx.accounts.token_mint.key(),
+                authority: ctx.accounts.authority.key(),
+                referral_share_bps,
+                insurance_fund,
+                insurance_share_bps,
+                unlock_timestamp,
+            });
+            
+            return Ok(());
+        }
+        
+        require!(
+            now >= pending.unlock_timestamp,
+            ContractError::FeeTimelockActive
+        );
+    }
+    
+    token_config.pending_fee_split = PendingFeeSplit::default();
+    token_config.referral_share_bps = referral_share_bps;
+    token_config.insurance_fund = insurance_fund;
+    token_config.insurance_share_bps = insurance_share_bps;
+    
+    msg!(
+        "Fee split updated: referral {}, insurance {}",
+        referral_share_bps,
+        insurance_share_bps
+    );
+    
+    emit!(FeeSplitUpdated {
+        token_mint: ctx.accounts.token_mint.key(),
+        authority: ctx.accounts.authority.key(),
+        referral_share_bps,
+        insurance_fund,
+        insurance_share_bps,
+    });
+    
+    Ok(())
+}
@@ -0,0 +1,54 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::Mint;
+
+use crate::{state::*, errors::ContractError, events::*};
+
+#[derive(Accounts)]
+#[instruction(fee_tiers: [FeeTier; MAX_FEE_TIERS])]
+pub struct UpdateFeeTiers<'info> {
+    #[account(
+        constraint = authority.key() == token_config.authority @ ContractError::Unauthorized,
+    )]
+    pub authority: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"token_config", token_mint.key().as_ref()],
+        bump = token_config.bump,
+        constraint = !token_config.is_paused(PAUSE_UPDATES) @ ContractError::UpdatesPaused,
+    )]
+    pub token_config: Account<'info, TokenConfig>,
+    
+    /// Volatility token mint
+    #[account(
+        constraint = token_mint.key() == token_config.token_mint @ ContractError::InvalidOracleData,
+    )]
+    pub token_mint: InterfaceAccount<'info, Mint>,
+}
+
+pub fn update_fee_tiers(
+    ctx: Context<UpdateFeeTiers>,
+    fee_tiers: [FeeTier; MAX_FEE_TIERS],
+) -> Result<()> {
+    // Tiers must be ordered by volume and discount at most the whole fee
+    require!(
+        fee_tiers.iter().all(|tier| tier.discount_bps <= 10000),
+        ContractError::InvalidFeeTiers
+    );
+    require!(
+        fee_tiers.windows(2).all(|pair| pair[0].min_volume <= pair[1].min_volume),
+        ContractError::InvalidFeeTiers
+    );
+    
+    ctx.accounts.token_config.fee_tiers = fee_tiers;
+    
+    msg!("Fee tiers updated");
+    
+    emit!(FeeTiersUpdated {
+        token_mint: ctx.accounts.token_mint.key(),
+        authority: ctx.accounts.authority.key(),
+        fee_tiers,
+    });
+    
+    Ok(())
+}
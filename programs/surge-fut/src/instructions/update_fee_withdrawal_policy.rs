@@ -0,0 +1,89 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::Mint;
+
+use crate::{state::*, errors::ContractError, events::*};
+
+#[derive(Accounts)]
+#[instruction(timelock: i64, allowlist: [Pubkey; MAX_FEE_DESTINATIONS])]
+pub struct UpdateFeeWithdrawalPolicy<'info> {
+    #[account(
+        constraint = authority.key() == token_config.authority @ ContractError::Unauthorized,
+    )]
+    pub authority: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"token_config", token_mint.key().as_ref()],
+        bump = token_config.bump,
+        constraint = !token_config.is_paused(PAUSE_UPDATES) @ ContractError::UpdatesPaused,
+    )]
+    pub token_config: Account<'info, TokenConfig>,
+    
+    /// Volatility token mint
+    #[account(
+        constraint = token_mint.key() == token_config.token_mint @ ContractError::InvalidOracleData,
+    )]
+    pub token_mint: InterfaceAccount<'info, Mint>,
+}
+
+pub fn update_fee_withdrawal_policy(
+    ctx: Context<UpdateFeeWithdrawalPolicy>,
+    timelock: i64,
+    allowlist: [Pubkey; MAX_FEE_DESTINATIONS],
+) -> Result<()> {
+    // Validate timelock
+    require!(
+        (0..=MAX_FEE_WITHDRAWAL_TIMELOCK).contains(&timelock),
+        ContractError::InvalidFeeWithdrawalPolicy
+    );
+    
+    let now = Clock::get()?.unix_timestamp;
+    let policy = FeeWithdrawalPolicy { timelock, allowlist };
+    let token_config = &mut ctx.accounts.token_config;
+    
+    // Changes wait out the current timelock so it cannot be bypassed by lowering it
+    let current_timelock = token_config.fee_withdrawal_policy.timelock;
+    if current_timelock > 0 {
+        let queued = token_config.pending_fee_withdrawal_policy_unlock != 0
+            && token_config.pending_fee_withdrawal_policy == policy;
+        if !queued {
+            let unlock_timestamp = now
+                .checked_add(current_timelock)
+                .ok_or(ContractError::MathOverflow)?;
+            token_config.pending_fee_withdrawal_policy = policy;
+            token_config.pending_fee_withdrawal_policy_unlock = unlock_timestamp;
+            
+            msg!("Fee withdrawal policy queued until: {}", unlock_timestamp);
+            
+            emit!(FeeWithdrawalPolicyQueued {
+                token_mint: ctx.accounts.token_mint.key(),
+                authority: ctx.accounts.authority.key(),
+                timelock,
+                allowlist,
+                unlock_timestamp,
+            });
+            
+            return Ok(());
+        }
+        
+        require!(
+            now >= token_config.pending_fee_withdrawal_policy_unlock,
+            ContractError::FeeTimelockActive
+        );
+    }
+    
+    token_config.fee_withdrawal_policy = policy;
+    token_config.pending_fee_withdrawal_policy = FeeWithdrawalPolicy::default();
+    token_config.pending_fee_withdrawal_policy_unlock = 0;
+    
+    msg!("Fee withdrawal policy updated, timelock: {}", timelock);
+    
+    emit!(FeeWithdrawalPolicyUpdated {
+        token_mint: ctx.accounts.token_mint.key(),
+        authority: ctx.accounts.authority.key(),
+        timelock,
+        allowlist,
+    });
+    
+    Ok(())
+}
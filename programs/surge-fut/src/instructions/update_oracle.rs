@@ -0,0 +1,52 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::Mint;
+
+use crate::{state::*, errors::ContractError, events::*};
+
+#[derive(Accounts)]
+pub struct UpdateOracle<'info> {
+    #[account(
+        constraint = authority.key() == token_config.authority @ ContractError::Unauthorized,
+    )]
+    pub authority: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"token_config", token_mint.key().as_ref()],
+        bump = token_config.bump,
+        constraint = !token_config.is_paused(PAUSE_UPDATES) @ ContractError::UpdatesPaused,
+    )]
+    pub token_config: Account<'info, TokenConfig>,
+    
+    /// Volatility token mint
+    #[account(
+        constraint = token_mint.key() == token_config.token_mint @ ContractError::InvalidOracleData,
+    )]
+    pub token_mint: InterfaceAccount<'info, Mint>,
+    
+    /// New oracle account with volatility data
+    /// CHECK: This account is validated in the handler
+    pub new_oracle: AccountInfo<'info>,
+}
+
+pub fn update_oracle(ctx: Context<UpdateOracle>) -> Result<()> {
+    // Validate the new oracle's volatility data
+    let volatility = VolatilityStats::load_from_account_info(&ctx.accounts.new_oracle)?;
+    require!(volatility > 0.0, ContractError::InvalidOracleData);
+    
+    // Update oracle
+    let old_oracle = ctx.accounts.token_config.oracle;
+    ctx.accounts.token_config.oracle = ctx.accounts.new_oracle.key();
+    
+    msg!("Oracle updated to: {}", ctx.accounts.new_oracle.key());
+    
+    emit!(OracleUpdated {
+        token_mint: ctx.accounts.token_mint.key(),
+        authority: ctx.accounts.authority.key(),
+        old_oracle,
+        new_oracle: ctx.accounts.new_oracle.key(),
+        volatility,
+    });
+    
+    Ok(())
+}
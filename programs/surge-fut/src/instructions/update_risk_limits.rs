@@ -0,0 +1,57 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::Mint;
+
+use crate::{state::*, errors::ContractError, events::*};
+
+#[derive(Accounts)]
+#[instruction(max_open_interest: u64, max_position_size: u64, max_mint_per_slot: u64)]
+pub struct UpdateRiskLimits<'info> {
+    #[account(
+        constraint = authority.key() == token_config.authority @ ContractError::Unauthorized,
+    )]
+    pub authority: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"token_config", token_mint.key().as_ref()],
+        bump = token_config.bump,
+        constraint = !token_config.is_paused(PAUSE_UPDATES) @ ContractError::UpdatesPaused,
+    )]
+    pub token_config: Account<'info, TokenConfig>,
+    
+    /// Volatility token mint
+    #[account(
+        constraint = token_mint.key() == token_config.token_mint @ ContractError::InvalidOracleData,
+    )]
+    pub token_mint: InterfaceAccount<'info, Mint>,
+}
+
+pub fn update_risk_limits(
+    ctx: Context<UpdateRiskLimits>,
+    max_open_interest: u64,
+    max_position_size: u64,
+    max_mint_per_slot: u64,
+) -> Result<()> {
+    // Update limits, 0 disables a limit
+    let token_config = &mut ctx.accounts.token_config;
+    token_config.max_open_interest = max_open_interest;
+    token_config.max_position_size = max_position_size;
+    token_config.max_mint_per_slot = max_mint_per_slot;
+    
+    msg!(
+        "Risk limits updated: open interest {}, position {}, per slot {}",
+        max_open_interest,
+        max_position_size,
+        max_mint_per_slot
+    );
+    
+    emit!(RiskLimitsUpdated {
+        token_mint: ctx.accounts.token_mint.key(),
+        authority: ctx.accounts.authority.key(),
+        max_open_interest,
+        max_position_size,
+        max_mint_per_slot,
+    });
+    
+    Ok(())
+}
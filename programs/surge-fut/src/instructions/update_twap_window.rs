@@ -0,0 +1,53 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::Mint;
+
+use crate::{state::*, errors::ContractError, events::*};
+
+#[derive(Accounts)]
+#[instruction(new_twap_window: i64)]
+pub struct UpdateTwapWindow<'info> {
+    #[account(
+        constraint = authority.key() == token_config.authority @ ContractError::Unauthorized,
+    )]
+    pub authority: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"token_config", token_mint.key().as_ref()],
+        bump = token_config.bump,
+        constraint = !token_config.is_paused(PAUSE_UPDATES) @ ContractError::UpdatesPaused,
+    )]
+    pub token_config: Account<'info, TokenConfig>,
+    
+    /// Volatility token mint
+    #[account(
+        constraint = token_mint.key() == token_config.token_mint @ ContractError::InvalidOracleData,
+    )]
+    pub token_mint: InterfaceAccount<'info, Mint>,
+}
+
+pub fn update_twap_window(
+    ctx: Context<UpdateTwapWindow>,
+    new_twap_window: i64,
+) -> Result<()> {
+    // Validate window, 0 switches pricing back to spot volatility
+    require!(
+        (0..=MAX_TWAP_WINDOW).contains(&new_twap_window),
+        ContractError::InvalidTwapWindow
+    );
+    
+    // Update TWAP window
+    let old_twap_window = ctx.accounts.token_config.twap_window;
+    ctx.accounts.token_config.twap_window = new_twap_window;
+    
+    msg!("TWAP window updated to: {}", new_twap_window);
+    
+    emit!(TwapWindowUpdated {
+        token_mint: ctx.accounts.token_mint.key(),
+        authority: ctx.accounts.authority.key(),
+        old_twap_window,
+        new_twap_window,
+    });
+    
+    Ok(())
+}
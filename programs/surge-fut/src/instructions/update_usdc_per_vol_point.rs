@@ -0,0 +1,57 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::Mint;
+
+use crate::{state::*, errors::ContractError, events::*};
+
+#[derive(Accounts)]
+#[instruction(new_usdc_per_vol_point: u64)]
+pub struct UpdateUsdcPerVolPoint<'info> {
+    #[account(
+        constraint = authority.key() == token_config.authority @ ContractError::Unauthorized,
+    )]
+    pub authority: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"token_config", token_mint.key().as_ref()],
+        bump = token_config.bump,
+        constraint = !token_config.is_paused(PAUSE_UPDATES) @ ContractError::UpdatesPaused,
+    )]
+    pub token_config: Account<'info, TokenConfig>,
+    
+    /// Volatility token mint
+    #[account(
+        constraint = token_mint.key() == token_config.token_mint @ ContractError::InvalidOracleData,
+    )]
+    pub token_mint: InterfaceAccount<'info, Mint>,
+}
+
+pub fn update_usdc_per_vol_point(
+    ctx: Context<UpdateUsdcPerVolPoint>,
+    new_usdc_per_vol_point: u64,
+) -> Result<()> {
+    // Validate pricing
+    require!(new_usdc_per_vol_point > 0, ContractError::InvalidAmount);
+    
+    // Repricing outstanding index tokens would break their full collateralization
+    let token_config = &ctx.accounts.token_config;
+    require!(
+        !token_config.is_index_mode() || token_config.total_tokens_outstanding == 0,
+        ContractError::InvalidTokenMode
+    );
+    
+    // Update pricing
+    let old_usdc_per_vol_point = ctx.accounts.token_config.usdc_per_vol_point;
+    ctx.accounts.token_config.usdc_per_vol_point = new_usdc_per_vol_point;
+    
+    msg!("USDC per vol point updated to: {}", new_usdc_per_vol_point);
+    
+    emit!(UsdcPerVolPointUpdated {
+        token_mint: ctx.accounts.token_mint.key(),
+        authority: ctx.accounts.authority.key(),
+        old_usdc_per_vol_point,
+        new_usdc_per_vol_point,
+    });
+    
+    Ok(())
+}
@@ -0,0 +1,137 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked};
+
+use crate::{state::*, errors::ContractError, events::*};
+
+#[derive(Accounts)]
+#[instruction(amount: u64)]
+pub struct WithdrawFees<'info> {
+    #[account(
+        constraint = authority.key() == token_config.authority @ ContractError::Unauthorized,
+    )]
+    pub authority: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"token_config", token_mint.key().as_ref()],
+        bump = token_config.bump,
+    )]
+    pub token_config: Account<'info, TokenConfig>,
+    
+    /// Volatility token mint
+    #[account(
+        constraint = token_mint.key() == token_config.token_mint @ ContractError::InvalidOracleData,
+    )]
+    pub token_mint: InterfaceAccount<'info, Mint>,
+    
+    /// Fee vault USDC account
+    #[account(
+        mut,
+        seeds = [b"fee_vault", token_mint.key().as_ref()],
+        bump = token_config.fee_vault_bump,
+        constraint = fee_vault.key() == token_config.fee_vault @ ContractError::Unauthorized,
+    )]
+    pub fee_vault: InterfaceAccount<'info, TokenAccount>,
+    
+    /// USDC account receiving the fees, must be on the allowlist if one is set
+    #[account(
+        mut,
+        constraint = destination.mint == token_config.usdc_mint @ ContractError::InvalidOracleData,
+        constraint = token_config.is_allowed_fee_destination(&destination.key())
+            @ ContractError::FeeDestinationNotAllowed,
+    )]
+    pub destination: InterfaceAccount<'info, TokenAccount>,
+    
+    /// USDC mint
+    #[account(
+        constraint = usdc_mint.key() == token_config.usdc_mint @ ContractError::InvalidOracleData,
+    )]
+    pub usdc_mint: InterfaceAccount<'info, Mint>,
+    
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+pub fn withdraw_fees(ctx: Context<WithdrawFees>, amount: u64) -> Result<()> {
+    // Validate amount
+    require!(amount > 0, ContractError::InvalidAmount);
+    require!(
+        ctx.accounts.fee_vault.amount >= amount,
+        ContractError::InsufficientBalance
+    );
+    
+    let now = Clock::get()?.unix_timestamp;
+    let destination = ctx.accounts.destination.key();
+    let token_config = &mut ctx.accounts.token_config;
+    
+    // With a timelock the first call queues the withdrawal and a matching call executes it
+    let timelock = token_config.fee_withdrawal_policy.timelock;
+    if timelock > 0 {
+        let pending = token_config.pending_fee_withdrawal;
+        if pending.amount != amount || pending.destination != destination {
+            let unlock_timestamp = now
+                .checked_add(timelock)
+                .ok_or(ContractError::MathOverflow)?;
+            token_config.pending_fee_withdrawal = PendingFeeWithdrawal {
+                destination,
+                amount,
+                unlock_timestamp,
+            };
+            
+            msg!("Fee withdrawal queued until: {}", unlock_timestamp);
+            
+            emit!(FeeWithdrawalQueued {
+                token_mint: ctx.accounts.token_mint.key(),
+                authority: ctx.accounts.authority.key(),
+                destination,
+                amount,
+                unlock_timestamp,
+            });
+            
+            return Ok(());
+        }
+        
+        require!(now >= pending.unlock_timestamp, ContractError::FeeTimelockActive);
+        token_config.pending_fee_withdrawal = PendingFeeWithdrawal::default();
+    }
+    
+    // Transfer fees out of the vault
+    let token_mint_key = ctx.accounts.token_mint.key();
+    let token_config_seeds = &[
+        b"token_config",
+        token_mint_key.as_ref(),
+        &[ctx.accounts.token_config.bump]
+    ];
+    let signer = &[&token_config_seeds[..]];
+    
+    token_interface::transfer_checked(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            TransferChecked {
+                from: ctx.accounts.fee_vault.to_account_info(),
+                mint: ctx.accounts.usdc_mint.to_account_info(),
+                to: ctx.accounts.destination.to_account_info(),
+                authority: ctx.accounts.token_config.to_account_info(),
+            },
+            signer,
+        ),
+        amount,
+        ctx.accounts.usdc_mint.decimals,
+    )?;
+    
+    msg!("Fees withdrawn: {}", amount);
+    
+    emit!(FeesWithdrawn {
+        token_mint: token_mint_key,
+        authority: ctx.accounts.authority.key(),
+        destination,
+        amount,
+        remaining_balance: ctx
+            .accounts
+            .fee_vault
+            .amount
+            .checked_sub(amount)
+            .ok_or(ContractError::MathOverflow)?,
+    });
+    
+    Ok(())
+}
@@ -0,0 +1,209 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{self, Burn, Mint, TokenAccount, TokenInterface, TransferChecked};
+use anchor_spl::token_2022::Token2022;
+
+use crate::{state::*, errors::ContractError, events::*, trade::*};
+
+#[derive(Accounts)]
+#[instruction(shares: u64)]
+pub struct WithdrawLiquidity<'info> {
+    pub provider: Signer<'info>,
+
+    /// USDC token account to receive funds
+    #[account(
+        mut,
+        constraint = provider_usdc_account.owner == provider.key() @ ContractError::Unauthorized,
+        constraint = provider_usdc_account.mint == token_config.usdc_mint @ ContractError::InvalidOracleData,
+    )]
+    pub provider_usdc_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// LP share token account of the liquidity provider
+    #[account(
+        mut,
+        constraint = provider_lp_account.owner == provider.key() @ ContractError::Unauthorized,
+        constraint = provider_lp_account.mint == lp_mint.key() @ ContractError::InvalidOracleData,
+    )]
+    pub provider_lp_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// Volatility token mint
+    #[account(
+        constraint = token_mint.key() == token_config.token_mint @ ContractError::InvalidOracleData,
+    )]
+    pub token_mint: InterfaceAccount<'info, Mint>,
+
+    /// Token Config
+    #[account(
+        seeds = [b"token_config", token_mint.key().as_ref()],
+        bump = token_config.bump,
+        constraint = !token_config.is_paused(PAUSE_REDEEMS) @ ContractError::RedeemsPaused,
+    )]
+    pub token_config: Account<'info, TokenConfig>,
+
+    /// Collateral pool USDC account, used to measure vault utilization
+    #[account(
+        seeds = [b"collateral_pool", token_mint.key().as_ref()],
+        bump = token_config.collateral_pool_bump,
+        constraint = collateral_pool.key() == token_config.collateral_pool @ ContractError::Unauthorized,
+    )]
+    pub collateral_pool: InterfaceAccount<'info, TokenAccount>,
+
+    /// Oracle account with volatility data, used to mark open trader positions
+    /// CHECK: Account is validated through VolatilityStats::load_from_account_info
+    #[account(
+        constraint = oracle.key() == token_config.oracle @ ContractError::InvalidOracleData,
+    )]
+    pub oracle: AccountInfo<'info>,
+
+    /// LP vault state
+    #[account(
+        mut,
+        seeds = [b"lp_vault", token_mint.key().as_ref()],
+        bump = lp_vault.bump,
+        constraint = lp_vault.key() == token_config.lp_vault @ ContractError::LpVaultRequired,
+    )]
+    pub lp_vault: Account<'info, LpVault>,
+
+    /// LP share token mint
+    #[account(
+        mut,
+        seeds = [b"lp_mint", token_mint.key().as_ref()],
+        bump = lp_vault.lp_mint_bump,
+    )]
+    pub lp_mint: InterfaceAccount<'info, Mint>,
+
+    /// USDC liquidity held by the vault
+    #[account(
+        mut,
+        seeds = [b"lp_usdc_vault", token_mint.key().as_ref()],
+        bump = lp_vault.usdc_vault_bump,
+    )]
+    pub lp_usdc_vault: InterfaceAccount<'info, TokenAccount>,
+
+    /// Liquidity provider position
+    #[account(
+        seeds = [b"lp_position", provider.key().as_ref(), token_mint.key().as_ref()],
+        bump = lp_position.bump,
+        constraint = lp_position.owner == provider.key() @ ContractError::Unauthorized,
+    )]
+    pub lp_position: Account<'info, LpPosition>,
+
+    /// Collateral registry entry, required once the market has one
+    pub collateral_asset: Option<Account<'info, CollateralAsset>>,
+
+    /// Pyth price update for the collateral asset
+    /// CHECK: Validated through CollateralAsset::load_price
+    pub collateral_price_feed: Option<AccountInfo<'info>>,
+
+    /// USDC mint
+    #[account(
+        constraint = usdc_mint.key() == token_config.usdc_mint @ ContractError::InvalidOracleData,
+    )]
+    pub usdc_mint: InterfaceAccount<'info, Mint>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    /// Token-2022 program owning the LP mint
+    pub lp_token_program: Program<'info, Token2022>,
+}
+
+pub fn withdraw_liquidity(ctx: Context<WithdrawLiquidity>, shares: u64) -> Result<()> {
+    // Validate amount
+    require!(shares > 0, ContractError::InvalidAmount);
+    require!(
+        ctx.accounts.provider_lp_account.amount >= shares,
+        ContractError::InsufficientTokens
+    );
+
+    // Enforce the withdrawal cooldown
+    let now = Clock::get()?.unix_timestamp;
+    let unlock_time = ctx
+        .accounts
+        .lp_position
+        .last_deposit_timestamp
+        .checked_add(ctx.accounts.lp_vault.withdrawal_cooldown)
+        .ok_or(ContractError::MathOverflow)?;
+    require!(now >= unlock_time, ContractError::WithdrawalCooldownActive);
+
+    // Calculate USDC owed for the shares against the vault's net assets
+    let collateral_price = resolve_collateral_price(
+        &ctx.accounts.token_config,
+        &ctx.accounts.collateral_asset,
+        &ctx.accounts.collateral_price_feed,
+    )?;
+    let net_assets = lp_net_assets(
+        &ctx.accounts.token_config,
+        &ctx.accounts.lp_vault,
+        &ctx.accounts.oracle,
+        &collateral_price,
+    )?;
+    let usdc_out = LpVault::assets_for_shares(shares, ctx.accounts.lp_mint.supply, net_assets)?;
+
+    msg!("USDC to withdraw: {}", usdc_out);
+
+    // Remaining liquidity must keep trader collateral within the utilization limit
+    let vault_balance = ctx.accounts.lp_usdc_vault.amount;
+    let remaining = vault_balance
+        .checked_sub(usdc_out)
+        .ok_or(ContractError::InsufficientLiquidity)?;
+    require!(
+        ctx.accounts
+            .lp_vault
+            .within_utilization(ctx.accounts.collateral_pool.amount, remaining)?,
+        ContractError::UtilizationLimitExceeded
+    );
+
+    // Burn the LP shares
+    token_interface::burn(
+        CpiContext::new(
+            ctx.accounts.lp_token_program.to_account_info(),
+            Burn {
+                mint: ctx.accounts.lp_mint.to_account_info(),
+                from: ctx.accounts.provider_lp_account.to_account_info(),
+                authority: ctx.accounts.provider.to_account_info(),
+            },
+        ),
+        shares,
+    )?;
+
+    // Transfer USDC to the provider
+    let token_mint_key = ctx.accounts.token_mint.key();
+    let lp_vault_seeds = &[
+        b"lp_vault",
+        token_mint_key.as_ref(),
+        &[ctx.accounts.lp_vault.bump]
+    ];
+    let signer = &[&lp_vault_seeds[..]];
+
+    token_interface::transfer_checked(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            TransferChecked {
+                from: ctx.accounts.lp_usdc_vault.to_account_info(),
+                mint: ctx.accounts.usdc_mint.to_account_info(),
+                to: ctx.accounts.provider_usdc_account.to_account_info(),
+                authority: ctx.accounts.lp_vault.to_account_info(),
+            },
+            signer,
+        ),
+        usdc_out,
+        ctx.accounts.usdc_mint.decimals,
+    )?;
+
+    ctx.accounts.lp_vault.debit(usdc_out)?;
+
+    emit!(LiquidityWithdrawn {
+        token_mint: ctx.accounts.token_mint.key(),
+        provider: ctx.accounts.provider.key(),
+        shares,
+        amount: usdc_out,
+        vault_balance: remaining,
+        net_assets: net_assets.saturating_sub(usdc_out),
+        share_supply: ctx
+            .accounts
+            .lp_mint
+            .supply
+            .checked_sub(shares)
+            .ok_or(ContractError::MathOverflow)?,
+    });
+
+    Ok(())
+}
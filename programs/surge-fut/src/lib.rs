@@ -3,8 +3,12 @@ use anchor_lang::prelude::*;
 pub mod errors;
 pub mod state;
 pub mod instructions;
+pub mod events;
+pub mod fees;
+pub mod trade;
 
 use instructions::*;
+use state::{FeeTier, OrderSide, TriggerCondition, MAX_FEE_DESTINATIONS, MAX_FEE_TIERS};
 
 declare_id!("CarydvHuPVR4TZbnPQjnEbrNWXFohefCYHEoWsZMPDvZ");
 
@@ -16,30 +20,247 @@ pub mod surge_fut {
         ctx: Context<Initialize>, 
         token_name: String, 
         token_symbol: String, 
-        fee_bps: u16
+        fee_bps: u16,
+        expiry_timestamp: i64,
+        vol_cap: f64,
+        token_uri: String,
     ) -> Result<()> {
-       instructions::initialize::initialize(ctx, token_name, token_symbol, fee_bps)
+       instructions::initialize::initialize(ctx, token_name, token_symbol, fee_bps, expiry_timestamp, vol_cap, token_uri)
     }
 
     pub fn mint_tokens(
         ctx: Context<MintTokens>,
         amount: u64,
+        max_usdc_in: u64,
+        max_oracle_age: Option<i64>,
     ) -> Result<()> {
-        instructions::mint_tokens::mint_tokens(ctx, amount)
+        instructions::mint_tokens::mint_tokens(ctx, amount, max_usdc_in, max_oracle_age)
     }
 
     pub fn redeem_tokens(
         ctx: Context<RedeemTokens>,
         amount: u64,
+        min_usdc_out: u64,
+        max_oracle_age: Option<i64>,
     ) -> Result<()> {
-        instructions::redeem_tokens::redeem_tokens(ctx, amount)
+        instructions::redeem_tokens::redeem_tokens(ctx, amount, min_usdc_out, max_oracle_age)
     }
 
     pub fn update_fee(
         ctx: Context<UpdateFee>,
-        new_fee_bps: u16,
+        new_mint_fee_bps: u16,
+        new_redeem_fee_bps: u16,
     ) -> Result<()> {
-        instructions::update_fee::update_fee(ctx, new_fee_bps)
+        instructions::update_fee::update_fee(ctx, new_mint_fee_bps, new_redeem_fee_bps)
+    }
+
+    pub fn initialize_lp_vault(
+        ctx: Context<InitializeLpVault>,
+        fee_share_bps: u16,
+        max_utilization_bps: u16,
+        withdrawal_cooldown: i64,
+    ) -> Result<()> {
+        instructions::initialize_lp_vault::initialize_lp_vault(
+            ctx,
+            fee_share_bps,
+            max_utilization_bps,
+            withdrawal_cooldown,
+        )
+    }
+
+    pub fn deposit_liquidity(
+        ctx: Context<DepositLiquidity>,
+        amount: u64,
+    ) -> Result<()> {
+        instructions::deposit_liquidity::deposit_liquidity(ctx, amount)
+    }
+
+    pub fn withdraw_liquidity(
+        ctx: Context<WithdrawLiquidity>,
+        shares: u64,
+    ) -> Result<()> {
+        instructions::withdraw_liquidity::withdraw_liquidity(ctx, shares)
+    }
+
+    pub fn update_usdc_per_vol_point(
+        ctx: Context<UpdateUsdcPerVolPoint>,
+        new_usdc_per_vol_point: u64,
+    ) -> Result<()> {
+        instructions::update_usdc_per_vol_point::update_usdc_per_vol_point(ctx, new_usdc_per_vol_point)
+    }
+
+    pub fn update_oracle(ctx: Context<UpdateOracle>) -> Result<()> {
+        instructions::update_oracle::update_oracle(ctx)
+    }
+
+    pub fn propose_authority(
+        ctx: Context<ProposeAuthority>,
+        new_authority: Pubkey,
+    ) -> Result<()> {
+        instructions::propose_authority::propose_authority(ctx, new_authority)
+    }
+
+    pub fn accept_authority(ctx: Context<AcceptAuthority>) -> Result<()> {
+        instructions::accept_authority::accept_authority(ctx)
+    }
+
+    pub fn set_guardian(
+        ctx: Context<SetGuardian>,
+        new_guardian: Pubkey,
+    ) -> Result<()> {
+        instructions::set_guardian::set_guardian(ctx, new_guardian)
+    }
+
+    pub fn set_pause_flags(
+        ctx: Context<SetPauseFlags>,
+        pause_flags: u8,
+    ) -> Result<()> {
+        instructions::set_pause_flags::set_pause_flags(ctx, pause_flags)
+    }
+
+    pub fn settle(ctx: Context<Settle>) -> Result<()> {
+        instructions::settle::settle(ctx)
+    }
+
+    pub fn update_twap_window(
+        ctx: Context<UpdateTwapWindow>,
+        new_twap_window: i64,
+    ) -> Result<()> {
+        instructions::update_twap_window::update_twap_window(ctx, new_twap_window)
+    }
+
+    pub fn update_risk_limits(
+        ctx: Context<UpdateRiskLimits>,
+        max_open_interest: u64,
+        max_position_size: u64,
+        max_mint_per_slot: u64,
+    ) -> Result<()> {
+        instructions::update_risk_limits::update_risk_limits(
+            ctx,
+            max_open_interest,
+            max_position_size,
+            max_mint_per_slot,
+        )
+    }
+
+    pub fn close_position(ctx: Context<ClosePosition>) -> Result<()> {
+        instructions::close_position::close_position(ctx)
+    }
+
+    pub fn transfer_position(
+        ctx: Context<TransferPosition>,
+        amount: u64,
+    ) -> Result<()> {
+        instructions::transfer_position::transfer_position(ctx, amount)
     }
-}
 
+    pub fn set_delegate(
+        ctx: Context<SetDelegate>,
+        delegate: Pubkey,
+        amount: u64,
+    ) -> Result<()> {
+        instructions::set_delegate::set_delegate(ctx, delegate, amount)
+    }
+
+    pub fn mint_index_tokens(
+        ctx: Context<MintIndexTokens>,
+        amount: u64,
+        max_usdc_in: u64,
+        max_oracle_age: Option<i64>,
+    ) -> Result<()> {
+        instructions::mint_index_tokens::mint_index_tokens(ctx, amount, max_usdc_in, max_oracle_age)
+    }
+
+    pub fn redeem_index_tokens(
+        ctx: Context<RedeemIndexTokens>,
+        amount: u64,
+        min_usdc_out: u64,
+        max_oracle_age: Option<i64>,
+    ) -> Result<()> {
+        instructions::redeem_index_tokens::redeem_index_tokens(ctx, amount, min_usdc_out, max_oracle_age)
+    }
+
+    pub fn update_fee_tiers(
+        ctx: Context<UpdateFeeTiers>,
+        fee_tiers: [FeeTier; MAX_FEE_TIERS],
+    ) -> Result<()> {
+        instructions::update_fee_tiers::update_fee_tiers(ctx, fee_tiers)
+    }
+
+    pub fn update_fee_split(
+        ctx: Context<UpdateFeeSplit>,
+        referral_share_bps: u16,
+        insurance_share_bps: u16,
+    ) -> Result<()> {
+        instructions::update_fee_split::update_fee_split(ctx, referral_share_bps, insurance_share_bps)
+    }
+
+    pub fn withdraw_fees(ctx: Context<WithdrawFees>, amount: u64) -> Result<()> {
+        instructions::withdraw_fees::withdraw_fees(ctx, amount)
+    }
+
+    pub fn update_fee_withdrawal_policy(
+        ctx: Context<UpdateFeeWithdrawalPolicy>,
+        timelock: i64,
+        allowlist: [Pubkey; MAX_FEE_DESTINATIONS],
+    ) -> Result<()> {
+        instructions::update_fee_withdrawal_policy::update_fee_withdrawal_policy(ctx, timelock, allowlist)
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn place_order(
+        ctx: Context<PlaceOrder>,
+        order_id: u64,
+        side: OrderSide,
+        trigger: TriggerCondition,
+        trigger_volatility: f64,
+        amount: u64,
+        limit_usdc: u64,
+        keeper_fee: u64,
+    ) -> Result<()> {
+        instructions::place_order::place_order(
+            ctx,
+            order_id,
+            side,
+            trigger,
+            trigger_volatility,
+            amount,
+            limit_usdc,
+            keeper_fee,
+        )
+    }
+
+    pub fn execute_order(ctx: Context<ExecuteOrder>, max_oracle_age: Option<i64>) -> Result<()> {
+        instructions::execute_order::execute_order(ctx, max_oracle_age)
+    }
+
+    pub fn cancel_order(ctx: Context<CancelOrder>) -> Result<()> {
+        instructions::cancel_order::cancel_order(ctx)
+    }
+
+    pub fn add_collateral_asset(
+        ctx: Context<AddCollateralAsset>,
+        haircut_bps: u16,
+        feed_id: [u8; 32],
+        max_price_age: i64,
+    ) -> Result<()> {
+        instructions::add_collateral_asset::add_collateral_asset(ctx, haircut_bps, feed_id, max_price_age)
+    }
+
+    pub fn update_collateral_asset(
+        ctx: Context<UpdateCollateralAsset>,
+        haircut_bps: u16,
+        feed_id: [u8; 32],
+        max_price_age: i64,
+    ) -> Result<()> {
+        instructions::update_collateral_asset::update_collateral_asset(ctx, haircut_bps, feed_id, max_price_age)
+    }
+
+    pub fn register_referrer(ctx: Context<RegisterReferrer>) -> Result<()> {
+        instructions::register_referrer::register_referrer(ctx)
+    }
+
+    pub fn migrate_token_config(ctx: Context<MigrateTokenConfig>) -> Result<()> {
+        instructions::migrate_token_config::migrate_token_config(ctx)
+    }
+}
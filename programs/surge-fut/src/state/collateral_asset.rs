@@ -0,0 +1,238 @@
+use anchor_lang::prelude::*;
+
+use crate::{state::TokenConfig, errors::ContractError};
+
+/// Pyth receiver program owning PriceUpdateV2 accounts
+pub const PYTH_RECEIVER_PROGRAM_ID: Pubkey = pubkey!("rec5EKMGg6MxZYaMdyBfgwp4d5rB9T1VQH5pJv5LtFJ");
+
+/// Prices and USD amounts are expressed with 6 decimals
+pub const USD_DECIMALS: u32 = 6;
+
+#[account]
+pub struct CollateralAsset {
+    pub authority: Pubkey,           // Authority maintaining this registry entry
+    pub mint: Pubkey,                // Collateral mint
+    pub decimals: u8,                // Decimals of the collateral mint
+    pub haircut_bps: u16,            // Discount applied when valuing deposits of this asset
+    pub price_feed: Pubkey,          // Pyth price update account (default for a $1 peg)
+    pub feed_id: [u8; 32],           // Pyth feed id expected in the price update
+    pub max_price_age: i64,          // Max age in seconds of the price used for conversion
+    pub bump: u8,                    // PDA bump
+}
+
+/// Conversion between USD amounts and collateral base units
+pub struct CollateralPrice {
+    pub price: u64,                  // USD per whole collateral token, 6 decimals
+    pub decimals: u8,                // Decimals of the collateral mint
+    pub haircut_bps: u16,            // Haircut applied to deposits
+}
+
+impl CollateralPrice {
+    /// Legacy markets collateralized by 6-decimal USDC convert 1:1
+    pub const USDC: CollateralPrice = CollateralPrice {
+        price: 1_000_000,
+        decimals: 6,
+        haircut_bps: 0,
+    };
+
+    /// Collateral a user must deposit to cover `usd`, grossed up by the haircut and rounded up
+    pub fn usd_to_collateral_in(&self, usd: u64) -> Result<u64> {
+        let numerator = (usd as u128)
+            .checked_mul(10u128.pow(self.decimals as u32))
+            .and_then(|value| value.checked_mul(10000))
+            .ok_or(ContractError::MathOverflow)?;
+        let denominator = (self.price as u128)
+            .checked_mul(10000 - self.haircut_bps as u128)
+            .ok_or(ContractError::MathOverflow)?;
+        require!(denominator > 0, ContractError::InvalidCollateralPrice);
+
+        let units = numerator
+            .checked_add(denominator - 1)
+            .ok_or(ContractError::MathOverflow)?
+            / denominator;
+        u64::try_from(units).map_err(|_| ContractError::MathOverflow.into())
+    }
+
+    /// Collateral paid out for `usd`, rounded down
+    pub fn usd_to_collateral_out(&self, usd: u64) -> Result<u64> {
+        require!(self.price > 0, ContractError::InvalidCollateralPrice);
+
+        let units = (usd as u128)
+            .checked_mul(10u128.pow(self.decimals as u32))
+            .ok_or(ContractError::MathOverflow)?
+            / self.price as u128;
+        u64::try_from(units).map_err(|_| ContractError::MathOverflow.into())
+    }
+}
+
+impl CollateralAsset {
+    /// Current conversion price, read from the Pyth price update unless the asset is pegged
+    pub fn load_price(&self, price_feed: Option<&AccountInfo>) -> Result<CollateralPrice> {
+        let price = if self.price_feed == Pubkey::default() {
+            10u64.pow(USD_DECIMALS)
+        } else {
+            let price_feed = price_feed
+                .filter(|price_feed| price_feed.key() == self.price_feed)
+                .ok_or(ContractError::InvalidCollateralPrice)?;
+            load_pyth_price(price_feed, &self.feed_id, self.max_price_age)?
+        };
+
+        Ok(CollateralPrice {
+            price,
+            decimals: self.decimals,
+            haircut_bps: self.haircut_bps,
+        })
+    }
+}
+
+/// Reads a fully verified Pyth PriceUpdateV2 account and returns the price in USD with 6 decimals
+pub fn load_pyth_price(account_info: &AccountInfo, feed_id: &[u8; 32], max_age: i64) -> Result<u64> {
+    require!(
+        account_info.owner == &PYTH_RECEIVER_PROGRAM_ID,
+        ContractError::InvalidCollateralPrice
+    );
+
+    let data = account_info.try_borrow_data()?;
+
+    // Skip the 8-byte discriminator and write_authority (Pubkey - 32 bytes)
+    let mut offset = 8 + 32;
+
+    // verification_level is a borsh enum, only Full (variant 1) is accepted
+    require!(
+        data.get(offset) == Some(&1),
+        ContractError::InvalidCollateralPrice
+    );
+    offset += 1;
+
+    // feed_id (32), price (i64), conf (u64), exponent (i32), publish_time (i64)
+    if data.len() < offset + 32 + 8 + 8 + 4 + 8 {
+        return Err(ContractError::InvalidCollateralPrice.into());
+    }
+    require!(
+        &data[offset..offset + 32] == feed_id,
+        ContractError::InvalidCollateralPrice
+    );
+    offset += 32;
+
+    let price = i64::from_le_bytes(data[offset..offset + 8].try_into().unwrap());
+    offset += 8 + 8;
+    let exponent = i32::from_le_bytes(data[offset..offset + 4].try_into().unwrap());
+    offset += 4;
+    let publish_time = i64::from_le_bytes(data[offset..offset + 8].try_into().unwrap());
+
+    let age = Clock::get()?
+        .unix_timestamp
+        .checked_sub(publish_time)
+        .ok_or(ContractError::MathOverflow)?;
+    require!(age <= max_age, ContractError::OracleStale);
+    require!(price > 0, ContractError::InvalidCollateralPrice);
+
+    // Rescale price * 10^exponent to 6 decimals
+    let scale = exponent + USD_DECIMALS as i32;
+    let price = if scale >= 0 {
+        (price as u128)
+            .checked_mul(10u128.pow(scale as u32))
+            .ok_or(ContractError::MathOverflow)?
+    } else {
+        (price as u128) / 10u128.pow(scale.unsigned_abs())
+    };
+    require!(price > 0, ContractError::InvalidCollateralPrice);
+
+    u64::try_from(price).map_err(|_| ContractError::MathOverflow.into())
+}
+
+/// Resolves the conversion price for a trade in the market's collateral.
+/// Once a registry entry has been set on the TokenConfig it must be supplied.
+pub fn resolve_collateral_price(
+    token_config: &TokenConfig,
+    collateral_asset: &Option<Account<CollateralAsset>>,
+    collateral_price_feed: &Option<AccountInfo>,
+) -> Result<CollateralPrice> {
+    if token_config.collateral_asset == Pubkey::default() {
+        return Ok(CollateralPrice::USDC);
+    }
+
+    match collateral_asset {
+        Some(collateral_asset) if collateral_asset.key() == token_config.collateral_asset => {
+            collateral_asset.load_price(collateral_price_feed.as_ref())
+        }
+        _ => Err(ContractError::CollateralAssetRequired.into()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn price(price: u64, decimals: u8, haircut_bps: u16) -> CollateralPrice {
+        CollateralPrice {
+            price,
+            decimals,
+            haircut_bps,
+        }
+    }
+
+    #[test]
+    fn usdc_converts_one_to_one() {
+        assert_eq!(CollateralPrice::USDC.usd_to_collateral_in(1_234_567).unwrap(), 1_234_567);
+        assert_eq!(CollateralPrice::USDC.usd_to_collateral_out(1_234_567).unwrap(), 1_234_567);
+    }
+
+    #[test]
+    fn conversions_scale_by_price_and_decimals() {
+        // $2,000 per token with 9 decimals: $1 is 0.0005 tokens
+        let eth = price(2_000_000_000, 9, 0);
+        assert_eq!(eth.usd_to_collateral_in(1_000_000).unwrap(), 500_000);
+        assert_eq!(eth.usd_to_collateral_out(1_000_000).unwrap(), 500_000);
+    }
+
+    #[test]
+    fn deposits_are_grossed_up_by_the_haircut() {
+        // A 20% haircut means $80 of value needs $100 of collateral
+        let discounted = price(1_000_000, 6, 2000);
+        assert_eq!(discounted.usd_to_collateral_in(80_000_000).unwrap(), 100_000_000);
+        // Payouts are not discounted
+        assert_eq!(discounted.usd_to_collateral_out(80_000_000).unwrap(), 80_000_000);
+    }
+
+    #[test]
+    fn deposits_round_up_and_payouts_round_down() {
+        let odd = price(3_000_000, 6, 0);
+        assert_eq!(odd.usd_to_collateral_in(1_000_000).unwrap(), 333_334);
+        assert_eq!(odd.usd_to_collateral_out(1_000_000).unwrap(), 333_333);
+    }
+
+    #[test]
+    fn zero_price_is_rejected() {
+        let zero = price(0, 6, 0);
+        assert!(zero.usd_to_collateral_in(1).is_err());
+        assert!(zero.usd_to_collateral_out(1).is_err());
+        // A 100% haircut would make any deposit worthless
+        assert!(price(1_000_000, 6, 10000).usd_to_collateral_in(1).is_err());
+    }
+
+    #[test]
+    fn pegged_assets_need_no_price_feed() {
+        let asset = CollateralAsset {
+            authority: Pubkey::default(),
+            mint: Pubkey::default(),
+            decimals: 8,
+            haircut_bps: 500,
+            price_feed: Pubkey::default(),
+            feed_id: [0; 32],
+            max_price_age: 60,
+            bump: 0,
+        };
+        let price = asset.load_price(None).unwrap();
+        assert_eq!(price.price, 1_000_000);
+        assert_eq!(price.decimals, 8);
+        assert_eq!(price.haircut_bps, 500);
+
+        // A feed-priced asset without its feed is rejected
+        let asset = CollateralAsset {
+            price_feed: Pubkey::new_unique(),
+            ..asset
+        };
+        assert!(asset.load_price(None).is_err());
+    }
+}
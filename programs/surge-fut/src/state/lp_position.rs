@@ -0,0 +1,9 @@
+use anchor_lang::prelude::*;
+
+#[account]
+pub struct LpPosition {
+    pub owner: Pubkey,               // Liquidity provider
+    pub lp_vault: Pubkey,            // Vault the liquidity was provided to
+    pub last_deposit_timestamp: i64, // Used to enforce the withdrawal cooldown
+    pub bump: u8,                    // PDA bump
+}
@@ -0,0 +1,211 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::TokenAccount;
+
+use crate::{state::TokenConfig, errors::ContractError};
+
+pub const LP_VAULT_SPACE: usize = 8 + 32 + 32 + 32 + 2 + 2 + 8 + 8 + 1 + 1 + 1 + 8 + 8;
+
+/// Virtual shares and assets added to both sides of the share price, so a first
+/// depositor cannot inflate the price of a share to round later deposits down
+pub const LP_VIRTUAL_SHARES: u64 = 1_000_000;
+pub const LP_VIRTUAL_ASSETS: u64 = 1_000_000;
+
+#[account]
+pub struct LpVault {
+    pub token_config: Pubkey,        // TokenConfig this vault is counterparty for
+    pub lp_mint: Pubkey,             // Mint for LP share tokens
+    pub usdc_vault: Pubkey,          // USDC liquidity provided by LPs
+    pub fee_share_bps: u16,          // Share of trading fees paid to the vault (bps of fee)
+    pub max_utilization_bps: u16,    // Max trader collateral relative to vault liquidity
+    pub withdrawal_cooldown: i64,    // Seconds an LP must wait after depositing before withdrawing
+    pub total_fees_earned: u64,      // Cumulative fees credited to the vault
+    pub usdc_vault_bump: u8,         // Bump for the USDC vault PDA
+    pub lp_mint_bump: u8,            // Bump for the LP mint PDA
+    pub bump: u8,                    // PDA bump
+    pub total_assets: u64,           // Liquidity owned by LPs: deposits, fees and realized PnL, less withdrawals
+    pub trader_collateral: u64,      // Trader collateral in the pool the vault is counterparty to
+}
+
+impl LpVault {
+    /// Portion of a trading fee credited to the vault
+    pub fn fee_share(&self, fee_amount: u64) -> Result<u64> {
+        fee_amount
+            .checked_mul(self.fee_share_bps as u64)
+            .ok_or(ContractError::MathOverflow)?
+            .checked_div(10000)
+            .ok_or(ContractError::MathOverflow.into())
+    }
+
+    /// Whether the vault can back `collateral` of trader collateral at `vault_balance`
+    pub fn within_utilization(&self, collateral: u64, vault_balance: u64) -> Result<bool> {
+        let max_collateral = (vault_balance as u128)
+            .checked_mul(self.max_utilization_bps as u128)
+            .ok_or(ContractError::MathOverflow)?
+            .checked_div(10000)
+            .ok_or(ContractError::MathOverflow)?;
+        Ok((collateral as u128) <= max_collateral)
+    }
+
+    /// Net asset value once outstanding tokens worth `trader_claims` are paid out
+    /// of the trader collateral, with the vault covering any shortfall.
+    /// Tokens sent to the vault without going through the program are not counted.
+    pub fn net_assets(&self, trader_claims: u64) -> u64 {
+        self.total_assets
+            .saturating_add(self.trader_collateral)
+            .saturating_sub(trader_claims)
+    }
+
+    /// LP shares minted for depositing `amount` into a vault worth `net_assets`
+    pub fn shares_for_deposit(amount: u64, share_supply: u64, net_assets: u64) -> Result<u64> {
+        let shares = (amount as u128)
+            .checked_mul(share_supply as u128 + LP_VIRTUAL_SHARES as u128)
+            .ok_or(ContractError::MathOverflow)?
+            .checked_div(net_assets as u128 + LP_VIRTUAL_ASSETS as u128)
+            .ok_or(ContractError::MathOverflow)?;
+        u64::try_from(shares).map_err(|_| ContractError::MathOverflow.into())
+    }
+
+    /// Liquidity owed for burning `shares` of a vault worth `net_assets`
+    pub fn assets_for_shares(shares: u64, share_supply: u64, net_assets: u64) -> Result<u64> {
+        let assets = (shares as u128)
+            .checked_mul(net_assets as u128 + LP_VIRTUAL_ASSETS as u128)
+            .ok_or(ContractError::MathOverflow)?
+            .checked_div(share_supply as u128 + LP_VIRTUAL_SHARES as u128)
+            .ok_or(ContractError::MathOverflow)?;
+        u64::try_from(assets).map_err(|_| ContractError::MathOverflow.into())
+    }
+
+    /// Records liquidity flowing into the vault
+    pub fn credit(&mut self, amount: u64) -> Result<()> {
+        self.total_assets = self.total_assets
+            .checked_add(amount)
+            .ok_or(ContractError::MathOverflow)?;
+        Ok(())
+    }
+
+    /// Records liquidity leaving the vault
+    pub fn debit(&mut self, amount: u64) -> Result<()> {
+        self.total_assets = self.total_assets
+            .checked_sub(amount)
+            .ok_or(ContractError::InsufficientLiquidity)?;
+        Ok(())
+    }
+
+    /// Records trader collateral added to the pool
+    pub fn lock_collateral(&mut self, amount: u64) -> Result<()> {
+        self.trader_collateral = self.trader_collateral
+            .checked_add(amount)
+            .ok_or(ContractError::MathOverflow)?;
+        Ok(())
+    }
+
+    /// Records trader collateral released from the pool
+    pub fn release_collateral(&mut self, amount: u64) {
+        self.trader_collateral = self.trader_collateral.saturating_sub(amount);
+    }
+}
+
+/// Resolves the optional LP vault accounts passed to a trade.
+/// Once a vault has been set on the TokenConfig it must be supplied.
+pub fn resolve_lp_vault<'a, 'info>(
+    token_config: &TokenConfig,
+    lp_vault: &'a Option<Account<'info, LpVault>>,
+    lp_usdc_vault: &'a Option<InterfaceAccount<'info, TokenAccount>>,
+) -> Result<Option<(&'a Account<'info, LpVault>, &'a InterfaceAccount<'info, TokenAccount>)>> {
+    if token_config.lp_vault == Pubkey::default() {
+        return Ok(None);
+    }
+
+    match (lp_vault, lp_usdc_vault) {
+        (Some(lp_vault), Some(lp_usdc_vault)) => {
+            require!(
+                lp_vault.key() == token_config.lp_vault,
+                ContractError::LpVaultRequired
+            );
+            require!(
+                lp_usdc_vault.key() == lp_vault.usdc_vault,
+                ContractError::LpVaultRequired
+            );
+            Ok(Some((lp_vault, lp_usdc_vault)))
+        }
+        _ => Err(ContractError::LpVaultRequired.into()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lp_vault(total_assets: u64, trader_collateral: u64) -> LpVault {
+        LpVault {
+            token_config: Pubkey::default(),
+            lp_mint: Pubkey::default(),
+            usdc_vault: Pubkey::default(),
+            fee_share_bps: 0,
+            max_utilization_bps: 10000,
+            withdrawal_cooldown: 0,
+            total_fees_earned: 0,
+            usdc_vault_bump: 0,
+            lp_mint_bump: 0,
+            bump: 0,
+            total_assets,
+            trader_collateral,
+        }
+    }
+
+    #[test]
+    fn first_deposit_mints_one_share_per_unit() {
+        assert_eq!(LpVault::shares_for_deposit(5_000_000, 0, 0).unwrap(), 5_000_000);
+        assert_eq!(LpVault::assets_for_shares(5_000_000, 5_000_000, 5_000_000).unwrap(), 5_000_000);
+    }
+
+    #[test]
+    fn donations_do_not_change_the_share_price() {
+        // An attacker deposits 1 unit, then sends 1,000 USDC straight to the vault
+        let mut vault = lp_vault(0, 0);
+        let attacker_shares = LpVault::shares_for_deposit(1, 0, vault.net_assets(0)).unwrap();
+        vault.credit(1).unwrap();
+        assert_eq!(attacker_shares, 1);
+
+        // The donation is not tracked, so the next depositor is priced as before
+        let shares = LpVault::shares_for_deposit(1_000_000, attacker_shares, vault.net_assets(0)).unwrap();
+        assert_eq!(shares, 1_000_000);
+    }
+
+    #[test]
+    fn virtual_shares_limit_rounding_losses() {
+        // Even with inflated net assets a small deposit still gets shares
+        let shares = LpVault::shares_for_deposit(1_000_000, 1, 1_000_000_000).unwrap();
+        assert!(shares > 0);
+
+        // Withdrawing never pays out more than was deposited
+        let out = LpVault::assets_for_shares(shares, 1 + shares, 1_001_000_000).unwrap();
+        assert!(out <= 1_000_000);
+    }
+
+    #[test]
+    fn net_assets_mark_trader_pnl() {
+        let vault = lp_vault(10_000_000, 2_000_000);
+        // Traders are up 500k: the vault owes it out of its own liquidity
+        assert_eq!(vault.net_assets(2_500_000), 9_500_000);
+        // Traders are down 500k: the vault keeps the difference
+        assert_eq!(vault.net_assets(1_500_000), 10_500_000);
+        // Claims beyond everything the vault holds floor at zero
+        assert_eq!(vault.net_assets(20_000_000), 0);
+    }
+
+    #[test]
+    fn debit_rejects_more_than_the_vault_owns() {
+        let mut vault = lp_vault(1_000, 0);
+        assert!(vault.debit(1_001).is_err());
+        vault.debit(1_000).unwrap();
+        assert_eq!(vault.total_assets, 0);
+    }
+
+    #[test]
+    fn release_collateral_saturates() {
+        let mut vault = lp_vault(0, 100);
+        vault.release_collateral(150);
+        assert_eq!(vault.trader_collateral, 0);
+    }
+}
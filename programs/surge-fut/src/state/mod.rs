@@ -1,7 +1,19 @@
+pub mod collateral_asset;
+pub mod lp_position;
+pub mod lp_vault;
 pub mod oracle;
+pub mod order;
+pub mod referrer;
 pub mod token_config;
 pub mod user_position;
+pub mod user_volume;
 
+pub use collateral_asset::*;
+pub use lp_position::*;
+pub use lp_vault::*;
 pub use oracle::*;
+pub use order::*;
+pub use referrer::*;
 pub use token_config::*;
-pub use user_position::*;
\ No newline at end of file
+pub use user_position::*;
+pub use user_volume::*;
@@ -1,4 +1,5 @@
 use anchor_lang::prelude::*;
+pub use surge_oracle::state::OracleHistory;
 
 #[derive(Clone, AnchorDeserialize, AnchorSerialize)]
 pub struct VolatilityStats {
@@ -8,10 +9,27 @@ pub struct VolatilityStats {
     pub m2: f64,
     pub count: u64,
     pub annualized_volatility: f64,
+    pub last_update_timestamp: i64,
+    pub guardian: Pubkey,
+    pub pause_flags: u8,
+    pub cumulative_volatility: f64,
+    pub observation_index: u8,
+    pub observations: [VolatilityObservation; OBSERVATION_COUNT],
 }
 
+#[derive(Clone, Copy, Default, AnchorDeserialize, AnchorSerialize)]
+pub struct VolatilityObservation {
+    pub timestamp: i64,
+    pub cumulative_volatility: f64,
+}
+
+pub const OBSERVATION_COUNT: usize = 24;
+
 impl VolatilityStats {
     pub const SIZE: usize = 8 + 32 + 8 + 8 + 8 + 8 + 8;
+    pub const SIZE_WITH_TIMESTAMP: usize = Self::SIZE + 8;
+    pub const SIZE_WITH_OBSERVATIONS: usize =
+        Self::SIZE_WITH_TIMESTAMP + 32 + 1 + 8 + 1 + 16 * OBSERVATION_COUNT;
 
     pub fn load_from_account_info(account_info: &AccountInfo) -> Result<f64> {
         let data = account_info.try_borrow_data()?;
@@ -44,4 +62,204 @@ impl VolatilityStats {
         
         Ok(annualized_volatility)
     }
-}
\ No newline at end of file
+
+    pub fn load_last_update_from_account_info(account_info: &AccountInfo) -> Result<i64> {
+        let data = account_info.try_borrow_data()?;
+        
+        if data.len() < Self::SIZE_WITH_TIMESTAMP {
+            return Err(crate::errors::ContractError::InvalidOracleData.into());
+        }
+        
+        // last_update_timestamp (i64 - 8 bytes) directly follows annualized_volatility
+        let offset = Self::SIZE;
+        let timestamp_bytes = &data[offset..offset + 8];
+        let last_update_timestamp = i64::from_le_bytes(timestamp_bytes.try_into().unwrap());
+        
+        Ok(last_update_timestamp)
+    }
+
+    /// Fails if the oracle was last updated more than `max_oracle_age` seconds ago
+    pub fn check_oracle_age(account_info: &AccountInfo, max_oracle_age: Option<i64>) -> Result<()> {
+        if let Some(max_age) = max_oracle_age {
+            let last_update = Self::load_last_update_from_account_info(account_info)?;
+            let age = Clock::get()?
+                .unix_timestamp
+                .checked_sub(last_update)
+                .ok_or(crate::errors::ContractError::MathOverflow)?;
+            
+            require!(age <= max_age, crate::errors::ContractError::OracleStale);
+        }
+        
+        Ok(())
+    }
+
+    /// Cumulative volatility history of the oracle, see `OracleHistory`
+    pub fn load_history_from_account_info(account_info: &AccountInfo) -> Result<OracleHistory> {
+        OracleHistory::load(&account_info.try_borrow_data()?)
+            .ok_or(crate::errors::ContractError::InvalidOracleData.into())
+    }
+
+    /// Time-weighted average volatility over the `window` seconds ending at `end`,
+    /// interpolated from the oracle's cumulative volatility observations.
+    /// A window reaching back past the oldest observation is shortened to start there.
+    pub fn load_twap_from_account_info(account_info: &AccountInfo, window: i64, end: i64) -> Result<f64> {
+        require!(window > 0, crate::errors::ContractError::InvalidTwapWindow);
+        
+        Self::load_history_from_account_info(account_info)?
+            .twap(window, end)
+            .ok_or(crate::errors::ContractError::InsufficientOracleHistory.into())
+    }
+
+    /// Volatility estimate in effect at `timestamp`, read from the observations
+    /// so a snapshot can be taken after the fact
+    pub fn load_volatility_at_from_account_info(account_info: &AccountInfo, timestamp: i64) -> Result<f64> {
+        Self::load_history_from_account_info(account_info)?
+            .volatility_at(timestamp)
+            .ok_or(crate::errors::ContractError::InsufficientOracleHistory.into())
+    }
+
+    /// Volatility a dated token settles on, see `OracleHistory::settlement_volatility`
+    pub fn load_settlement_volatility_from_account_info(
+        account_info: &AccountInfo,
+        twap_window: i64,
+        expiry: i64,
+    ) -> Result<f64> {
+        require!(twap_window >= 0, crate::errors::ContractError::InvalidTwapWindow);
+        
+        Self::load_history_from_account_info(account_info)?
+            .settlement_volatility(twap_window, expiry)
+            .ok_or(crate::errors::ContractError::InsufficientOracleHistory.into())
+    }
+
+    /// Volatility used to price trades: spot when `twap_window` is 0, otherwise the TWAP
+    pub fn load_mark_from_account_info(account_info: &AccountInfo, twap_window: i64) -> Result<f64> {
+        if twap_window == 0 {
+            return Self::load_from_account_info(account_info);
+        }
+        
+        Self::load_twap_from_account_info(account_info, twap_window, Clock::get()?.unix_timestamp)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Serializes an oracle account with the given observations, the last of which is the latest update.
+    /// Only the last `OBSERVATION_COUNT` are retained.
+    fn oracle_data(annualized_volatility: f64, observations: &[(i64, f64)]) -> Vec<u8> {
+        let mut stats = VolatilityStats {
+            authority: Pubkey::default(),
+            last_price: 0,
+            mean: 0.0,
+            m2: 0.0,
+            count: 0,
+            annualized_volatility,
+            last_update_timestamp: 0,
+            guardian: Pubkey::default(),
+            pause_flags: 0,
+            cumulative_volatility: 0.0,
+            observation_index: 0,
+            observations: [VolatilityObservation::default(); OBSERVATION_COUNT],
+        };
+        for (i, (timestamp, cumulative_volatility)) in observations.iter().enumerate() {
+            // The oracle writes its observations to a ring buffer
+            let slot = i % OBSERVATION_COUNT;
+            stats.observations[slot] = VolatilityObservation {
+                timestamp: *timestamp,
+                cumulative_volatility: *cumulative_volatility,
+            };
+            stats.observation_index = slot as u8;
+            stats.last_update_timestamp = *timestamp;
+            stats.cumulative_volatility = *cumulative_volatility;
+        }
+        
+        let mut data = vec![0u8; 8];
+        stats.serialize(&mut data).unwrap();
+        data
+    }
+
+    fn load_twap(data: &mut [u8], window: i64, end: i64) -> Result<f64> {
+        let key = Pubkey::default();
+        let mut lamports = 0;
+        let account_info = AccountInfo::new(&key, false, false, &mut lamports, data, &key, false, 0);
+        VolatilityStats::load_twap_from_account_info(&account_info, window, end)
+    }
+
+    #[test]
+    fn twap_weights_each_estimate_by_time() {
+        // 50 for the first 100s, then 100 for the next 100s, and 100 after that
+        let mut data = oracle_data(100.0, &[(1_000, 0.0), (1_100, 5_000.0), (1_200, 15_000.0)]);
+        assert_eq!(load_twap(&mut data, 200, 1_200).unwrap(), 75.0);
+        assert_eq!(load_twap(&mut data, 100, 1_150).unwrap(), 75.0);
+        // Past the last update the current estimate is extrapolated
+        assert_eq!(load_twap(&mut data, 100, 1_300).unwrap(), 100.0);
+    }
+
+    #[test]
+    fn twap_window_is_clamped_to_the_oldest_observation() {
+        let mut data = oracle_data(100.0, &[(1_000, 0.0), (1_100, 5_000.0), (1_200, 15_000.0)]);
+        // A window longer than the history averages over everything there is
+        assert_eq!(load_twap(&mut data, 1_000_000, 1_200).unwrap(), 75.0);
+    }
+
+    #[test]
+    fn twap_needs_history_before_the_end() {
+        let mut data = oracle_data(100.0, &[(1_000, 0.0), (1_100, 5_000.0)]);
+        assert!(load_twap(&mut data, 100, 900).is_err());
+        assert!(load_twap(&mut data, 100, 1_000).is_err());
+    }
+
+    #[test]
+    fn volatility_at_reads_the_estimate_in_effect() {
+        let mut data = oracle_data(120.0, &[(1_000, 0.0), (1_100, 5_000.0), (1_200, 15_000.0)]);
+        let key = Pubkey::default();
+        let mut lamports = 0;
+        let account_info = AccountInfo::new(&key, false, false, &mut lamports, &mut data, &key, false, 0);
+        
+        let volatility_at = |timestamp| VolatilityStats::load_volatility_at_from_account_info(&account_info, timestamp);
+        assert_eq!(volatility_at(1_050).unwrap(), 50.0);
+        assert_eq!(volatility_at(1_100).unwrap(), 100.0);
+        assert_eq!(volatility_at(1_200).unwrap(), 120.0);
+        assert_eq!(volatility_at(5_000).unwrap(), 120.0);
+        assert!(volatility_at(999).is_err());
+    }
+
+    #[test]
+    fn settlement_falls_back_to_the_oldest_observation_once_rotated() {
+        // Expiry at 1_000, followed by more updates than the buffer holds: each hour
+        // the estimate steps up by 1, so only hours 7.. are still retained
+        let observations: Vec<(i64, f64)> = (0..=OBSERVATION_COUNT as i64 + 6)
+            .map(|hour| (1_000 + hour * 3_600, (hour * (hour - 1) / 2 * 3_600) as f64))
+            .collect();
+        let mut data = oracle_data(30.0, &observations);
+        let key = Pubkey::default();
+        let mut lamports = 0;
+        let account_info = AccountInfo::new(&key, false, false, &mut lamports, &mut data, &key, false, 0);
+        let history = VolatilityStats::load_history_from_account_info(&account_info).unwrap();
+        assert_eq!(history.oldest_timestamp(), 1_000 + 7 * 3_600);
+        
+        // The readings at expiry are gone, settlement uses the oldest retained segment
+        assert!(history.volatility_at(1_000).is_none());
+        assert!(history.twap(3_600, 1_000).is_none());
+        assert_eq!(history.settlement_volatility(0, 1_000).unwrap(), 7.0);
+        assert_eq!(history.settlement_volatility(3_600, 1_000).unwrap(), 7.0);
+        assert_eq!(history.settlement_volatility(7_200, 1_000).unwrap(), 7.5);
+        
+        // Retained history is read as before
+        assert_eq!(history.settlement_volatility(0, 1_000 + 10 * 3_600).unwrap(), 10.0);
+        assert_eq!(
+            history.settlement_volatility(3_600, 1_000 + 10 * 3_600).unwrap(),
+            history.twap(3_600, 1_000 + 10 * 3_600).unwrap()
+        );
+    }
+
+    #[test]
+    fn twap_rejects_bad_input() {
+        let mut data = oracle_data(100.0, &[(1_000, 0.0)]);
+        assert!(load_twap(&mut data, 0, 1_100).is_err());
+        
+        let mut short = vec![0u8; VolatilityStats::SIZE_WITH_OBSERVATIONS - 1];
+        assert!(load_twap(&mut short, 100, 1_100).is_err());
+    }
+}
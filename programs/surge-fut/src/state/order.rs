@@ -0,0 +1,40 @@
+use anchor_lang::prelude::*;
+
+#[account]
+pub struct Order {
+    pub owner: Pubkey,               // User who placed the order
+    pub token_mint: Pubkey,          // Volatility token the order trades
+    pub order_id: u64,               // Owner-chosen id, part of the PDA seeds
+    pub side: OrderSide,             // Mint (buy) or redeem (sell)
+    pub trigger: TriggerCondition,   // Whether the order fires above or below the trigger
+    pub trigger_volatility: f64,     // Oracle mark volatility that triggers execution
+    pub amount: u64,                 // Tokens to mint or redeem
+    pub limit_usdc: u64,             // Max USDC in for buys, min USDC out for sells
+    pub keeper_fee: u64,             // USDC paid to the keeper that executes the order
+    pub escrow: Pubkey,              // Escrow holding USDC (buys) or tokens (sells)
+    pub created_at: i64,             // When the order was placed
+    pub escrow_bump: u8,             // Bump for the escrow PDA
+    pub bump: u8,                    // PDA bump
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub enum OrderSide {
+    Buy,
+    Sell,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub enum TriggerCondition {
+    Above,
+    Below,
+}
+
+impl Order {
+    /// Whether the order may execute at `volatility`
+    pub fn is_triggered(&self, volatility: f64) -> bool {
+        match self.trigger {
+            TriggerCondition::Above => volatility >= self.trigger_volatility,
+            TriggerCondition::Below => volatility <= self.trigger_volatility,
+        }
+    }
+}
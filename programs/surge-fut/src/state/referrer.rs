@@ -0,0 +1,11 @@
+use anchor_lang::prelude::*;
+
+pub const REFERRER_SPACE: usize = 8 + 32 + 32 + 32 + 1;
+
+#[account]
+pub struct Referrer {
+    pub token_mint: Pubkey,          // Volatility token the referrer is registered for
+    pub owner: Pubkey,               // Wallet of the referrer
+    pub usdc_account: Pubkey,        // USDC account receiving the rebates
+    pub bump: u8,                    // PDA bump
+}
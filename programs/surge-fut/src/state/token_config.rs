@@ -1,18 +1,134 @@
 use anchor_lang::prelude::*;
 
+use crate::errors::ContractError;
+
 #[account]
 pub struct TokenConfig {
     pub authority: Pubkey,           // Admin who can update fees
     pub token_mint: Pubkey,          // The mint for our volatility token
     pub usdc_mint: Pubkey,           // USDC mint address
-    pub fee_destination: Pubkey,     // Where fees go
+    pub fee_vault: Pubkey,           // Program-owned USDC account collecting protocol fees
     pub collateral_pool: Pubkey,     // Where collateral is stored
     pub token_name: String,          // Name of the token
     pub token_symbol: String,        // Symbol of the token
-    pub fee_bps: u16,                // Fee in basis points (1/100 of 1%)
+    pub mint_fee_bps: u16,           // Mint fee in basis points (1/100 of 1%)
     pub oracle: Pubkey,              // Volatility oracle address
     pub total_tokens_outstanding: u64, // Total number of tokens minted
     pub usdc_per_vol_point: u64,     // How much USDC per 1% point of volatility
     pub collateral_pool_bump: u8,    // Bump for the collateral pool PDA
     pub bump: u8,                    // PDA bump
+    pub lp_vault: Pubkey,            // LP vault acting as counterparty (default if none)
+    pub pending_authority: Pubkey,   // Proposed new authority awaiting acceptance
+    pub guardian: Pubkey,            // Key allowed to pause the market
+    pub pause_flags: u8,             // Bitmask of PAUSE_* flags
+    pub expiry_timestamp: i64,       // Expiry of a dated token (0 for perpetual)
+    pub settlement_volatility: f64,  // Oracle volatility snapshotted at settlement
+    pub is_settled: bool,            // Whether the dated token has been settled
+    pub twap_window: i64,            // Oracle TWAP window in seconds used for pricing (0 for spot)
+    pub max_open_interest: u64,      // Cap on total_tokens_outstanding (0 for unlimited)
+    pub max_position_size: u64,      // Cap on tokens held by a single position (0 for unlimited)
+    pub max_mint_per_slot: u64,      // Cap on tokens minted within one slot (0 for unlimited)
+    pub last_mint_slot: u64,         // Slot of the most recent mint
+    pub minted_in_slot: u64,         // Tokens minted so far in last_mint_slot
+    pub vol_cap: f64,                // Volatility cap of a fungible index token (0 for position mode)
+    pub redeem_fee_bps: u16,         // Redeem fee in basis points
+    pub fee_tiers: [FeeTier; MAX_FEE_TIERS], // Volume-based fee discounts, ascending by min_volume
+    pub referral_share_bps: u16,     // Share of the discounted fee rebated to a referrer
+    pub insurance_fund: Pubkey,      // USDC account receiving the insurance share (default if none)
+    pub insurance_share_bps: u16,    // Share of the fee, after rebates, sent to the insurance fund
+    pub fee_vault_bump: u8,          // Bump for the fee vault PDA
+    pub fee_withdrawal_policy: FeeWithdrawalPolicy, // Timelock and allowlist applied to withdraw_fees
+    pub pending_fee_withdrawal: PendingFeeWithdrawal, // Withdrawal queued behind the timelock
+    pub pending_fee_withdrawal_policy: FeeWithdrawalPolicy, // Policy change queued behind the timelock
+    pub pending_fee_withdrawal_policy_unlock: i64, // When the queued policy can be applied (0 if none)
+    pub collateral_asset: Pubkey,    // Collateral registry entry pricing usdc_mint (default for 6-decimal USDC)
+    pub pending_fee_split: PendingFeeSplit, // Fee split change queued behind the withdrawal timelock
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default, PartialEq, Eq)]
+pub struct FeeWithdrawalPolicy {
+    pub timelock: i64,               // Seconds a withdrawal waits after being queued (0 for none)
+    pub allowlist: [Pubkey; MAX_FEE_DESTINATIONS], // Allowed destinations (all default for any)
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default)]
+pub struct PendingFeeWithdrawal {
+    pub destination: Pubkey,         // USDC account that will receive the fees
+    pub amount: u64,                 // Amount queued for withdrawal (0 if none)
+    pub unlock_timestamp: i64,       // When the withdrawal can be executed
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default, PartialEq, Eq)]
+pub struct PendingFeeSplit {
+    pub referral_share_bps: u16,     // Queued referral share
+    pub insurance_fund: Pubkey,      // Queued insurance fund (default to disable it)
+    pub insurance_share_bps: u16,    // Queued insurance share
+    pub unlock_timestamp: i64,       // When the change can be applied (0 if none)
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default)]
+pub struct FeeTier {
+    pub min_volume: u64,             // Cumulative USDC volume needed to reach the tier
+    pub discount_bps: u16,           // Discount applied to the base fee (bps of fee)
+}
+
+pub const TOKEN_CONFIG_SPACE: usize = 8 + 32 + 32 + 32 + 32 + 32 + 32 + 2 + 32 + 8 + 8 + 1 + 1 + 32 + 32 + 32 + 1 + 8 + 8 + 1 + 8 + 8 + 8 + 8 + 8 + 8 + 8 + 2 + (8 + 2) * MAX_FEE_TIERS + 2 + 32 + 2 + 1 + (8 + 32 * MAX_FEE_DESTINATIONS) + (32 + 8 + 8) + (8 + 32 * MAX_FEE_DESTINATIONS) + 8 + 32 + (2 + 32 + 2 + 8) + 200; // Extra space for name/symbol
+
+pub const PAUSE_MINTS: u8 = 1 << 0;
+pub const PAUSE_REDEEMS: u8 = 1 << 1;
+pub const PAUSE_UPDATES: u8 = 1 << 2;
+pub const PAUSE_ALL: u8 = PAUSE_MINTS | PAUSE_REDEEMS | PAUSE_UPDATES;
+pub const REDEMPTIONS_ONLY: u8 = PAUSE_MINTS | PAUSE_UPDATES;
+
+pub const MAX_TWAP_WINDOW: i64 = 7 * 24 * 60 * 60;
+
+pub const MAX_FEE_TIERS: usize = 4;
+pub const MAX_FEE_DESTINATIONS: usize = 4;
+pub const MAX_FEE_WITHDRAWAL_TIMELOCK: i64 = 30 * 24 * 60 * 60;
+pub const MAX_REFERRAL_SHARE_BPS: u16 = 5000;
+pub const MAX_INSURANCE_SHARE_BPS: u16 = 2000;
+
+impl TokenConfig {
+    pub fn is_paused(&self, flag: u8) -> bool {
+        self.pause_flags & flag != 0
+    }
+
+    pub fn is_expired(&self, now: i64) -> bool {
+        self.expiry_timestamp != 0 && now >= self.expiry_timestamp
+    }
+
+    /// Index tokens are fungible: their value depends only on the oracle
+    pub fn is_index_mode(&self) -> bool {
+        self.vol_cap > 0.0
+    }
+
+    /// USDC value of `amount` index tokens at `volatility`, capped at vol_cap
+    pub fn index_value(&self, amount: u64, volatility: f64) -> Result<u64> {
+        let vol_points = (volatility.min(self.vol_cap) * 1000.0) as u64;
+        
+        amount
+            .checked_mul(vol_points)
+            .ok_or(ContractError::MathOverflow)?
+            .checked_mul(self.usdc_per_vol_point)
+            .ok_or(ContractError::MathOverflow)?
+            .checked_div(1000) // Adjust for the volatility scaling
+            .ok_or(ContractError::MathOverflow.into())
+    }
+
+    /// Discount earned by a user with `volume` of cumulative USDC volume
+    pub fn fee_discount_bps(&self, volume: u64) -> u16 {
+        self.fee_tiers
+            .iter()
+            .filter(|tier| tier.discount_bps > 0 && volume >= tier.min_volume)
+            .map(|tier| tier.discount_bps)
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// Whether `destination` may receive fee withdrawals
+    pub fn is_allowed_fee_destination(&self, destination: &Pubkey) -> bool {
+        let allowlist = &self.fee_withdrawal_policy.allowlist;
+        allowlist.iter().all(|entry| *entry == Pubkey::default())
+            || allowlist.contains(destination)
+    }
 }
\ No newline at end of file
@@ -1,5 +1,9 @@
 use anchor_lang::prelude::*;
 
+use crate::errors::ContractError;
+
+pub const USER_POSITION_SPACE: usize = 8 + 32 + 8 + 8 + 8 + 8 + 1 + 32;
+
 #[account]
 pub struct UserPosition {
     pub owner: Pubkey,               // User who owns this position
@@ -8,4 +12,46 @@ pub struct UserPosition {
     pub usdc_collateral: u64,        // USDC deposited as collateral
     pub mint_timestamp: i64,         // When position was created
     pub bump: u8,                    // PDA bump
-}
\ No newline at end of file
+    pub delegate: Pubkey,            // Key allowed to redeem on the owner's behalf
+}
+
+impl UserPosition {
+    /// Adds tokens entered at `volatility` and the collateral backing them,
+    /// blending the entry volatility by token count
+    pub fn add_tokens(&mut self, amount: u64, collateral: u64, volatility: f64) -> Result<()> {
+        let tokens = self.tokens_minted
+            .checked_add(amount)
+            .ok_or(ContractError::MathOverflow)?;
+        if tokens > 0 {
+            self.entry_volatility = (self.entry_volatility * self.tokens_minted as f64
+                + volatility * amount as f64)
+                / tokens as f64;
+        }
+        self.tokens_minted = tokens;
+        self.usdc_collateral = self.usdc_collateral
+            .checked_add(collateral)
+            .ok_or(ContractError::MathOverflow)?;
+        Ok(())
+    }
+
+    /// Portion of the position's collateral backing `amount` of its tokens
+    pub fn collateral_for(&self, amount: u64) -> Result<u64> {
+        Ok(self
+            .usdc_collateral
+            .checked_mul(amount)
+            .ok_or(ContractError::MathOverflow)?
+            .checked_div(self.tokens_minted)
+            .unwrap_or(self.usdc_collateral))
+    }
+
+    /// Removes redeemed tokens and the collateral that backed them
+    pub fn remove_tokens(&mut self, amount: u64, collateral: u64) -> Result<()> {
+        self.tokens_minted = self.tokens_minted
+            .checked_sub(amount)
+            .ok_or(ContractError::MathOverflow)?;
+        self.usdc_collateral = self.usdc_collateral
+            .checked_sub(collateral)
+            .ok_or(ContractError::MathOverflow)?;
+        Ok(())
+    }
+}
@@ -0,0 +1,24 @@
+use anchor_lang::prelude::*;
+
+pub const USER_VOLUME_SPACE: usize = 8 + 32 + 32 + 8 + 1 + 32;
+
+#[account]
+pub struct UserVolume {
+    pub owner: Pubkey,               // Trader whose volume is tracked
+    pub token_mint: Pubkey,          // Volatility token the volume was traded in
+    pub volume: u64,                 // Cumulative USDC notional of mints and redeems
+    pub bump: u8,                    // PDA bump
+    pub referrer: Pubkey,            // Referrer bound at the trader's first referred trade
+}
+
+impl UserVolume {
+    /// Adds traded notional, filling in the account on the trader's first trade
+    pub fn record(&mut self, owner: Pubkey, token_mint: Pubkey, bump: u8, notional: u64) {
+        if self.owner == Pubkey::default() {
+            self.owner = owner;
+            self.token_mint = token_mint;
+            self.bump = bump;
+        }
+        self.volume = self.volume.saturating_add(notional);
+    }
+}
@@ -0,0 +1,577 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{self, Mint, MintTo, TokenAccount, TransferChecked};
+
+use crate::{state::*, errors::ContractError, events::PositionClosed, fees::{split_fee, FeeSplit}};
+
+/// Market totals after a mint, once it has passed the risk limits
+pub struct MintLimits {
+    pub open_interest: u64,
+    pub position_size: u64,
+    pub minted_in_slot: u64,
+    pub slot: u64,
+}
+
+/// Enforces open interest, position size and per-slot mint limits for minting
+/// `amount` tokens on top of a holding of `holding` tokens
+pub fn check_mint_limits(token_config: &TokenConfig, holding: u64, amount: u64) -> Result<MintLimits> {
+    let open_interest = token_config
+        .total_tokens_outstanding
+        .checked_add(amount)
+        .ok_or(ContractError::MathOverflow)?;
+    require!(
+        token_config.max_open_interest == 0 || open_interest <= token_config.max_open_interest,
+        ContractError::OpenInterestCapExceeded
+    );
+
+    let position_size = holding
+        .checked_add(amount)
+        .ok_or(ContractError::MathOverflow)?;
+    require!(
+        token_config.max_position_size == 0 || position_size <= token_config.max_position_size,
+        ContractError::PositionLimitExceeded
+    );
+
+    let slot = Clock::get()?.slot;
+    let minted_in_slot = if token_config.last_mint_slot == slot {
+        token_config.minted_in_slot
+    } else {
+        0
+    }
+    .checked_add(amount)
+    .ok_or(ContractError::MathOverflow)?;
+    require!(
+        token_config.max_mint_per_slot == 0 || minted_in_slot <= token_config.max_mint_per_slot,
+        ContractError::MintRateLimitExceeded
+    );
+
+    Ok(MintLimits {
+        open_interest,
+        position_size,
+        minted_in_slot,
+        slot,
+    })
+}
+
+/// Records a mint that passed `check_mint_limits`
+pub fn record_mint(token_config: &mut TokenConfig, limits: &MintLimits) {
+    token_config.total_tokens_outstanding = limits.open_interest;
+    token_config.last_mint_slot = limits.slot;
+    token_config.minted_in_slot = limits.minted_in_slot;
+}
+
+/// Price of a mint, shared by direct mints and filled buy orders
+pub struct MintQuote {
+    pub usd_value: u64,              // Notional of the minted tokens
+    pub collateral: u64,             // Collateral added to the pool
+    pub fee: FeeSplit,               // Fee charged on top of the collateral
+    pub total_payment: u64,          // Collateral plus fee
+}
+
+/// Prices minting `amount` tokens at `volatility`, checks the LP vault can back
+/// the collateral and splits the fee, discounted by the trader's `volume`
+#[allow(clippy::too_many_arguments)]
+pub fn quote_mint<'info>(
+    token_config: &TokenConfig,
+    collateral_price: &CollateralPrice,
+    lp_vault: &Option<Account<'info, LpVault>>,
+    lp_usdc_vault: &Option<InterfaceAccount<'info, TokenAccount>>,
+    collateral_pool: &TokenAccount,
+    amount: u64,
+    volatility: f64,
+    volume: u64,
+    has_referrer: bool,
+) -> Result<MintQuote> {
+    let usd_value = mint_value(token_config, amount, volatility)?;
+    let collateral = collateral_price.usd_to_collateral_in(usd_value)?;
+    let lp_vault = check_lp_utilization(token_config, lp_vault, lp_usdc_vault, collateral_pool, collateral)?;
+    let fee = split_fee(
+        token_config,
+        collateral,
+        token_config.mint_fee_bps,
+        volume,
+        lp_vault,
+        has_referrer,
+    )?;
+    let total_payment = collateral
+        .checked_add(fee.total)
+        .ok_or(ContractError::MathOverflow)?;
+
+    Ok(MintQuote {
+        usd_value,
+        collateral,
+        fee,
+        total_payment,
+    })
+}
+
+/// Books a paid mint: the LP vault's fee share and collateral, open interest,
+/// the trader's volume and their position
+#[allow(clippy::too_many_arguments)]
+pub fn record_mint_fill(
+    token_config: &mut TokenConfig,
+    lp_vault: &mut Option<Account<LpVault>>,
+    user_position: &mut UserPosition,
+    user_volume: &mut UserVolume,
+    owner: Pubkey,
+    volume_bump: u8,
+    limits: &MintLimits,
+    quote: &MintQuote,
+    amount: u64,
+    volatility: f64,
+) -> Result<()> {
+    credit_lp_fees(lp_vault, &quote.fee)?;
+    lock_lp_collateral(lp_vault, quote.collateral)?;
+    record_mint(token_config, limits);
+    user_volume.record(owner, token_config.token_mint, volume_bump, quote.usd_value);
+
+    // Average the entry volatility over all held tokens
+    user_position.add_tokens(amount, quote.collateral, volatility)?;
+    user_position.mint_timestamp = Clock::get()?.unix_timestamp;
+    Ok(())
+}
+
+/// Price of a redemption, shared by direct redeems and filled sell orders
+pub struct RedeemQuote {
+    pub usd_value: u64,              // Value of the redeemed tokens
+    pub collateral: u64,             // Collateral paid out of the pool, fee included
+    pub fee: FeeSplit,               // Fee taken out of the collateral
+}
+
+/// Prices redeeming `amount` tokens entered at `entry_volatility` and splits
+/// the fee, discounted by the trader's `volume`
+#[allow(clippy::too_many_arguments)]
+pub fn quote_redeem<'info>(
+    token_config: &TokenConfig,
+    collateral_price: &CollateralPrice,
+    lp_vault: &Option<Account<'info, LpVault>>,
+    lp_usdc_vault: &Option<InterfaceAccount<'info, TokenAccount>>,
+    amount: u64,
+    entry_volatility: f64,
+    volatility: f64,
+    volume: u64,
+    has_referrer: bool,
+) -> Result<RedeemQuote> {
+    let usd_value = redeem_value(token_config, amount, entry_volatility, volatility)?;
+    let collateral = collateral_price.usd_to_collateral_out(usd_value)?;
+    let fee = split_fee(
+        token_config,
+        collateral,
+        token_config.redeem_fee_bps,
+        volume,
+        resolve_lp_vault(token_config, lp_vault, lp_usdc_vault)?.map(|(lp_vault, _)| &**lp_vault),
+        has_referrer,
+    )?;
+
+    Ok(RedeemQuote {
+        usd_value,
+        collateral,
+        fee,
+    })
+}
+
+/// Settles a redemption's PnL against the LP vault, if one backs the market,
+/// and checks the pool then holds the full redemption value
+#[allow(clippy::too_many_arguments)]
+pub fn fund_redemption<'info>(
+    usdc_token_program: AccountInfo<'info>,
+    usdc_mint: &InterfaceAccount<'info, Mint>,
+    token_config: &Account<'info, TokenConfig>,
+    collateral_pool: &mut InterfaceAccount<'info, TokenAccount>,
+    lp_vault: &mut Option<Account<'info, LpVault>>,
+    lp_usdc_vault: &Option<InterfaceAccount<'info, TokenAccount>>,
+    redemption_value: u64,
+    collateral_reduction: u64,
+) -> Result<()> {
+    settle_lp_pnl(
+        usdc_token_program,
+        usdc_mint,
+        token_config,
+        collateral_pool,
+        lp_vault,
+        lp_usdc_vault,
+        redemption_value,
+        collateral_reduction,
+    )?;
+    collateral_pool.reload()?;
+
+    require!(
+        collateral_pool.amount >= redemption_value,
+        ContractError::InsufficientBalance
+    );
+    Ok(())
+}
+
+/// Books a paid redemption: the LP vault's fee share, open interest, the
+/// trader's volume and their position
+#[allow(clippy::too_many_arguments)]
+pub fn record_redeem_fill(
+    token_config: &mut TokenConfig,
+    lp_vault: &mut Option<Account<LpVault>>,
+    user_position: &mut UserPosition,
+    user_volume: &mut UserVolume,
+    owner: Pubkey,
+    volume_bump: u8,
+    quote: &RedeemQuote,
+    amount: u64,
+    collateral_reduction: u64,
+) -> Result<()> {
+    credit_lp_fees(lp_vault, &quote.fee)?;
+    token_config.total_tokens_outstanding = token_config
+        .total_tokens_outstanding
+        .checked_sub(amount)
+        .ok_or(ContractError::MathOverflow)?;
+    user_volume.record(owner, token_config.token_mint, volume_bump, quote.usd_value);
+
+    // Scale the position's collateral down proportionally
+    user_position.remove_tokens(amount, collateral_reduction)
+}
+
+/// Mints `amount` volatility tokens to `to`, signed by the token config
+pub fn mint_volatility_tokens<'info>(
+    token_program: AccountInfo<'info>,
+    token_mint: &InterfaceAccount<'info, Mint>,
+    to: AccountInfo<'info>,
+    token_config: &Account<'info, TokenConfig>,
+    amount: u64,
+) -> Result<()> {
+    let token_mint_key = token_mint.key();
+    let token_config_seeds = &[
+        b"token_config",
+        token_mint_key.as_ref(),
+        &[token_config.bump]
+    ];
+
+    token_interface::mint_to(
+        CpiContext::new_with_signer(
+            token_program,
+            MintTo {
+                mint: token_mint.to_account_info(),
+                to,
+                authority: token_config.to_account_info(),
+            },
+            &[&token_config_seeds[..]],
+        ),
+        amount,
+    )
+}
+
+/// Transfers `amount` USDC from `from` to `to`, skipping empty transfers
+pub fn transfer_usdc<'info>(
+    usdc_token_program: AccountInfo<'info>,
+    usdc_mint: &InterfaceAccount<'info, Mint>,
+    from: AccountInfo<'info>,
+    to: AccountInfo<'info>,
+    authority: AccountInfo<'info>,
+    signer_seeds: &[&[&[u8]]],
+    amount: u64,
+) -> Result<()> {
+    if amount == 0 {
+        return Ok(());
+    }
+
+    token_interface::transfer_checked(
+        CpiContext::new_with_signer(
+            usdc_token_program,
+            TransferChecked {
+                from,
+                mint: usdc_mint.to_account_info(),
+                to,
+                authority,
+            },
+            signer_seeds,
+        ),
+        amount,
+        usdc_mint.decimals,
+    )
+}
+
+/// Volatility in points, scaled by 1000 to preserve precision
+pub fn vol_points(volatility: f64) -> u64 {
+    (volatility * 1000.0) as u64
+}
+
+/// USD value of `amount` tokens at `volatility`: amount * volatility * usdc_per_vol_point
+pub fn mint_value(token_config: &TokenConfig, amount: u64, volatility: f64) -> Result<u64> {
+    Ok(amount
+        .checked_mul(vol_points(volatility))
+        .ok_or(ContractError::MathOverflow)?
+        .checked_mul(token_config.usdc_per_vol_point)
+        .ok_or(ContractError::MathOverflow)?
+        .checked_div(1000) // Adjust for the volatility scaling
+        .ok_or(ContractError::MathOverflow)?)
+}
+
+/// Checks the LP vault, if the market has one, can back `collateral_in` more
+/// collateral in the pool. Returns the vault so it can take its share of the fee.
+pub fn check_lp_utilization<'a, 'info>(
+    token_config: &TokenConfig,
+    lp_vault: &'a Option<Account<'info, LpVault>>,
+    lp_usdc_vault: &'a Option<InterfaceAccount<'info, TokenAccount>>,
+    collateral_pool: &TokenAccount,
+    collateral_in: u64,
+) -> Result<Option<&'a LpVault>> {
+    match resolve_lp_vault(token_config, lp_vault, lp_usdc_vault)? {
+        Some((lp_vault, lp_usdc_vault)) => {
+            let collateral_after = collateral_pool
+                .amount
+                .checked_add(collateral_in)
+                .ok_or(ContractError::MathOverflow)?;
+            require!(
+                lp_vault.within_utilization(collateral_after, lp_usdc_vault.amount)?,
+                ContractError::UtilizationLimitExceeded
+            );
+            Ok(Some(&**lp_vault))
+        }
+        None => Ok(None),
+    }
+}
+
+/// Credits the LP vault's share of a fee once it has been paid out
+pub fn credit_lp_fees(lp_vault: &mut Option<Account<LpVault>>, fee: &FeeSplit) -> Result<()> {
+    if fee.lp > 0 {
+        if let Some(lp_vault) = lp_vault.as_mut() {
+            lp_vault.total_fees_earned = lp_vault
+                .total_fees_earned
+                .checked_add(fee.lp)
+                .ok_or(ContractError::MathOverflow)?;
+            lp_vault.credit(fee.lp)?;
+        }
+    }
+    Ok(())
+}
+
+/// Records collateral a trader added to the pool against the LP vault, if the market has one
+pub fn lock_lp_collateral(lp_vault: &mut Option<Account<LpVault>>, collateral: u64) -> Result<()> {
+    if let Some(lp_vault) = lp_vault.as_mut() {
+        lp_vault.lock_collateral(collateral)?;
+    }
+    Ok(())
+}
+
+/// Settles a trader's PnL against the LP vault, if one backs the market.
+/// A profit is paid from the vault into the pool, a loss is moved from the pool
+/// into the vault, so the pool ends up holding exactly `redemption_value` for the trade.
+#[allow(clippy::too_many_arguments)]
+pub fn settle_lp_pnl<'info>(
+    usdc_token_program: AccountInfo<'info>,
+    usdc_mint: &InterfaceAccount<'info, Mint>,
+    token_config: &Account<'info, TokenConfig>,
+    collateral_pool: &InterfaceAccount<'info, TokenAccount>,
+    lp_vault: &mut Option<Account<'info, LpVault>>,
+    lp_usdc_vault: &Option<InterfaceAccount<'info, TokenAccount>>,
+    redemption_value: u64,
+    collateral_reduction: u64,
+) -> Result<()> {
+    let Some((vault, vault_usdc)) = resolve_lp_vault(token_config, lp_vault, lp_usdc_vault)? else {
+        return Ok(());
+    };
+
+    let token_mint_key = token_config.token_mint;
+    if redemption_value > collateral_reduction {
+        // Volatility increased, the vault pays the profit into the pool
+        let profit = redemption_value
+            .checked_sub(collateral_reduction)
+            .ok_or(ContractError::MathOverflow)?;
+        require!(
+            vault_usdc.amount >= profit,
+            ContractError::InsufficientLiquidity
+        );
+
+        let lp_vault_seeds = &[
+            b"lp_vault",
+            token_mint_key.as_ref(),
+            &[vault.bump]
+        ];
+
+        token_interface::transfer_checked(
+            CpiContext::new_with_signer(
+                usdc_token_program,
+                TransferChecked {
+                    from: vault_usdc.to_account_info(),
+                    mint: usdc_mint.to_account_info(),
+                    to: collateral_pool.to_account_info(),
+                    authority: vault.to_account_info(),
+                },
+                &[&lp_vault_seeds[..]],
+            ),
+            profit,
+            usdc_mint.decimals,
+        )?;
+    } else if collateral_reduction > redemption_value {
+        // Volatility decreased, the vault keeps the trader's loss
+        let loss = collateral_reduction
+            .checked_sub(redemption_value)
+            .ok_or(ContractError::MathOverflow)?;
+
+        let token_config_seeds = &[
+            b"token_config",
+            token_mint_key.as_ref(),
+            &[token_config.bump]
+        ];
+
+        token_interface::transfer_checked(
+            CpiContext::new_with_signer(
+                usdc_token_program,
+                TransferChecked {
+                    from: collateral_pool.to_account_info(),
+                    mint: usdc_mint.to_account_info(),
+                    to: vault_usdc.to_account_info(),
+                    authority: token_config.to_account_info(),
+                },
+                &[&token_config_seeds[..]],
+            ),
+            loss,
+            usdc_mint.decimals,
+        )?;
+    }
+
+    // The redeemed tokens no longer have collateral in the pool, only the PnL stays with the vault
+    if let Some(vault) = lp_vault.as_mut() {
+        vault.release_collateral(collateral_reduction);
+        if redemption_value > collateral_reduction {
+            vault.debit(redemption_value - collateral_reduction)?;
+        } else {
+            vault.credit(collateral_reduction - redemption_value)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// What every outstanding token would redeem for at `volatility`, in collateral units
+pub fn trader_claims(
+    token_config: &TokenConfig,
+    collateral_price: &CollateralPrice,
+    volatility: f64,
+) -> Result<u64> {
+    let outstanding = token_config.total_tokens_outstanding;
+    let usd_value = if token_config.is_index_mode() {
+        token_config.index_value(outstanding, volatility)?
+    } else {
+        mint_value(token_config, outstanding, volatility)?
+    };
+    collateral_price.usd_to_collateral_out(usd_value)
+}
+
+/// Net asset value of the LP vault, marking open trader positions at the
+/// settlement volatility once settled and at the oracle mark otherwise
+pub fn lp_net_assets(
+    token_config: &TokenConfig,
+    lp_vault: &LpVault,
+    oracle: &AccountInfo,
+    collateral_price: &CollateralPrice,
+) -> Result<u64> {
+    let volatility = if token_config.is_settled {
+        token_config.settlement_volatility
+    } else {
+        VolatilityStats::load_mark_from_account_info(oracle, token_config.twap_window)?
+    };
+    Ok(lp_vault.net_assets(trader_claims(token_config, collateral_price, volatility)?))
+}
+
+/// Closes a position once its last token has been redeemed, sold or transferred,
+/// returning the rent to `owner`. Buy orders reserve rent to reopen it when they fill.
+pub fn close_if_empty<'info>(
+    user_position: &mut Account<'info, UserPosition>,
+    owner: AccountInfo<'info>,
+    token_mint: Pubkey,
+) -> Result<()> {
+    if user_position.tokens_minted != 0 {
+        return Ok(());
+    }
+
+    let rent_returned = user_position.to_account_info().lamports();
+    let owner_key = owner.key();
+    user_position.close(owner)?;
+
+    emit!(PositionClosed {
+        token_mint,
+        owner: owner_key,
+        rent_returned,
+        token_account_closed: false,
+    });
+
+    Ok(())
+}
+
+/// Redemption value in USD of `amount` tokens entered at `entry_volatility`
+pub fn redeem_value(
+    token_config: &TokenConfig,
+    amount: u64,
+    entry_volatility: f64,
+    current_volatility: f64,
+) -> Result<u64> {
+    calculate_redemption_value(
+        amount,
+        vol_points(entry_volatility),
+        vol_points(current_volatility),
+        token_config.usdc_per_vol_point,
+    )
+}
+
+// Helper function to calculate redemption value
+pub(crate) fn calculate_redemption_value(
+    amount: u64,
+    entry_vol_points: u64,
+    current_vol_points: u64,
+    usdc_per_vol: u64,
+) -> Result<u64> {
+    // Base redemption is tokens * entry_volatility * usdc_per_vol_point
+    let base_value = amount
+        .checked_mul(entry_vol_points)
+        .ok_or(ContractError::MathOverflow)?
+        .checked_mul(usdc_per_vol)
+        .ok_or(ContractError::MathOverflow)?
+        .checked_div(1000) // Adjust for the volatility scaling
+        .ok_or(ContractError::MathOverflow)?;
+
+    // Calculate profit/loss based on volatility change
+    if current_vol_points > entry_vol_points {
+        // Volatility increased, user profits
+        let profit_per_vol_point = usdc_per_vol
+            .checked_mul(amount)
+            .ok_or(ContractError::MathOverflow)?;
+
+        let vol_diff = current_vol_points
+            .checked_sub(entry_vol_points)
+            .ok_or(ContractError::MathOverflow)?;
+
+        let profit = profit_per_vol_point
+            .checked_mul(vol_diff)
+            .ok_or(ContractError::MathOverflow)?
+            .checked_div(1000) // Adjust for the volatility scaling
+            .ok_or(ContractError::MathOverflow)?;
+
+        base_value
+            .checked_add(profit)
+            .ok_or(ContractError::MathOverflow.into())
+    } else if current_vol_points < entry_vol_points {
+        // Volatility decreased, user takes a loss
+        let loss_per_vol_point = usdc_per_vol
+            .checked_mul(amount)
+            .ok_or(ContractError::MathOverflow)?;
+
+        let vol_diff = entry_vol_points
+            .checked_sub(current_vol_points)
+            .ok_or(ContractError::MathOverflow)?;
+
+        let loss = loss_per_vol_point
+            .checked_mul(vol_diff)
+            .ok_or(ContractError::MathOverflow)?
+            .checked_div(1000) // Adjust for the volatility scaling
+            .ok_or(ContractError::MathOverflow)?;
+
+        // Ensure loss doesn't exceed base value
+        if loss >= base_value {
+            // Return minimum value (1) to avoid complete loss
+            Ok(1)
+        } else {
+            base_value
+                .checked_sub(loss)
+                .ok_or(ContractError::MathOverflow.into())
+        }
+    } else {
+        // No change in volatility
+        Ok(base_value)
+    }
+}
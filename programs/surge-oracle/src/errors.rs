@@ -13,4 +13,13 @@ pub enum OracleError {
 
     #[msg("Invalid authority")]
     InvalidAuthority,
+
+    #[msg("Volatility updates are paused")]
+    UpdatesPaused,
+
+    #[msg("Invalid pause flags")]
+    InvalidPauseFlags,
+
+    #[msg("Account already has the current layout")]
+    AlreadyMigrated,
 }
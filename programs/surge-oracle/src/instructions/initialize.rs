@@ -29,6 +29,21 @@ impl Initialize<'_> {
             Some(0.0), // annualized_volatility
         );
         stats.authority = ctx.accounts.authority.key();
+        stats.guardian = ctx.accounts.authority.key();
+        stats.pause_flags = 0;
+
+        // Seed the TWAP accumulator with an initial observation
+        let now = Clock::get()?.unix_timestamp;
+        stats.cumulative_volatility = 0.0;
+        stats.observation_index = 0;
+        stats.observations = [VolatilityObservation::default(); OBSERVATION_COUNT];
+        stats.observations[0] = VolatilityObservation {
+            timestamp: now,
+            cumulative_volatility: 0.0,
+        };
+        stats.last_update_timestamp = now;
+        stats.return_index = 0;
+        stats.returns = [ReturnObservation::default(); RETURN_COUNT];
 
         msg!("Volatility stats account initialized with Welford's method");
         Ok(())
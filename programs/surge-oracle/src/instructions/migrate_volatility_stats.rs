@@ -0,0 +1,104 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{self, Transfer};
+use anchor_lang::Discriminator;
+
+use crate::{
+    errors::OracleError,
+    state::{VolatilityObservation, VolatilityStats},
+};
+
+// Offsets of the fields added since the first deployment, a smaller account predates them
+const LAST_UPDATE_TIMESTAMP_OFFSET: usize = 8 + 32 + 8 + 8 + 8 + 8 + 8;
+const GUARDIAN_OFFSET: usize = LAST_UPDATE_TIMESTAMP_OFFSET + 8;
+const OBSERVATIONS_OFFSET: usize = GUARDIAN_OFFSET + 32 + 1 + 8 + 1;
+
+#[derive(Accounts)]
+pub struct MigrateVolatilityStats<'info> {
+    /// CHECK: Owner, discriminator and authority are checked before the account is
+    /// resized, it cannot be deserialized until then
+    #[account(
+        mut,
+        seeds = [b"volatility_stats"],
+        bump,
+        owner = crate::ID,
+    )]
+    pub volatility_stats: UncheckedAccount<'info>,
+
+    /// Pays the rent for the added space
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[event]
+pub struct VolatilityStatsMigrated {
+    pub authority: Pubkey,
+    pub old_size: u64,
+    pub new_size: u64,
+}
+
+impl MigrateVolatilityStats<'_> {
+    /// Grows a stats account created by an earlier version of the program to the
+    /// current layout. Added fields start out as `initialize_volatility_stats` sets them.
+    pub fn migrate_volatility_stats(ctx: Context<MigrateVolatilityStats>) -> Result<()> {
+        let info = ctx.accounts.volatility_stats.to_account_info();
+        let old_size = info.data_len();
+        {
+            let data = info.try_borrow_data()?;
+            require!(
+                data.len() >= 8 + 32 && data[..8] == *VolatilityStats::DISCRIMINATOR,
+                ErrorCode::AccountDiscriminatorMismatch
+            );
+            require!(
+                data[8..8 + 32] == ctx.accounts.authority.key().to_bytes(),
+                OracleError::InvalidAuthority
+            );
+        }
+        require!(old_size < VolatilityStats::SIZE, OracleError::AlreadyMigrated);
+
+        let rent = Rent::get()?.minimum_balance(VolatilityStats::SIZE);
+        let top_up = rent.saturating_sub(info.lamports());
+        if top_up > 0 {
+            system_program::transfer(
+                CpiContext::new(
+                    ctx.accounts.system_program.to_account_info(),
+                    Transfer {
+                        from: ctx.accounts.authority.to_account_info(),
+                        to: info.clone(),
+                    },
+                ),
+                top_up,
+            )?;
+        }
+        info.realloc(VolatilityStats::SIZE, true)?;
+
+        let mut data = info.try_borrow_mut_data()?;
+        let mut stats = VolatilityStats::try_deserialize(&mut &data[..])?;
+        if old_size <= LAST_UPDATE_TIMESTAMP_OFFSET {
+            stats.last_update_timestamp = Clock::get()?.unix_timestamp;
+        }
+        if old_size <= GUARDIAN_OFFSET {
+            stats.guardian = stats.authority;
+        }
+        if old_size <= OBSERVATIONS_OFFSET {
+            // Seed the TWAP accumulator like a freshly initialized account
+            stats.observation_index = 0;
+            stats.observations[0] = VolatilityObservation {
+                timestamp: stats.last_update_timestamp,
+                cumulative_volatility: stats.cumulative_volatility,
+            };
+        }
+        stats.try_serialize(&mut &mut data[..])?;
+
+        msg!("Volatility stats migrated from {} to {} bytes", old_size, VolatilityStats::SIZE);
+
+        emit!(VolatilityStatsMigrated {
+            authority: ctx.accounts.authority.key(),
+            old_size: old_size as u64,
+            new_size: VolatilityStats::SIZE as u64,
+        });
+
+        Ok(())
+    }
+}
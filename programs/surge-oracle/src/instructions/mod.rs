@@ -1,5 +1,11 @@
 pub mod initialize;
+pub mod migrate_volatility_stats;
+pub mod set_guardian;
+pub mod set_pause_flags;
 pub mod update_volatility;
 
 pub use initialize::*;
+pub use migrate_volatility_stats::*;
+pub use set_guardian::*;
+pub use set_pause_flags::*;
 pub use update_volatility::*;
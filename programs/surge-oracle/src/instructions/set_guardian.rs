@@ -0,0 +1,39 @@
+use anchor_lang::prelude::*;
+
+use crate::state::VolatilityStats;
+
+#[derive(Accounts)]
+pub struct SetGuardian<'info> {
+    #[account(
+        mut,
+        seeds = [b"volatility_stats"],
+        bump,
+        has_one = authority,
+    )]
+    pub volatility_stats: Account<'info, VolatilityStats>,
+
+    pub authority: Signer<'info>,
+}
+
+#[event]
+pub struct GuardianUpdated {
+    pub old_guardian: Pubkey,
+    pub new_guardian: Pubkey,
+}
+
+impl SetGuardian<'_> {
+    pub fn set_guardian(ctx: Context<SetGuardian>, new_guardian: Pubkey) -> Result<()> {
+        let stats = &mut ctx.accounts.volatility_stats;
+        let old_guardian = stats.guardian;
+        stats.guardian = new_guardian;
+
+        msg!("Oracle guardian updated to: {}", new_guardian);
+
+        emit!(GuardianUpdated {
+            old_guardian,
+            new_guardian,
+        });
+
+        Ok(())
+    }
+}
@@ -0,0 +1,48 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    errors::OracleError,
+    state::{VolatilityStats, PAUSE_ALL},
+};
+
+#[derive(Accounts)]
+pub struct SetPauseFlags<'info> {
+    #[account(
+        mut,
+        seeds = [b"volatility_stats"],
+        bump,
+        constraint = signer.key() == volatility_stats.guardian
+            || signer.key() == volatility_stats.authority @ OracleError::InvalidAuthority,
+    )]
+    pub volatility_stats: Account<'info, VolatilityStats>,
+
+    /// Guardian or authority
+    pub signer: Signer<'info>,
+}
+
+#[event]
+pub struct PauseFlagsUpdated {
+    pub signer: Pubkey,
+    pub old_pause_flags: u8,
+    pub new_pause_flags: u8,
+}
+
+impl SetPauseFlags<'_> {
+    pub fn set_pause_flags(ctx: Context<SetPauseFlags>, pause_flags: u8) -> Result<()> {
+        require!(pause_flags & !PAUSE_ALL == 0, OracleError::InvalidPauseFlags);
+
+        let stats = &mut ctx.accounts.volatility_stats;
+        let old_pause_flags = stats.pause_flags;
+        stats.pause_flags = pause_flags;
+
+        msg!("Oracle pause flags updated to: {:#05b}", pause_flags);
+
+        emit!(PauseFlagsUpdated {
+            signer: ctx.accounts.signer.key(),
+            old_pause_flags,
+            new_pause_flags: pause_flags,
+        });
+
+        Ok(())
+    }
+}
@@ -1,7 +1,7 @@
 use anchor_lang::prelude::*;
 use pyth_solana_receiver_sdk::price_update::{get_feed_id_from_hex, PriceUpdateV2};
 
-use crate::{errors::OracleError, state::VolatilityStats};
+use crate::{errors::OracleError, state::{VolatilityStats, PAUSE_UPDATES}};
 
 #[derive(Accounts)]
 pub struct UpdateVolatility<'info> {
@@ -10,6 +10,7 @@ pub struct UpdateVolatility<'info> {
         seeds = [b"volatility_stats"],
         bump,     
         has_one = authority,
+        constraint = !volatility_stats.is_paused(PAUSE_UPDATES) @ OracleError::UpdatesPaused,
     )]
     pub volatility_stats: Account<'info, VolatilityStats>,
 
@@ -25,6 +26,8 @@ pub struct VolatilityUpdated {
     pub m2: f64,
     pub count: u64,
     pub annualized_volatility: f64,
+    pub timestamp: i64,
+    pub cumulative_volatility: f64,
 }
 
 impl UpdateVolatility<'_> {
@@ -32,6 +35,7 @@ impl UpdateVolatility<'_> {
         let stats = &mut ctx.accounts.volatility_stats;
         let price_update = &ctx.accounts.price_update;
         let max_age = 3600;
+        let now = Clock::get()?.unix_timestamp;
 
         let feed_id: [u8; 32] =
             get_feed_id_from_hex("ef0d8b6fda2ceba41da15d4095d1da392a0d2f8ed0c6c7bc0f4cfac8c280b56d")?;
@@ -64,6 +68,10 @@ impl UpdateVolatility<'_> {
             new_mean += delta / (new_count as f64);
             new_m2 += delta * (log_return - new_mean);
 
+            // Returns are numbered from 1, the first price only seeds last_price
+            let start_price = stats.last_price;
+            stats.record_return(new_count - 1, now, start_price, log_return);
+
             if new_count > 1 {
                 let variance = new_m2 / ((new_count - 1) as f64);
                 let daily_vol = variance.sqrt();
@@ -77,6 +85,9 @@ impl UpdateVolatility<'_> {
             new_count = 1;
         }
 
+        // Accrue the previous estimate for TWAP consumers before replacing it
+        stats.record_observation(now);
+
         stats.update_volatility(
             Some(current_price),
             Some(new_mean),
@@ -91,6 +102,8 @@ impl UpdateVolatility<'_> {
             m2: new_m2,
             count: new_count,
             annualized_volatility: new_annualized_volatility,
+            timestamp: now,
+            cumulative_volatility: stats.cumulative_volatility,
         });
 
         Ok(())
@@ -20,4 +20,16 @@ pub mod surge_oracle {
     pub fn update_volatility(ctx: Context<UpdateVolatility>) -> Result<()> {
         UpdateVolatility::update_volatility(ctx)
     }
+
+    pub fn set_guardian(ctx: Context<SetGuardian>, new_guardian: Pubkey) -> Result<()> {
+        SetGuardian::set_guardian(ctx, new_guardian)
+    }
+
+    pub fn set_pause_flags(ctx: Context<SetPauseFlags>, pause_flags: u8) -> Result<()> {
+        SetPauseFlags::set_pause_flags(ctx, pause_flags)
+    }
+
+    pub fn migrate_volatility_stats(ctx: Context<MigrateVolatilityStats>) -> Result<()> {
+        MigrateVolatilityStats::migrate_volatility_stats(ctx)
+    }
 }
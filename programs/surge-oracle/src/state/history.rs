@@ -0,0 +1,134 @@
+use crate::state::{VolatilityStats, OBSERVATION_COUNT};
+
+/// Cumulative volatility history of a VolatilityStats account, for programs
+/// settling on the volatility at a past time. Reads return `None` when the
+/// history does not cover the request, callers map that to their own error.
+pub struct OracleHistory {
+    pub annualized_volatility: f64,
+    pub last_update_timestamp: i64,
+    pub cumulative_volatility: f64,
+    pub observations: Vec<(i64, f64)>,
+}
+
+impl OracleHistory {
+    /// Reads the history from raw account data. Accounts migrated up to the
+    /// observations but not the returns still load.
+    pub fn load(data: &[u8]) -> Option<Self> {
+        if data.len() < VolatilityStats::OBSERVATIONS_OFFSET + 16 * OBSERVATION_COUNT {
+            return None;
+        }
+
+        let read_i64 = |offset: usize| i64::from_le_bytes(data[offset..offset + 8].try_into().unwrap());
+        let read_f64 = |offset: usize| f64::from_le_bytes(data[offset..offset + 8].try_into().unwrap());
+
+        let observations = (0..OBSERVATION_COUNT)
+            .map(|i| {
+                let offset = VolatilityStats::OBSERVATIONS_OFFSET + i * 16;
+                (read_i64(offset), read_f64(offset + 8))
+            })
+            // Unused slots are zeroed
+            .filter(|(timestamp, _)| *timestamp != 0)
+            .collect();
+
+        Some(Self {
+            annualized_volatility: read_f64(VolatilityStats::ANNUALIZED_VOLATILITY_OFFSET),
+            last_update_timestamp: read_i64(VolatilityStats::LAST_UPDATE_TIMESTAMP_OFFSET),
+            cumulative_volatility: read_f64(VolatilityStats::CUMULATIVE_VOLATILITY_OFFSET),
+            observations,
+        })
+    }
+
+    /// Earliest time the cumulative volatility is known for
+    pub fn oldest_timestamp(&self) -> i64 {
+        self.observations
+            .iter()
+            .map(|(timestamp, _)| *timestamp)
+            .min()
+            .unwrap_or(self.last_update_timestamp)
+    }
+
+    /// Observations either side of `timestamp`, which must be before the last update
+    fn segment(&self, timestamp: i64) -> Option<((i64, f64), (i64, f64))> {
+        let before = self
+            .observations
+            .iter()
+            .filter(|(t, _)| *t <= timestamp)
+            .max_by_key(|(t, _)| *t)
+            .copied()?;
+        let after = self
+            .observations
+            .iter()
+            .filter(|(t, _)| *t > timestamp)
+            .min_by_key(|(t, _)| *t)
+            .copied()
+            .unwrap_or((self.last_update_timestamp, self.cumulative_volatility));
+
+        Some((before, after))
+    }
+
+    /// Cumulative volatility at any time covered by the observations
+    pub fn cumulative_at(&self, timestamp: i64) -> Option<f64> {
+        if timestamp >= self.last_update_timestamp {
+            // Extrapolate with the current estimate
+            let elapsed = timestamp - self.last_update_timestamp;
+            return Some(self.cumulative_volatility + self.annualized_volatility * (elapsed as f64));
+        }
+
+        let ((before_timestamp, before_cumulative), (after_timestamp, after_cumulative)) =
+            self.segment(timestamp)?;
+
+        // The estimate is constant between observations, so interpolate linearly
+        if after_timestamp <= before_timestamp {
+            return Some(before_cumulative);
+        }
+        Some(before_cumulative
+            + (after_cumulative - before_cumulative) * ((timestamp - before_timestamp) as f64)
+                / ((after_timestamp - before_timestamp) as f64))
+    }
+
+    /// Volatility estimate in effect at `timestamp`: the slope of the cumulative
+    /// volatility over the observations either side of it
+    pub fn volatility_at(&self, timestamp: i64) -> Option<f64> {
+        if timestamp >= self.last_update_timestamp {
+            return Some(self.annualized_volatility);
+        }
+
+        let ((before_timestamp, before_cumulative), (after_timestamp, after_cumulative)) =
+            self.segment(timestamp)?;
+        if after_timestamp <= before_timestamp {
+            return None;
+        }
+
+        Some((after_cumulative - before_cumulative) / ((after_timestamp - before_timestamp) as f64))
+    }
+
+    /// Time-weighted average volatility over the `window` seconds ending at `end`,
+    /// starting no earlier than the oldest observation
+    pub fn twap(&self, window: i64, end: i64) -> Option<f64> {
+        let start = end.checked_sub(window)?.max(self.oldest_timestamp());
+        if start >= end {
+            return None;
+        }
+
+        Some((self.cumulative_at(end)? - self.cumulative_at(start)?) / ((end - start) as f64))
+    }
+
+    /// Volatility at `expiry`: the estimate in effect when `twap_window` is 0,
+    /// otherwise the TWAP ending there. Once later updates have rotated the
+    /// observations around expiry out of the buffer, the oldest retained ones
+    /// stand in, so settlement never depends on being called in time.
+    pub fn settlement_volatility(&self, twap_window: i64, expiry: i64) -> Option<f64> {
+        let oldest_timestamp = self.oldest_timestamp();
+        if twap_window == 0 {
+            return self.volatility_at(expiry.max(oldest_timestamp));
+        }
+
+        // A window ending before the oldest observation is moved to start there
+        let end = if expiry > oldest_timestamp {
+            expiry
+        } else {
+            oldest_timestamp.checked_add(twap_window)?
+        };
+        self.twap(twap_window, end)
+    }
+}
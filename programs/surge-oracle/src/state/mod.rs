@@ -1,3 +1,5 @@
+pub mod history;
 pub mod volatility;
 
+pub use history::*;
 pub use volatility::*;
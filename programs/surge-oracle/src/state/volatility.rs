@@ -8,8 +8,43 @@ pub struct VolatilityStats {
     pub m2: f64,                    // Running Σ(r - mean)^2
     pub count: u64,                 // Number of returns seen
     pub annualized_volatility: f64, // Annualized σ estimate
+    pub last_update_timestamp: i64, // Unix timestamp of the last update
+    pub guardian: Pubkey,           // Key allowed to pause updates
+    pub pause_flags: u8,            // Bitmask of PAUSE_* flags
+    pub cumulative_volatility: f64, // Running Σ(σ × seconds) for TWAP queries
+    pub observation_index: u8,      // Slot of the most recent observation
+    pub observations: [VolatilityObservation; OBSERVATION_COUNT],
+    pub return_index: u8,           // Slot of the most recent return
+    pub returns: [ReturnObservation; RETURN_COUNT],
 }
 
+/// Snapshot of the cumulative volatility, consumers interpolate between
+/// two observations to compute a time-weighted average over any window
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default)]
+pub struct VolatilityObservation {
+    pub timestamp: i64,
+    pub cumulative_volatility: f64,
+}
+
+pub const OBSERVATION_COUNT: usize = 24;
+
+/// A single log-return with the price it started from, so consumers can
+/// accrue variance conditioned on where the underlying traded
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default)]
+pub struct ReturnObservation {
+    pub sequence: u64,              // 1-based index of the return, 0 for an empty slot
+    pub timestamp: i64,
+    pub start_price: u64,           // Fixed-point price (1e6) at the start of the return
+    pub log_return: f64,
+}
+
+pub const RETURN_COUNT: usize = 32;
+
+// The oracle only has volatility updates to pause, markets pause their own
+// mints and redemptions. Bit 2 is kept so existing flags read the same.
+pub const PAUSE_UPDATES: u8 = 1 << 2;
+pub const PAUSE_ALL: u8 = PAUSE_UPDATES;
+
 impl VolatilityStats {
     pub const SIZE: usize = 8 +  // discriminator
         32 +  // authority
@@ -17,7 +52,53 @@ impl VolatilityStats {
         8 +   // mean
         8 +   // m2
         8 +   // count
-        8; // annualized_volatility
+        8 +   // annualized_volatility
+        8 +   // last_update_timestamp
+        32 +  // guardian
+        1 +   // pause_flags
+        8 +   // cumulative_volatility
+        1 +   // observation_index
+        16 * OBSERVATION_COUNT + // observations
+        1 +   // return_index
+        32 * RETURN_COUNT; // returns
+
+    // Offsets of the fields other programs read straight from the account data
+    pub const LAST_PRICE_OFFSET: usize = 8 + 32; // discriminator, authority
+    pub const COUNT_OFFSET: usize = Self::LAST_PRICE_OFFSET + 8 + 8 + 8; // last_price, mean, m2
+    pub const ANNUALIZED_VOLATILITY_OFFSET: usize = Self::COUNT_OFFSET + 8; // count
+    pub const LAST_UPDATE_TIMESTAMP_OFFSET: usize = Self::ANNUALIZED_VOLATILITY_OFFSET + 8; // annualized_volatility
+    pub const CUMULATIVE_VOLATILITY_OFFSET: usize = Self::LAST_UPDATE_TIMESTAMP_OFFSET + 8 + 32 + 1; // last_update_timestamp, guardian, pause_flags
+    pub const OBSERVATIONS_OFFSET: usize = Self::CUMULATIVE_VOLATILITY_OFFSET + 8 + 1; // cumulative_volatility, observation_index
+    pub const RETURNS_OFFSET: usize = Self::OBSERVATIONS_OFFSET + 16 * OBSERVATION_COUNT + 1; // observations, return_index
+
+    pub fn is_paused(&self, flag: u8) -> bool {
+        self.pause_flags & flag != 0
+    }
+
+    /// Accrues the current volatility up to `timestamp` and records an observation.
+    /// Must be called before the volatility estimate changes.
+    pub fn record_observation(&mut self, timestamp: i64) {
+        let elapsed = timestamp.saturating_sub(self.last_update_timestamp).max(0);
+        self.cumulative_volatility += self.annualized_volatility * (elapsed as f64);
+        self.last_update_timestamp = timestamp;
+
+        self.observation_index = ((self.observation_index as usize + 1) % OBSERVATION_COUNT) as u8;
+        self.observations[self.observation_index as usize] = VolatilityObservation {
+            timestamp,
+            cumulative_volatility: self.cumulative_volatility,
+        };
+    }
+
+    /// Records a log-return in the ring buffer
+    pub fn record_return(&mut self, sequence: u64, timestamp: i64, start_price: u64, log_return: f64) {
+        self.return_index = ((self.return_index as usize + 1) % RETURN_COUNT) as u8;
+        self.returns[self.return_index as usize] = ReturnObservation {
+            sequence,
+            timestamp,
+            start_price,
+            log_return,
+        };
+    }
 
     pub fn update_volatility(
         &mut self,
@@ -7,4 +7,121 @@ pub enum ErrorCode {
 
     #[msg("Numeric overflow occurred")]
     NumberOverflow,
+
+    #[msg("Only the market authority can perform this action")]
+    Unauthorized,
+
+    #[msg("Minting is paused")]
+    MintsPaused,
+
+    #[msg("Redemptions are paused")]
+    RedeemsPaused,
+
+    #[msg("Invalid pause flags")]
+    InvalidPauseFlags,
+
+    #[msg("Invalid collateral asset")]
+    InvalidCollateralAsset,
+
+    #[msg("Collateral price feed is missing or invalid")]
+    InvalidCollateralPrice,
+
+    #[msg("Collateral price is stale")]
+    StalePrice,
+
+    #[msg("Invalid amount")]
+    InvalidAmount,
+
+    #[msg("Invalid pool fee")]
+    InvalidPoolFee,
+
+    #[msg("Pool does not have enough liquidity")]
+    InsufficientLiquidity,
+
+    #[msg("Slippage tolerance exceeded")]
+    SlippageExceeded,
+
+    #[msg("Pools are only supported for 6-decimal USDC collateral")]
+    UnsupportedCollateral,
+
+    #[msg("Invalid auction parameters")]
+    InvalidAuction,
+
+    #[msg("The market is still in its strike auction")]
+    AuctionInProgress,
+
+    #[msg("The auction is no longer accepting bids")]
+    AuctionClosed,
+
+    #[msg("The auction has not ended yet")]
+    AuctionNotEnded,
+
+    #[msg("The auction has not been cleared")]
+    AuctionNotCleared,
+
+    #[msg("Invalid series parameters")]
+    InvalidSeries,
+
+    #[msg("The current epoch of the series has not ended")]
+    EpochNotEnded,
+
+    #[msg("The market has not been settled")]
+    MarketNotSettled,
+
+    #[msg("Series markets are settled by roll_series")]
+    SeriesMarket,
+
+    #[msg("Invalid variance type")]
+    InvalidVarianceType,
+
+    #[msg("Oracle account does not match the market")]
+    InvalidOracle,
+
+    #[msg("Invalid option terms")]
+    InvalidOptionTerms,
+
+    #[msg("Not available on option markets")]
+    OptionMarket,
+
+    #[msg("Premium exceeds the buyer's limit")]
+    PremiumExceeded,
+
+    #[msg("Oracle history does not cover the requested window")]
+    InsufficientOracleHistory,
+
+    #[msg("Invalid settlement terms")]
+    InvalidSettlementTerms,
+
+    #[msg("Market settles through propose_settlement")]
+    DisputableSettlement,
+
+    #[msg("The dispute window has closed")]
+    DisputeWindowClosed,
+
+    #[msg("The dispute window is still open")]
+    DisputeWindowOpen,
+
+    #[msg("Invalid market caps")]
+    InvalidMarketCaps,
+
+    #[msg("Mint would exceed the market's max total deposits")]
+    MarketCapExceeded,
+
+    #[msg("Mint would exceed the max supply of this side")]
+    SideCapExceeded,
+
+    #[msg("Mint would exceed the per-user deposit cap")]
+    UserCapExceeded,
+
+    #[msg("Mint would exceed the max long/short imbalance")]
+    ImbalanceLimitExceeded,
+
+    #[msg("Market updates are paused")]
+    UpdatesPaused,
+
+    #[msg("Account already has the current layout")]
+    AlreadyMigrated,
+
+    #[msg("Market account does not match its seeds")]
+    InvalidMarket,
 }
@@ -1,10 +1,13 @@
 use anchor_lang::prelude::*;
+use crate::state::{OptionType, StrikeRule, VarianceType};
 
 #[event]
 pub struct MarketInitialized {
     pub market: Pubkey,
     pub authority: Pubkey,
-    pub usdc_vault: Pubkey,
+    pub collateral_vault: Pubkey,
+    pub collateral_mint: Pubkey,
+    pub collateral_asset: Pubkey,
     pub var_long_mint: Pubkey,
     pub var_short_mint: Pubkey,
     pub epoch: u64,
@@ -17,18 +20,307 @@ pub struct MarketInitialized {
 pub struct TokensMinted {
     pub market: Pubkey,
     pub user: Pubkey,
+    pub collateral_amount: u64,
     pub amount: u64,
     pub is_long: bool,
     pub total_deposits: u64,
 }
 
 #[event]
-pub struct MarketRedeemed {
+pub struct GuardianUpdated {
     pub market: Pubkey,
+    pub authority: Pubkey,
+    pub old_guardian: Pubkey,
+    pub new_guardian: Pubkey,
+}
+
+#[event]
+pub struct PauseFlagsUpdated {
+    pub market: Pubkey,
+    pub signer: Pubkey,
+    pub old_pause_flags: u8,
+    pub new_pause_flags: u8,
+}
+
+#[event]
+pub struct CollateralAssetUpdated {
+    pub collateral_asset: Pubkey,
+    pub authority: Pubkey,
+    pub mint: Pubkey,
+    pub haircut_bps: u16,
+    pub price_feed: Pubkey,
+    pub max_price_age: i64,
+}
+
+#[event]
+pub struct PoolInitialized {
+    pub market: Pubkey,
+    pub pool: Pubkey,
+    pub lp_mint: Pubkey,
+    pub fee_bps: u16,
+    pub seed_amount: u64,
+}
+
+#[event]
+pub struct LiquidityAdded {
+    pub market: Pubkey,
+    pub user: Pubkey,
+    pub amount: u64,
+    pub lp_amount: u64,
+    pub long_added: u64,
+    pub short_added: u64,
+}
+
+#[event]
+pub struct LiquidityRemoved {
+    pub market: Pubkey,
+    pub user: Pubkey,
+    pub lp_amount: u64,
+    pub long_out: u64,
+    pub short_out: u64,
+}
+
+#[event]
+pub struct Swapped {
+    pub market: Pubkey,
+    pub user: Pubkey,
+    pub is_long: bool,
+    pub is_buy: bool,
+    pub amount_in: u64,
+    pub amount_out: u64,
+    pub fee: u64,
+    pub long_reserve: u64,
+    pub short_reserve: u64,
+    pub long_price: f64,
+}
+
+#[event]
+pub struct AuctionStarted {
+    pub market: Pubkey,
+    pub auction: Pubkey,
+    pub min_strike: f64,
+    pub max_strike: f64,
+    pub bucket_count: u8,
+    pub end_time: i64,
+}
+
+#[event]
+pub struct AuctionBidSubmitted {
+    pub market: Pubkey,
+    pub auction: Pubkey,
     pub user: Pubkey,
+    pub is_long: bool,
+    pub bucket: u8,
+    pub strike: f64,
+    pub amount: u64,
+}
+
+#[event]
+pub struct AuctionCleared {
+    pub market: Pubkey,
+    pub auction: Pubkey,
+    pub strike: f64,
+    pub clearing_bucket: u8,
+    pub long_notional: u64,
+    pub short_notional: u64,
+    pub matched: u64,
+    pub start_volatility: f64,
+}
+
+#[event]
+pub struct AuctionBidClaimed {
+    pub market: Pubkey,
+    pub auction: Pubkey,
+    pub user: Pubkey,
+    pub is_long: bool,
+    pub bucket: u8,
+    pub filled: u64,
+    pub refunded: u64,
+}
+
+#[event]
+pub struct SeriesInitialized {
+    pub series: Pubkey,
+    pub authority: Pubkey,
+    pub series_id: u64,
+    pub volatility_stats: Pubkey,
+    pub collateral_mint: Pubkey,
+    pub tenor: i64,
+    pub strike_rule: StrikeRule,
+    pub base_strike: f64,
+    pub strike_spread: f64,
+    pub variance_cap: f64,
+    pub start_timestamp: i64,
+    pub dispute_window: i64,
+    pub dispute_twap_window: i64,
+}
+
+#[event]
+pub struct MarketSettled {
+    pub market: Pubkey,
     pub realized_variance: f64,
     pub strike: f64,
     pub long_payout: u64,
     pub short_payout: u64,
     pub total_deposits: u64,
+    pub dispute_deadline: i64,
+}
+
+#[event]
+pub struct SeriesRolled {
+    pub series: Pubkey,
+    pub settled_market: Pubkey,
+    pub realized_variance: f64,
+    pub market: Pubkey,
+    pub epoch: u64,
+    pub timestamp: i64,
+    pub expiry_timestamp: i64,
+    pub strike: f64,
+    pub start_volatility: f64,
+}
+
+#[event]
+pub struct SettlementClaimed {
+    pub market: Pubkey,
+    pub user: Pubkey,
+    pub long_amount: u64,
+    pub short_amount: u64,
+    pub payout: u64,
+}
+
+#[event]
+pub struct PositionRolled {
+    pub series: Pubkey,
+    pub market: Pubkey,
+    pub next_market: Pubkey,
+    pub user: Pubkey,
+    pub long_amount: u64,
+    pub short_amount: u64,
+    pub amount: u64,
+    pub is_long: bool,
+    pub total_deposits: u64,
+}
+
+#[event]
+pub struct VarianceTypeSet {
+    pub market: Pubkey,
+    pub variance_type: VarianceType,
+    pub reference_price: u64,
+    pub return_sequence: u64,
+}
+
+#[event]
+pub struct VarianceAccrued {
+    pub market: Pubkey,
+    pub last_return_sequence: u64,
+    pub observed_returns: u64,
+    pub skipped_returns: u64,
+    pub conditional_variance: f64,
+}
+
+#[event]
+pub struct OptionTermsSet {
+    pub market: Pubkey,
+    pub option_type: OptionType,
+    pub strike: f64,
+    pub payout_per_point: u64,
+    pub max_payoff: u64,
+    pub expiry_timestamp: i64,
+}
+
+#[event]
+pub struct OptionsWritten {
+    pub market: Pubkey,
+    pub writer: Pubkey,
+    pub offer: Pubkey,
+    pub amount: u64,
+    pub collateral: u64,
+    pub premium_per_option: u64,
+}
+
+#[event]
+pub struct OptionsBought {
+    pub market: Pubkey,
+    pub offer: Pubkey,
+    pub writer: Pubkey,
+    pub buyer: Pubkey,
+    pub amount: u64,
+    pub premium: u64,
+}
+
+#[event]
+pub struct OptionOfferCancelled {
+    pub market: Pubkey,
+    pub offer: Pubkey,
+    pub writer: Pubkey,
+    pub returned: u64,
+}
+
+#[event]
+pub struct BinaryTermsSet {
+    pub market: Pubkey,
+    pub strike: f64,
+    pub expiry_timestamp: i64,
+}
+
+#[event]
+pub struct BinaryPairMinted {
+    pub market: Pubkey,
+    pub user: Pubkey,
+    pub amount: u64,
+    pub total_deposits: u64,
+}
+
+#[event]
+pub struct BinaryPairRedeemed {
+    pub market: Pubkey,
+    pub user: Pubkey,
+    pub amount: u64,
+    pub total_deposits: u64,
+}
+
+#[event]
+pub struct SettlementTermsSet {
+    pub market: Pubkey,
+    pub dispute_window: i64,
+    pub dispute_twap_window: i64,
+    pub expiry_timestamp: i64,
+}
+
+#[event]
+pub struct SettlementDisputed {
+    pub market: Pubkey,
+    pub guardian: Pubkey,
+    pub proposed_realized_variance: f64,
+    pub realized_variance: f64,
+    pub long_payout: u64,
+    pub short_payout: u64,
+    pub twap_window: i64,
+}
+
+#[event]
+pub struct SettlementFinalized {
+    pub market: Pubkey,
+    pub realized_variance: f64,
+    pub long_payout: u64,
+    pub short_payout: u64,
+}
+
+#[event]
+pub struct MarketCapsUpdated {
+    pub market: Pubkey,
+    pub authority: Pubkey,
+    pub max_total_deposits: u64,
+    pub max_side_supply: u64,
+    pub max_user_deposits: u64,
+    pub max_imbalance_bps: u32,
+    pub imbalance_floor: u64,
+}
+
+#[event]
+pub struct MarketMigrated {
+    pub market: Pubkey,
+    pub authority: Pubkey,
+    pub old_size: u64,
+    pub new_size: u64,
 }
@@ -0,0 +1,44 @@
+use crate::errors::ErrorCode;
+use crate::state::*;
+use crate::events::*;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct AccrueVariance<'info> {
+    #[account(
+        mut,
+        has_one = volatility_stats @ ErrorCode::InvalidOracle,
+        constraint = !market.is_paused(PAUSE_UPDATES) @ ErrorCode::UpdatesPaused,
+    )]
+    pub market: Account<'info, Market>,
+
+    /// The volatility stats account from the oracle program
+    /// CHECK: This account is not owned by this program, but we read from it
+    pub volatility_stats: AccountInfo<'info>,
+}
+
+impl<'info> AccrueVariance<'info> {
+    /// Permissionless crank folding new oracle returns into a conditional market.
+    /// Must run at least every RETURN_COUNT oracle updates so no return is missed.
+    pub fn accrue_variance(ctx: Context<AccrueVariance>) -> Result<()> {
+        let market = &mut ctx.accounts.market;
+        require!(!market.is_expired, ErrorCode::MarketExpired);
+        require!(
+            market.variance_type != VarianceType::Standard,
+            ErrorCode::InvalidVarianceType
+        );
+
+        let returns = read_return_observations(&ctx.accounts.volatility_stats)?;
+        let skipped = market.accrue_returns(&returns);
+
+        emit!(VarianceAccrued {
+            market: market.key(),
+            last_return_sequence: market.last_return_sequence,
+            observed_returns: market.observed_returns,
+            skipped_returns: skipped,
+            conditional_variance: market.conditional_variance(),
+        });
+
+        Ok(())
+    }
+}
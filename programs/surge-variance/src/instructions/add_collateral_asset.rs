@@ -0,0 +1,71 @@
+use crate::errors::ErrorCode;
+use crate::state::*;
+use crate::events::*;
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::Mint;
+
+#[derive(Accounts)]
+pub struct AddCollateralAsset<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + 32 + 32 + 1 + 2 + 32 + 32 + 8 + 1,
+        seeds = [b"collateral_asset", authority.key().as_ref(), mint.key().as_ref()],
+        bump
+    )]
+    pub collateral_asset: Account<'info, CollateralAsset>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    /// Pyth price update account, omit for a stablecoin pegged at $1
+    /// CHECK: Validated through load_pyth_price
+    pub price_feed: Option<AccountInfo<'info>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> AddCollateralAsset<'info> {
+    pub fn add_collateral_asset(
+        ctx: Context<AddCollateralAsset>,
+        haircut_bps: u16,
+        feed_id: [u8; 32],
+        max_price_age: i64,
+    ) -> Result<()> {
+        require!(haircut_bps < 10000, ErrorCode::InvalidCollateralAsset);
+        require!(max_price_age > 0, ErrorCode::InvalidCollateralAsset);
+
+        let price_feed = ctx
+            .accounts
+            .price_feed
+            .as_ref()
+            .map(|price_feed| price_feed.key())
+            .unwrap_or_default();
+
+        let collateral_asset = &mut ctx.accounts.collateral_asset;
+        collateral_asset.authority = ctx.accounts.authority.key();
+        collateral_asset.mint = ctx.accounts.mint.key();
+        collateral_asset.decimals = ctx.accounts.mint.decimals;
+        collateral_asset.haircut_bps = haircut_bps;
+        collateral_asset.price_feed = price_feed;
+        collateral_asset.feed_id = feed_id;
+        collateral_asset.max_price_age = max_price_age;
+        collateral_asset.bump = ctx.bumps.collateral_asset;
+
+        // Make sure the feed can be read before markets rely on it
+        collateral_asset.usd_value(0, ctx.accounts.price_feed.as_ref())?;
+
+        emit!(CollateralAssetUpdated {
+            collateral_asset: collateral_asset.key(),
+            authority: collateral_asset.authority,
+            mint: collateral_asset.mint,
+            haircut_bps,
+            price_feed,
+            max_price_age,
+        });
+
+        Ok(())
+    }
+}
@@ -0,0 +1,210 @@
+use crate::errors::ErrorCode;
+use crate::state::*;
+use crate::events::*;
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{self, Mint, MintTo, TokenAccount, TokenInterface, TransferChecked};
+
+#[derive(Accounts)]
+pub struct AddLiquidity<'info> {
+    #[account(
+        mut,
+        constraint = market.is_derived_at(&market.key()) @ ErrorCode::InvalidMarket,
+        constraint = !market.is_paused(PAUSE_MINTS) @ ErrorCode::MintsPaused,
+    )]
+    pub market: Account<'info, Market>,
+
+    #[account(
+        seeds = [b"pool", market.key().as_ref()],
+        bump = pool.bump,
+        has_one = market,
+        has_one = lp_mint,
+        has_one = long_vault,
+        has_one = short_vault,
+    )]
+    pub pool: Account<'info, Pool>,
+
+    pub user_authority: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = user_collateral.mint == market.collateral_mint @ ErrorCode::InvalidCollateralAsset,
+    )]
+    pub user_collateral: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(mut, address = market.collateral_vault)]
+    pub collateral_vault: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(address = market.collateral_mint)]
+    pub collateral_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(mut, address = market.var_long_mint)]
+    pub var_long_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(mut, address = market.var_short_mint)]
+    pub var_short_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(mut)]
+    pub lp_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(mut)]
+    pub long_vault: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub short_vault: InterfaceAccount<'info, TokenAccount>,
+
+    /// Receives the long tokens left over when the reserves are uneven
+    #[account(
+        mut,
+        constraint = user_var_long.mint == market.var_long_mint @ ErrorCode::InvalidAmount,
+    )]
+    pub user_var_long: InterfaceAccount<'info, TokenAccount>,
+
+    /// Receives the short tokens left over when the reserves are uneven
+    #[account(
+        mut,
+        constraint = user_var_short.mint == market.var_short_mint @ ErrorCode::InvalidAmount,
+    )]
+    pub user_var_short: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        constraint = user_lp.mint == pool.lp_mint @ ErrorCode::InvalidAmount,
+    )]
+    pub user_lp: InterfaceAccount<'info, TokenAccount>,
+
+    /// Registry entry pricing the collateral, required once the market has one
+    pub collateral_asset: Option<Account<'info, CollateralAsset>>,
+
+    /// Pyth price update for the collateral asset
+    /// CHECK: Validated through load_pyth_price
+    pub collateral_price_feed: Option<AccountInfo<'info>>,
+
+    /// Token program owning the VAR and LP mints
+    pub token_program: Interface<'info, TokenInterface>,
+
+    /// Token program owning the collateral mint
+    pub collateral_token_program: Interface<'info, TokenInterface>,
+}
+
+impl<'info> AddLiquidity<'info> {
+    /// Splits `amount` collateral into pairs at two units of its USD value each and adds them at the
+    /// current reserve ratio. The cheaper side's surplus is returned to the provider.
+    pub fn add_liquidity(ctx: Context<AddLiquidity>, amount: u64, min_lp_out: u64) -> Result<()> {
+        let market = &ctx.accounts.market;
+        require!(!market.is_expired_at(Clock::get()?.unix_timestamp), ErrorCode::MarketExpired);
+        require!(amount > 0, ErrorCode::InvalidAmount);
+
+        // Pairs are minted at the haircut USD value of the deposit, like mint_tokens
+        let pairs = pairs_for_value(collateral_value(
+            market,
+            &ctx.accounts.collateral_asset,
+            &ctx.accounts.collateral_price_feed,
+            amount,
+        )?);
+        require!(pairs > 0, ErrorCode::InvalidCollateralAsset);
+
+        let long_reserve = ctx.accounts.long_vault.amount;
+        let short_reserve = ctx.accounts.short_vault.amount;
+        let max_reserve = long_reserve.max(short_reserve);
+        require!(max_reserve > 0, ErrorCode::InsufficientLiquidity);
+
+        let proportional = |reserve: u64| -> Result<u64> {
+            let share = (pairs as u128)
+                .checked_mul(reserve as u128)
+                .ok_or(ErrorCode::NumberOverflow)?
+                / max_reserve as u128;
+            u64::try_from(share).map_err(|_| ErrorCode::NumberOverflow.into())
+        };
+        let long_added = proportional(long_reserve)?;
+        let short_added = proportional(short_reserve)?;
+        let lp_amount = proportional(ctx.accounts.lp_mint.supply)?;
+        require!(lp_amount > 0, ErrorCode::InvalidAmount);
+        require!(lp_amount >= min_lp_out, ErrorCode::SlippageExceeded);
+        market.check_mint_caps(
+            long_added,
+            short_added,
+            amount,
+            ctx.accounts.var_long_mint.supply,
+            ctx.accounts.var_short_mint.supply,
+        )?;
+
+        token_interface::transfer_checked(
+            CpiContext::new(
+                ctx.accounts.collateral_token_program.to_account_info(),
+                TransferChecked {
+                    from: ctx.accounts.user_collateral.to_account_info(),
+                    mint: ctx.accounts.collateral_mint.to_account_info(),
+                    to: ctx.accounts.collateral_vault.to_account_info(),
+                    authority: ctx.accounts.user_authority.to_account_info(),
+                },
+            ),
+            amount,
+            ctx.accounts.collateral_mint.decimals,
+        )?;
+
+        let seeds = market.seeds();
+        let market_seeds = seeds.as_slices();
+        let market_signer = &[&market_seeds[..]];
+
+        let mints = [
+            (&ctx.accounts.var_long_mint, &ctx.accounts.long_vault, long_added),
+            (&ctx.accounts.var_short_mint, &ctx.accounts.short_vault, short_added),
+            (&ctx.accounts.var_long_mint, &ctx.accounts.user_var_long, pairs - long_added),
+            (&ctx.accounts.var_short_mint, &ctx.accounts.user_var_short, pairs - short_added),
+        ];
+        for (mint, to, mint_amount) in mints {
+            if mint_amount == 0 {
+                continue;
+            }
+            token_interface::mint_to(
+                CpiContext::new_with_signer(
+                    ctx.accounts.token_program.to_account_info(),
+                    MintTo {
+                        mint: mint.to_account_info(),
+                        to: to.to_account_info(),
+                        authority: market.to_account_info(),
+                    },
+                    market_signer,
+                ),
+                mint_amount,
+            )?;
+        }
+
+        let market_key = market.key();
+        let pool_seeds = &[
+            b"pool".as_ref(),
+            market_key.as_ref(),
+            &[ctx.accounts.pool.bump]
+        ];
+
+        token_interface::mint_to(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                MintTo {
+                    mint: ctx.accounts.lp_mint.to_account_info(),
+                    to: ctx.accounts.user_lp.to_account_info(),
+                    authority: ctx.accounts.pool.to_account_info(),
+                },
+                &[&pool_seeds[..]],
+            ),
+            lp_amount,
+        )?;
+
+        let market = &mut ctx.accounts.market;
+        market.total_deposits = market
+            .total_deposits
+            .checked_add(amount)
+            .ok_or(ErrorCode::NumberOverflow)?;
+
+        emit!(LiquidityAdded {
+            market: market_key,
+            user: ctx.accounts.user_authority.key(),
+            amount,
+            lp_amount,
+            long_added,
+            short_added,
+        });
+
+        Ok(())
+    }
+}
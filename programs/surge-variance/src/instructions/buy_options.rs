@@ -0,0 +1,126 @@
+use crate::errors::ErrorCode;
+use crate::state::*;
+use crate::events::*;
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked};
+
+#[derive(Accounts)]
+pub struct BuyOptions<'info> {
+    pub market: Account<'info, Market>,
+
+    #[account(
+        seeds = [b"option_offer", market.key().as_ref(), offer.writer.as_ref()],
+        bump = offer.bump,
+        has_one = market,
+    )]
+    pub offer: Account<'info, OptionOffer>,
+
+    #[account(mut, address = offer.escrow)]
+    pub offer_escrow: InterfaceAccount<'info, TokenAccount>,
+
+    pub buyer: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = buyer_collateral.mint == market.collateral_mint @ ErrorCode::InvalidCollateralAsset,
+    )]
+    pub buyer_collateral: InterfaceAccount<'info, TokenAccount>,
+
+    /// Receives the options
+    #[account(
+        mut,
+        constraint = buyer_var_long.mint == market.var_long_mint @ ErrorCode::InvalidAmount,
+    )]
+    pub buyer_var_long: InterfaceAccount<'info, TokenAccount>,
+
+    /// Receives the premium
+    #[account(
+        mut,
+        constraint = writer_collateral.owner == offer.writer @ ErrorCode::Unauthorized,
+        constraint = writer_collateral.mint == market.collateral_mint @ ErrorCode::InvalidCollateralAsset,
+    )]
+    pub writer_collateral: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(address = market.collateral_mint)]
+    pub collateral_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(address = market.var_long_mint)]
+    pub var_long_mint: InterfaceAccount<'info, Mint>,
+
+    /// Token program owning the VAR mints
+    pub token_program: Interface<'info, TokenInterface>,
+
+    /// Token program owning the collateral mint
+    pub collateral_token_program: Interface<'info, TokenInterface>,
+}
+
+impl<'info> BuyOptions<'info> {
+    /// Buys `amount` options from a writer's offer, paying the premium to the writer
+    pub fn buy_options(ctx: Context<BuyOptions>, amount: u64, max_premium: u64) -> Result<()> {
+        require!(
+            !ctx.accounts.market.is_expired_at(Clock::get()?.unix_timestamp),
+            ErrorCode::MarketExpired
+        );
+        require!(amount > 0, ErrorCode::InvalidAmount);
+        require!(
+            ctx.accounts.offer_escrow.amount >= amount,
+            ErrorCode::InsufficientLiquidity
+        );
+
+        let premium = amount
+            .checked_mul(ctx.accounts.offer.premium_per_option)
+            .ok_or(ErrorCode::NumberOverflow)?;
+        require!(premium <= max_premium, ErrorCode::PremiumExceeded);
+
+        if premium > 0 {
+            token_interface::transfer_checked(
+                CpiContext::new(
+                    ctx.accounts.collateral_token_program.to_account_info(),
+                    TransferChecked {
+                        from: ctx.accounts.buyer_collateral.to_account_info(),
+                        mint: ctx.accounts.collateral_mint.to_account_info(),
+                        to: ctx.accounts.writer_collateral.to_account_info(),
+                        authority: ctx.accounts.buyer.to_account_info(),
+                    },
+                ),
+                premium,
+                ctx.accounts.collateral_mint.decimals,
+            )?;
+        }
+
+        let offer = &ctx.accounts.offer;
+        let market_key = ctx.accounts.market.key();
+        let offer_seeds = &[
+            b"option_offer".as_ref(),
+            market_key.as_ref(),
+            offer.writer.as_ref(),
+            &[offer.bump]
+        ];
+
+        token_interface::transfer_checked(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                TransferChecked {
+                    from: ctx.accounts.offer_escrow.to_account_info(),
+                    mint: ctx.accounts.var_long_mint.to_account_info(),
+                    to: ctx.accounts.buyer_var_long.to_account_info(),
+                    authority: offer.to_account_info(),
+                },
+                &[&offer_seeds[..]],
+            ),
+            amount,
+            ctx.accounts.var_long_mint.decimals,
+        )?;
+
+        emit!(OptionsBought {
+            market: market_key,
+            offer: offer.key(),
+            writer: offer.writer,
+            buyer: ctx.accounts.buyer.key(),
+            amount,
+            premium,
+        });
+
+        Ok(())
+    }
+}
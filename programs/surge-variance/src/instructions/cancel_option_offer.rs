@@ -0,0 +1,92 @@
+use crate::errors::ErrorCode;
+use crate::state::*;
+use crate::events::*;
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{self, CloseAccount, Mint, TokenAccount, TokenInterface, TransferChecked};
+
+#[derive(Accounts)]
+pub struct CancelOptionOffer<'info> {
+    pub market: Account<'info, Market>,
+
+    #[account(
+        mut,
+        close = writer,
+        seeds = [b"option_offer", market.key().as_ref(), writer.key().as_ref()],
+        bump = offer.bump,
+        has_one = market,
+        has_one = writer @ ErrorCode::Unauthorized,
+    )]
+    pub offer: Account<'info, OptionOffer>,
+
+    #[account(mut, address = offer.escrow)]
+    pub offer_escrow: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub writer: Signer<'info>,
+
+    /// Receives the unsold options
+    #[account(
+        mut,
+        constraint = writer_var_long.mint == market.var_long_mint @ ErrorCode::InvalidAmount,
+    )]
+    pub writer_var_long: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(address = market.var_long_mint)]
+    pub var_long_mint: InterfaceAccount<'info, Mint>,
+
+    /// Token program owning the VAR mints
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+impl<'info> CancelOptionOffer<'info> {
+    /// Returns the unsold options to the writer and closes the offer
+    pub fn cancel_option_offer(ctx: Context<CancelOptionOffer>) -> Result<()> {
+        let returned = ctx.accounts.offer_escrow.amount;
+
+        let offer = &ctx.accounts.offer;
+        let market_key = ctx.accounts.market.key();
+        let offer_seeds = &[
+            b"option_offer".as_ref(),
+            market_key.as_ref(),
+            offer.writer.as_ref(),
+            &[offer.bump]
+        ];
+        let signer = &[&offer_seeds[..]];
+
+        if returned > 0 {
+            token_interface::transfer_checked(
+                CpiContext::new_with_signer(
+                    ctx.accounts.token_program.to_account_info(),
+                    TransferChecked {
+                        from: ctx.accounts.offer_escrow.to_account_info(),
+                        mint: ctx.accounts.var_long_mint.to_account_info(),
+                        to: ctx.accounts.writer_var_long.to_account_info(),
+                        authority: offer.to_account_info(),
+                    },
+                    signer,
+                ),
+                returned,
+                ctx.accounts.var_long_mint.decimals,
+            )?;
+        }
+
+        token_interface::close_account(CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            CloseAccount {
+                account: ctx.accounts.offer_escrow.to_account_info(),
+                destination: ctx.accounts.writer.to_account_info(),
+                authority: offer.to_account_info(),
+            },
+            signer,
+        ))?;
+
+        emit!(OptionOfferCancelled {
+            market: market_key,
+            offer: offer.key(),
+            writer: offer.writer,
+            returned,
+        });
+
+        Ok(())
+    }
+}
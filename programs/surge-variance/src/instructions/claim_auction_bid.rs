@@ -0,0 +1,143 @@
+use crate::errors::ErrorCode;
+use crate::state::*;
+use crate::events::*;
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{self, Mint, MintTo, TokenAccount, TokenInterface, TransferChecked};
+
+#[derive(Accounts)]
+pub struct ClaimAuctionBid<'info> {
+    #[account(
+        mut,
+        constraint = market.is_derived_at(&market.key()) @ ErrorCode::InvalidMarket,
+        has_one = auction,
+    )]
+    pub market: Account<'info, Market>,
+
+    #[account(has_one = market)]
+    pub auction: Account<'info, Auction>,
+
+    #[account(
+        mut,
+        close = owner,
+        seeds = [
+            b"auction_bid",
+            auction.key().as_ref(),
+            owner.key().as_ref(),
+            &[bid.is_long as u8],
+            &[bid.bucket],
+        ],
+        bump = bid.bump,
+        has_one = auction,
+        has_one = owner @ ErrorCode::Unauthorized,
+    )]
+    pub bid: Account<'info, AuctionBid>,
+
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    /// Receives the unfilled part of the bid
+    #[account(
+        mut,
+        constraint = owner_collateral.mint == market.collateral_mint @ ErrorCode::InvalidCollateralAsset,
+    )]
+    pub owner_collateral: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(mut, address = market.collateral_vault)]
+    pub collateral_vault: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(address = market.collateral_mint)]
+    pub collateral_mint: InterfaceAccount<'info, Mint>,
+
+    /// VAR mint of the bid's side
+    #[account(
+        mut,
+        constraint = var_mint.key() == if bid.is_long {
+            market.var_long_mint
+        } else {
+            market.var_short_mint
+        } @ ErrorCode::InvalidAmount,
+    )]
+    pub var_mint: InterfaceAccount<'info, Mint>,
+
+    /// Receives the filled part of the bid as VAR tokens
+    #[account(
+        mut,
+        constraint = owner_var.mint == var_mint.key() @ ErrorCode::InvalidAmount,
+    )]
+    pub owner_var: InterfaceAccount<'info, TokenAccount>,
+
+    /// Token program owning the VAR mints
+    pub token_program: Interface<'info, TokenInterface>,
+
+    /// Token program owning the collateral mint
+    pub collateral_token_program: Interface<'info, TokenInterface>,
+}
+
+impl<'info> ClaimAuctionBid<'info> {
+    /// Mints the filled part of a bid as VAR tokens and refunds the rest
+    pub fn claim_auction_bid(ctx: Context<ClaimAuctionBid>) -> Result<()> {
+        let auction = &ctx.accounts.auction;
+        require!(auction.is_cleared, ErrorCode::AuctionNotCleared);
+
+        let bid = &ctx.accounts.bid;
+        let filled = bid.filled_amount(auction)?;
+        let refunded = bid.amount.checked_sub(filled).ok_or(ErrorCode::NumberOverflow)?;
+
+        let market = &ctx.accounts.market;
+        let market_seeds = market.seeds();
+        let seeds = market_seeds.as_slices();
+        let signer = &[&seeds[..]];
+
+        if filled > 0 {
+            token_interface::mint_to(
+                CpiContext::new_with_signer(
+                    ctx.accounts.token_program.to_account_info(),
+                    MintTo {
+                        mint: ctx.accounts.var_mint.to_account_info(),
+                        to: ctx.accounts.owner_var.to_account_info(),
+                        authority: market.to_account_info(),
+                    },
+                    signer,
+                ),
+                filled,
+            )?;
+        }
+
+        if refunded > 0 {
+            token_interface::transfer_checked(
+                CpiContext::new_with_signer(
+                    ctx.accounts.collateral_token_program.to_account_info(),
+                    TransferChecked {
+                        from: ctx.accounts.collateral_vault.to_account_info(),
+                        mint: ctx.accounts.collateral_mint.to_account_info(),
+                        to: ctx.accounts.owner_collateral.to_account_info(),
+                        authority: market.to_account_info(),
+                    },
+                    signer,
+                ),
+                refunded,
+                ctx.accounts.collateral_mint.decimals,
+            )?;
+        }
+
+        // The fill is now part of the minted supply
+        let market = &mut ctx.accounts.market;
+        if bid.is_long {
+            market.unclaimed_long_fills = market.unclaimed_long_fills.saturating_sub(filled);
+        } else {
+            market.unclaimed_short_fills = market.unclaimed_short_fills.saturating_sub(filled);
+        }
+
+        emit!(AuctionBidClaimed {
+            market: market.key(),
+            auction: auction.key(),
+            user: ctx.accounts.owner.key(),
+            is_long: bid.is_long,
+            bucket: bid.bucket,
+            filled,
+            refunded,
+        });
+
+        Ok(())
+    }
+}
@@ -0,0 +1,140 @@
+use crate::errors::ErrorCode;
+use crate::state::*;
+use crate::events::*;
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{self, Burn, Mint, TokenAccount, TokenInterface, TransferChecked};
+
+#[derive(Accounts)]
+pub struct ClaimSettlement<'info> {
+    #[account(
+        mut,
+        constraint = market.is_derived_at(&market.key()) @ ErrorCode::InvalidMarket,
+        constraint = !market.is_paused(PAUSE_REDEEMS) @ ErrorCode::RedeemsPaused,
+    )]
+    pub market: Account<'info, Market>,
+
+    pub user_authority: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = user_collateral.mint == market.collateral_mint @ ErrorCode::InvalidCollateralAsset,
+    )]
+    pub user_collateral: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(mut, address = market.collateral_vault)]
+    pub collateral_vault: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(address = market.collateral_mint)]
+    pub collateral_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(mut, address = market.var_long_mint)]
+    pub var_long_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(mut, address = market.var_short_mint)]
+    pub var_short_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        constraint = user_var_long.mint == market.var_long_mint @ ErrorCode::InvalidAmount,
+    )]
+    pub user_var_long: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        constraint = user_var_short.mint == market.var_short_mint @ ErrorCode::InvalidAmount,
+    )]
+    pub user_var_short: InterfaceAccount<'info, TokenAccount>,
+
+    /// Token program owning the VAR mints
+    pub token_program: Interface<'info, TokenInterface>,
+
+    /// Token program owning the collateral mint
+    pub collateral_token_program: Interface<'info, TokenInterface>,
+}
+
+impl<'info> ClaimSettlement<'info> {
+    /// Burns the holder's VAR tokens of a settled market for their pro-rata share
+    /// of each side's payout
+    pub fn claim_settlement(ctx: Context<ClaimSettlement>) -> Result<()> {
+        require!(ctx.accounts.market.settlement_finalized, ErrorCode::MarketNotSettled);
+
+        let long_amount = ctx.accounts.user_var_long.amount;
+        let short_amount = ctx.accounts.user_var_short.amount;
+        require!(long_amount > 0 || short_amount > 0, ErrorCode::InvalidAmount);
+
+        // Auction fills not claimed yet keep their share of the payout
+        let market = &ctx.accounts.market;
+        let long_supply = market.outstanding_supply(true, ctx.accounts.var_long_mint.supply)?;
+        let short_supply = market.outstanding_supply(false, ctx.accounts.var_short_mint.supply)?;
+        let long_payout = settlement_share(long_amount, long_supply, market.long_payout)?;
+        let short_payout = settlement_share(short_amount, short_supply, market.short_payout)?;
+        let payout = long_payout.checked_add(short_payout).ok_or(ErrorCode::NumberOverflow)?;
+
+        for (mint, from, amount) in [
+            (&ctx.accounts.var_long_mint, &ctx.accounts.user_var_long, long_amount),
+            (&ctx.accounts.var_short_mint, &ctx.accounts.user_var_short, short_amount),
+        ] {
+            if amount == 0 {
+                continue;
+            }
+            token_interface::burn(
+                CpiContext::new(
+                    ctx.accounts.token_program.to_account_info(),
+                    Burn {
+                        mint: mint.to_account_info(),
+                        from: from.to_account_info(),
+                        authority: ctx.accounts.user_authority.to_account_info(),
+                    },
+                ),
+                amount,
+            )?;
+        }
+
+        if payout > 0 {
+            let market_seeds = market.seeds();
+            let seeds = market_seeds.as_slices();
+
+            token_interface::transfer_checked(
+                CpiContext::new_with_signer(
+                    ctx.accounts.collateral_token_program.to_account_info(),
+                    TransferChecked {
+                        from: ctx.accounts.collateral_vault.to_account_info(),
+                        mint: ctx.accounts.collateral_mint.to_account_info(),
+                        to: ctx.accounts.user_collateral.to_account_info(),
+                        authority: market.to_account_info(),
+                    },
+                    &[&seeds[..]],
+                ),
+                payout,
+                ctx.accounts.collateral_mint.decimals,
+            )?;
+        }
+
+        let market = &mut ctx.accounts.market;
+        market.long_payout -= long_payout;
+        market.short_payout -= short_payout;
+
+        emit!(SettlementClaimed {
+            market: market.key(),
+            user: ctx.accounts.user_authority.key(),
+            long_amount,
+            short_amount,
+            payout,
+        });
+
+        Ok(())
+    }
+}
+
+/// Share of `remaining_payout` owed to `amount` out of the outstanding `supply`.
+/// Claims shrink both, so the last holder receives any rounding dust.
+pub fn settlement_share(amount: u64, supply: u64, remaining_payout: u64) -> Result<u64> {
+    if amount == 0 || supply == 0 {
+        return Ok(0);
+    }
+    let share = (amount as u128)
+        .checked_mul(remaining_payout as u128)
+        .ok_or(ErrorCode::NumberOverflow)?
+        / supply as u128;
+    Ok(share as u64)
+}
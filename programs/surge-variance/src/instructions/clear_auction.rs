@@ -0,0 +1,75 @@
+use crate::errors::ErrorCode;
+use crate::state::*;
+use crate::events::*;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct ClearAuction<'info> {
+    #[account(
+        mut,
+        has_one = auction,
+        has_one = volatility_stats,
+        constraint = !market.is_paused(PAUSE_UPDATES) @ ErrorCode::UpdatesPaused,
+    )]
+    pub market: Account<'info, Market>,
+
+    #[account(mut, has_one = market)]
+    pub auction: Account<'info, Auction>,
+
+    /// The volatility stats account from the oracle program
+    /// CHECK: This account is not owned by this program, but we read from it
+    pub volatility_stats: AccountInfo<'info>,
+}
+
+impl<'info> ClearAuction<'info> {
+    /// Permissionless once bidding has ended. Sets the market strike to the
+    /// clearing strike and opens the market, with variance measured from now.
+    pub fn clear_auction(ctx: Context<ClearAuction>) -> Result<()> {
+        let auction = &mut ctx.accounts.auction;
+        require!(!auction.is_cleared, ErrorCode::AuctionClosed);
+        require!(
+            Clock::get()?.unix_timestamp >= auction.end_time,
+            ErrorCode::AuctionNotEnded
+        );
+
+        let clearing_bucket = auction.clearing_bucket()?;
+        let (long_notional, short_notional) = auction.notionals(clearing_bucket)?;
+        let matched = long_notional.min(short_notional);
+
+        auction.is_cleared = true;
+        auction.clearing_bucket = clearing_bucket;
+        auction.long_notional = long_notional;
+        auction.short_notional = short_notional;
+        auction.matched = matched;
+
+        let strike = auction.strike(clearing_bucket);
+        let start_volatility = read_annualized_volatility(&ctx.accounts.volatility_stats)?;
+
+        // Filled bids are minted 1:1 when claimed, so both sides count as deposits now
+        // and as outstanding supply until then. Rounding each fill down can leave a
+        // few units per side that are never claimed.
+        let market = &mut ctx.accounts.market;
+        market.strike = strike;
+        market.start_volatility = start_volatility;
+        market.auction_open = false;
+        market.total_deposits = market
+            .total_deposits
+            .checked_add(matched.checked_mul(2).ok_or(ErrorCode::NumberOverflow)?)
+            .ok_or(ErrorCode::NumberOverflow)?;
+        market.unclaimed_long_fills = matched;
+        market.unclaimed_short_fills = matched;
+
+        emit!(AuctionCleared {
+            market: market.key(),
+            auction: auction.key(),
+            strike,
+            clearing_bucket,
+            long_notional,
+            short_notional,
+            matched,
+            start_volatility,
+        });
+
+        Ok(())
+    }
+}
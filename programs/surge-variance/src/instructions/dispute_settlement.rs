@@ -0,0 +1,73 @@
+use crate::errors::ErrorCode;
+use crate::state::*;
+use crate::events::*;
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::Mint;
+
+#[derive(Accounts)]
+pub struct DisputeSettlement<'info> {
+    #[account(
+        mut,
+        has_one = guardian @ ErrorCode::Unauthorized,
+        has_one = volatility_stats @ ErrorCode::InvalidOracle,
+        has_one = var_long_mint,
+    )]
+    pub market: Account<'info, Market>,
+
+    pub guardian: Signer<'info>,
+
+    /// Option mint for option markets, every outstanding option shares the payoff
+    pub var_long_mint: InterfaceAccount<'info, Mint>,
+
+    /// The volatility stats account from the oracle program
+    /// CHECK: This account is not owned by this program, but we read from it
+    pub volatility_stats: AccountInfo<'info>,
+}
+
+impl<'info> DisputeSettlement<'info> {
+    /// Overrides a proposed settlement during its dispute window. The volatility is
+    /// recomputed as a TWAP over the market's `dispute_twap_window` ending at expiry;
+    /// conditional markets re-accrue every return from the oracle's history instead.
+    ///
+    /// The guardian is trusted to decide whether a settlement is disputed, but not
+    /// what it settles on: the window is fixed by the authority in the settlement
+    /// terms and the volatility always comes from the oracle.
+    pub fn dispute_settlement(ctx: Context<DisputeSettlement>) -> Result<()> {
+        let market = &mut ctx.accounts.market;
+        require!(market.is_expired, ErrorCode::MarketNotSettled);
+        require!(
+            !market.settlement_finalized && Clock::get()?.unix_timestamp < market.dispute_deadline(),
+            ErrorCode::DisputeWindowClosed
+        );
+
+        let proposed_realized_variance = market.realized_variance;
+        let twap_window = market.dispute_twap_window;
+        let annualized_volatility = if market.option_type == OptionType::None
+            && market.variance_type != VarianceType::Standard
+        {
+            market.reaccrue_returns(&read_return_observations(&ctx.accounts.volatility_stats)?)?;
+            0.0
+        } else {
+            let end = if market.expiry_timestamp != 0 {
+                market.expiry_timestamp
+            } else {
+                market.settlement_proposed_at
+            };
+            require!(twap_window > 0, ErrorCode::InvalidSettlementTerms);
+            read_settlement_volatility(&ctx.accounts.volatility_stats, twap_window, end)?
+        };
+        market.resettle(annualized_volatility, ctx.accounts.var_long_mint.supply)?;
+
+        emit!(SettlementDisputed {
+            market: market.key(),
+            guardian: ctx.accounts.guardian.key(),
+            proposed_realized_variance,
+            realized_variance: market.realized_variance,
+            long_payout: market.long_payout,
+            short_payout: market.short_payout,
+            twap_window,
+        });
+
+        Ok(())
+    }
+}
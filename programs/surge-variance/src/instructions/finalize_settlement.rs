@@ -0,0 +1,37 @@
+use crate::errors::ErrorCode;
+use crate::state::*;
+use crate::events::*;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct FinalizeSettlement<'info> {
+    #[account(
+        mut,
+        constraint = !market.is_paused(PAUSE_UPDATES) @ ErrorCode::UpdatesPaused,
+    )]
+    pub market: Account<'info, Market>,
+}
+
+impl<'info> FinalizeSettlement<'info> {
+    /// Permissionless once the dispute window has passed. Unlocks claims and rolls.
+    pub fn finalize_settlement(ctx: Context<FinalizeSettlement>) -> Result<()> {
+        let market = &mut ctx.accounts.market;
+        require!(market.is_expired, ErrorCode::MarketNotSettled);
+        require!(!market.settlement_finalized, ErrorCode::MarketExpired);
+        require!(
+            Clock::get()?.unix_timestamp >= market.dispute_deadline(),
+            ErrorCode::DisputeWindowOpen
+        );
+
+        market.settlement_finalized = true;
+
+        emit!(SettlementFinalized {
+            market: market.key(),
+            realized_variance: market.realized_variance,
+            long_payout: market.long_payout,
+            short_payout: market.short_payout,
+        });
+
+        Ok(())
+    }
+}
@@ -1,15 +1,20 @@
+use crate::errors::ErrorCode;
 use crate::state::*;
 use crate::events::*;
 use anchor_lang::prelude::*;
-use anchor_spl::token::{self, Mint, Token, TokenAccount};
+use anchor_lang::system_program::{self, Transfer};
+use anchor_spl::token_interface::{
+    self, spl_token_2022, spl_token_metadata_interface::state::TokenMetadata, Mint,
+    TokenAccount, TokenInterface, TokenMetadataInitialize,
+};
 
 #[derive(Accounts)]
-#[instruction(epoch: u64, strike: f64, timestamp: i64, bumps: MarketBumps)]
+#[instruction(epoch: u64, strike: f64, timestamp: i64, bumps: MarketBumps, long_metadata: VarTokenMetadata, short_metadata: VarTokenMetadata)]
 pub struct InitializeMarket<'info> {
     #[account(
         init,
         payer = authority,
-        space = 8 + 32 + 32 + 32 + 32 + 32 + 32 + 8 + 8 + 1 + 8 + 8 + 8 + 1 + 1 + 8,
+        space = MARKET_SPACE,
         seeds = [
             b"market", 
             &epoch.to_le_bytes()[..],
@@ -22,20 +27,34 @@ pub struct InitializeMarket<'info> {
     #[account(mut)]
     pub authority: Signer<'info>,
 
-    #[account(mut)]
-    pub usdc_vault: Account<'info, TokenAccount>,
+    #[account(
+        mut,
+        constraint = collateral_vault.mint == collateral_mint.key() @ ErrorCode::InvalidCollateralAsset,
+    )]
+    pub collateral_vault: InterfaceAccount<'info, TokenAccount>,
+
+    /// Collateral mint, 6-decimal USDC unless a collateral asset is supplied
+    pub collateral_mint: InterfaceAccount<'info, Mint>,
+
+    /// Registry entry pricing collateral_mint, omit for USDC
+    #[account(
+        constraint = collateral_asset.mint == collateral_mint.key() @ ErrorCode::InvalidCollateralAsset,
+        constraint = collateral_asset.authority == authority.key() @ ErrorCode::Unauthorized,
+    )]
+    pub collateral_asset: Option<Account<'info, CollateralAsset>>,
 
     #[account(mut)]
-    pub var_long_mint: Account<'info, Mint>,
+    pub var_long_mint: InterfaceAccount<'info, Mint>,
 
     #[account(mut)]
-    pub var_short_mint: Account<'info, Mint>,
+    pub var_short_mint: InterfaceAccount<'info, Mint>,
 
     /// The volatility stats account from the oracle program
     /// CHECK: This account is not owned by this program, but we read from it
     pub volatility_stats: AccountInfo<'info>,
 
-    pub token_program: Program<'info, Token>,
+    /// Token program owning the VAR mints, metadata is written when this is Token-2022
+    pub token_program: Interface<'info, TokenInterface>,
     pub system_program: Program<'info, System>,
 }
 
@@ -46,7 +65,34 @@ impl<'info> InitializeMarket<'info> {
         strike: f64,
         timestamp: i64,
         bumps: MarketBumps,
+        long_metadata: VarTokenMetadata,
+        short_metadata: VarTokenMetadata,
     ) -> Result<()> {
+        // Without a registry entry the collateral must be 6-decimal USDC
+        let collateral_asset = match &ctx.accounts.collateral_asset {
+            Some(collateral_asset) => collateral_asset.key(),
+            None => {
+                require!(ctx.accounts.collateral_mint.decimals == 6, ErrorCode::InvalidCollateralAsset);
+                Pubkey::default()
+            }
+        };
+
+        // Token-2022 VAR mints get name, symbol and URI so wallets can display them.
+        // The mints must be created with a metadata pointer to themselves.
+        if ctx.accounts.token_program.key() == spl_token_2022::ID {
+            let market_info = ctx.accounts.market.to_account_info();
+            ctx.accounts.initialize_metadata(
+                ctx.accounts.var_long_mint.to_account_info(),
+                market_info.clone(),
+                long_metadata,
+            )?;
+            ctx.accounts.initialize_metadata(
+                ctx.accounts.var_short_mint.to_account_info(),
+                market_info,
+                short_metadata,
+            )?;
+        }
+
         let market = &mut ctx.accounts.market;
 
         // Initialize market state
@@ -54,7 +100,9 @@ impl<'info> InitializeMarket<'info> {
         market.strike = strike;
         market.timestamp = timestamp;
         market.authority = ctx.accounts.authority.key();
-        market.usdc_vault = ctx.accounts.usdc_vault.key();
+        market.collateral_vault = ctx.accounts.collateral_vault.key();
+        market.collateral_mint = ctx.accounts.collateral_mint.key();
+        market.collateral_asset = collateral_asset;
         market.var_long_mint = ctx.accounts.var_long_mint.key();
         market.var_short_mint = ctx.accounts.var_short_mint.key();
         market.volatility_stats = ctx.accounts.volatility_stats.key();
@@ -62,6 +110,31 @@ impl<'info> InitializeMarket<'info> {
         market.is_initialized = true;
         market.is_expired = false;
         market.total_deposits = 0;
+        market.guardian = ctx.accounts.authority.key();
+        market.pause_flags = 0;
+        market.auction = Pubkey::default();
+        market.auction_open = false;
+        market.series = Pubkey::default();
+        market.expiry_timestamp = 0;
+        market.long_payout = 0;
+        market.short_payout = 0;
+        market.variance_type = VarianceType::Standard;
+        market.reference_price = 0;
+        market.last_return_sequence = 0;
+        market.observed_returns = 0;
+        market.conditional_sum = 0.0;
+        market.option_type = OptionType::None;
+        market.payout_per_point = 0;
+        market.max_payoff = 0;
+        market.variance_cap = 0.0;
+        market.first_return_sequence = 0;
+        market.dispute_window = 0;
+        market.settlement_proposed_at = 0;
+        market.settlement_finalized = false;
+        market.caps = MarketCaps::default();
+        market.unclaimed_long_fills = 0;
+        market.unclaimed_short_fills = 0;
+        market.dispute_twap_window = 0;
         
         // Get the annualized_volatility from the volatility_stats account
         let data = ctx.accounts.volatility_stats.try_borrow_data()?;
@@ -76,27 +149,27 @@ impl<'info> InitializeMarket<'info> {
         market.start_volatility = annualized_volatility;
 
         // Transfer authority of the mints to the PDA
-        token::set_authority(
+        token_interface::set_authority(
             CpiContext::new(
                 ctx.accounts.token_program.to_account_info(),
-                token::SetAuthority {
+                token_interface::SetAuthority {
                     current_authority: ctx.accounts.authority.to_account_info(),
                     account_or_mint: ctx.accounts.var_long_mint.to_account_info(),
                 },
             ),
-            token::spl_token::instruction::AuthorityType::MintTokens,
+            spl_token_2022::instruction::AuthorityType::MintTokens,
             Some(market.key()),
         )?;
 
-        token::set_authority(
+        token_interface::set_authority(
             CpiContext::new(
                 ctx.accounts.token_program.to_account_info(),
-                token::SetAuthority {
+                token_interface::SetAuthority {
                     current_authority: ctx.accounts.authority.to_account_info(),
                     account_or_mint: ctx.accounts.var_short_mint.to_account_info(),
                 },
             ),
-            token::spl_token::instruction::AuthorityType::MintTokens,
+            spl_token_2022::instruction::AuthorityType::MintTokens,
             Some(market.key()),
         )?;
 
@@ -104,7 +177,9 @@ impl<'info> InitializeMarket<'info> {
         emit!(MarketInitialized {
             market: market.key(),
             authority: market.authority,
-            usdc_vault: market.usdc_vault,
+            collateral_vault: market.collateral_vault,
+            collateral_mint: market.collateral_mint,
+            collateral_asset: market.collateral_asset,
             var_long_mint: market.var_long_mint,
             var_short_mint: market.var_short_mint,
             epoch: market.epoch,
@@ -115,4 +190,50 @@ impl<'info> InitializeMarket<'info> {
 
         Ok(())
     }
+
+    fn initialize_metadata(
+        &self,
+        mint: AccountInfo<'info>,
+        update_authority: AccountInfo<'info>,
+        metadata: VarTokenMetadata,
+    ) -> Result<()> {
+        // Fund the mint for the metadata it is about to grow by
+        let token_metadata = TokenMetadata {
+            name: metadata.name,
+            symbol: metadata.symbol,
+            uri: metadata.uri,
+            ..Default::default()
+        };
+        let required_lamports = Rent::get()?
+            .minimum_balance(mint.data_len() + token_metadata.tlv_size_of()?)
+            .saturating_sub(mint.lamports());
+        if required_lamports > 0 {
+            system_program::transfer(
+                CpiContext::new(
+                    self.system_program.to_account_info(),
+                    Transfer {
+                        from: self.authority.to_account_info(),
+                        to: mint.clone(),
+                    },
+                ),
+                required_lamports,
+            )?;
+        }
+
+        token_interface::token_metadata_initialize(
+            CpiContext::new(
+                self.token_program.to_account_info(),
+                TokenMetadataInitialize {
+                    program_id: self.token_program.to_account_info(),
+                    metadata: mint.clone(),
+                    update_authority,
+                    mint_authority: self.authority.to_account_info(),
+                    mint,
+                },
+            ),
+            token_metadata.name,
+            token_metadata.symbol,
+            token_metadata.uri,
+        )
+    }
 }
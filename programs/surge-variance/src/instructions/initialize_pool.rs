@@ -0,0 +1,212 @@
+use crate::errors::ErrorCode;
+use crate::state::*;
+use crate::events::*;
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token_interface::{self, Mint, MintTo, TokenAccount, TokenInterface, TransferChecked};
+
+#[derive(Accounts)]
+pub struct InitializePool<'info> {
+    #[account(
+        mut,
+        constraint = market.is_derived_at(&market.key()) @ ErrorCode::InvalidMarket,
+        has_one = authority @ ErrorCode::Unauthorized,
+        constraint = !market.is_paused(PAUSE_MINTS) @ ErrorCode::MintsPaused,
+    )]
+    pub market: Account<'info, Market>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + 32 + 32 + 32 + 32 + 2 + 1,
+        seeds = [b"pool", market.key().as_ref()],
+        bump
+    )]
+    pub pool: Account<'info, Pool>,
+
+    #[account(
+        init,
+        payer = authority,
+        seeds = [b"pool_lp_mint", pool.key().as_ref()],
+        bump,
+        mint::decimals = 6,
+        mint::authority = pool,
+        mint::token_program = token_program,
+    )]
+    pub lp_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        init,
+        payer = authority,
+        seeds = [b"pool_long", pool.key().as_ref()],
+        bump,
+        token::mint = var_long_mint,
+        token::authority = pool,
+        token::token_program = token_program,
+    )]
+    pub long_vault: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        init,
+        payer = authority,
+        seeds = [b"pool_short", pool.key().as_ref()],
+        bump,
+        token::mint = var_short_mint,
+        token::authority = pool,
+        token::token_program = token_program,
+    )]
+    pub short_vault: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(mut, address = market.var_long_mint)]
+    pub var_long_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(mut, address = market.var_short_mint)]
+    pub var_short_mint: InterfaceAccount<'info, Mint>,
+
+    /// Authority's collateral seeding the pool
+    #[account(mut)]
+    pub authority_collateral: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(mut, address = market.collateral_vault)]
+    pub collateral_vault: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(address = market.collateral_mint)]
+    pub collateral_mint: InterfaceAccount<'info, Mint>,
+
+    /// Authority's LP token account
+    #[account(
+        init,
+        payer = authority,
+        associated_token::mint = lp_mint,
+        associated_token::authority = authority,
+        associated_token::token_program = token_program,
+    )]
+    pub authority_lp: InterfaceAccount<'info, TokenAccount>,
+
+    /// Registry entry pricing the collateral, required once the market has one
+    pub collateral_asset: Option<Account<'info, CollateralAsset>>,
+
+    /// Pyth price update for the collateral asset
+    /// CHECK: Validated through load_pyth_price
+    pub collateral_price_feed: Option<AccountInfo<'info>>,
+
+    /// Token program owning the VAR mints
+    pub token_program: Interface<'info, TokenInterface>,
+
+    /// Token program owning the collateral mint
+    pub collateral_token_program: Interface<'info, TokenInterface>,
+
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> InitializePool<'info> {
+    pub fn initialize_pool(ctx: Context<InitializePool>, fee_bps: u16, seed_amount: u64) -> Result<()> {
+        let market = &ctx.accounts.market;
+        require!(!market.is_expired_at(Clock::get()?.unix_timestamp), ErrorCode::MarketExpired);
+        require!(!market.auction_open, ErrorCode::AuctionInProgress);
+        require!(market.option_type == OptionType::None, ErrorCode::OptionMarket);
+        require!(fee_bps <= MAX_POOL_FEE_BPS, ErrorCode::InvalidPoolFee);
+        require!(seed_amount > 0, ErrorCode::InvalidAmount);
+
+        // Pairs are minted at the haircut USD value of the seed, like mint_tokens
+        let seed_pairs = pairs_for_value(collateral_value(
+            market,
+            &ctx.accounts.collateral_asset,
+            &ctx.accounts.collateral_price_feed,
+            seed_amount,
+        )?);
+        require!(seed_pairs > 0, ErrorCode::InvalidCollateralAsset);
+        market.check_mint_caps(
+            seed_pairs,
+            seed_pairs,
+            seed_amount,
+            ctx.accounts.var_long_mint.supply,
+            ctx.accounts.var_short_mint.supply,
+        )?;
+
+        // Seed the reserves with equal long and short, i.e. an even implied price
+        token_interface::transfer_checked(
+            CpiContext::new(
+                ctx.accounts.collateral_token_program.to_account_info(),
+                TransferChecked {
+                    from: ctx.accounts.authority_collateral.to_account_info(),
+                    mint: ctx.accounts.collateral_mint.to_account_info(),
+                    to: ctx.accounts.collateral_vault.to_account_info(),
+                    authority: ctx.accounts.authority.to_account_info(),
+                },
+            ),
+            seed_amount,
+            ctx.accounts.collateral_mint.decimals,
+        )?;
+
+        let seeds = market.seeds();
+        let market_seeds = seeds.as_slices();
+        let market_signer = &[&market_seeds[..]];
+
+        for (mint, to) in [
+            (&ctx.accounts.var_long_mint, &ctx.accounts.long_vault),
+            (&ctx.accounts.var_short_mint, &ctx.accounts.short_vault),
+        ] {
+            token_interface::mint_to(
+                CpiContext::new_with_signer(
+                    ctx.accounts.token_program.to_account_info(),
+                    MintTo {
+                        mint: mint.to_account_info(),
+                        to: to.to_account_info(),
+                        authority: market.to_account_info(),
+                    },
+                    market_signer,
+                ),
+                seed_pairs,
+            )?;
+        }
+
+        let market_key = market.key();
+        let pool_seeds = &[
+            b"pool".as_ref(),
+            market_key.as_ref(),
+            &[ctx.bumps.pool]
+        ];
+
+        token_interface::mint_to(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                MintTo {
+                    mint: ctx.accounts.lp_mint.to_account_info(),
+                    to: ctx.accounts.authority_lp.to_account_info(),
+                    authority: ctx.accounts.pool.to_account_info(),
+                },
+                &[&pool_seeds[..]],
+            ),
+            seed_pairs,
+        )?;
+
+        let pool = &mut ctx.accounts.pool;
+        pool.market = market_key;
+        pool.lp_mint = ctx.accounts.lp_mint.key();
+        pool.long_vault = ctx.accounts.long_vault.key();
+        pool.short_vault = ctx.accounts.short_vault.key();
+        pool.fee_bps = fee_bps;
+        pool.bump = ctx.bumps.pool;
+
+        let market = &mut ctx.accounts.market;
+        market.total_deposits = market
+            .total_deposits
+            .checked_add(seed_amount)
+            .ok_or(ErrorCode::NumberOverflow)?;
+
+        emit!(PoolInitialized {
+            market: market_key,
+            pool: pool.key(),
+            lp_mint: pool.lp_mint,
+            fee_bps,
+            seed_amount,
+        });
+
+        Ok(())
+    }
+}
@@ -0,0 +1,97 @@
+use crate::errors::ErrorCode;
+use crate::state::*;
+use crate::events::*;
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::Mint;
+
+#[derive(Accounts)]
+#[instruction(series_id: u64)]
+pub struct InitializeSeries<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = SERIES_SPACE,
+        seeds = [b"series", authority.key().as_ref(), &series_id.to_le_bytes()],
+        bump
+    )]
+    pub series: Account<'info, Series>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    /// Series markets are collateralized 1:1 in 6-decimal USDC
+    #[account(
+        constraint = collateral_mint.decimals == 6 @ ErrorCode::UnsupportedCollateral,
+    )]
+    pub collateral_mint: InterfaceAccount<'info, Mint>,
+
+    /// The volatility stats account from the oracle program
+    /// CHECK: This account is not owned by this program, but we read from it
+    pub volatility_stats: AccountInfo<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> InitializeSeries<'info> {
+    #[allow(clippy::too_many_arguments)]
+    pub fn initialize_series(
+        ctx: Context<InitializeSeries>,
+        series_id: u64,
+        tenor: i64,
+        strike_rule: StrikeRule,
+        base_strike: f64,
+        strike_spread: f64,
+        variance_cap: f64,
+        start_timestamp: i64,
+        dispute_window: i64,
+        dispute_twap_window: i64,
+    ) -> Result<()> {
+        require!(tenor > 0, ErrorCode::InvalidSeries);
+        require!(base_strike.is_finite() && base_strike >= 0.0, ErrorCode::InvalidSeries);
+        require!(strike_spread.is_finite(), ErrorCode::InvalidSeries);
+        require!(variance_cap.is_finite() && variance_cap >= 0.0, ErrorCode::InvalidSeries);
+        require!(
+            Market::valid_dispute_terms(dispute_window, dispute_twap_window),
+            ErrorCode::InvalidSeries
+        );
+
+        // Validate the oracle account up front so rolls cannot get stuck on it
+        read_annualized_volatility(&ctx.accounts.volatility_stats)?;
+
+        let series = &mut ctx.accounts.series;
+        series.authority = ctx.accounts.authority.key();
+        series.series_id = series_id;
+        series.volatility_stats = ctx.accounts.volatility_stats.key();
+        series.collateral_mint = ctx.accounts.collateral_mint.key();
+        series.tenor = tenor;
+        series.strike_rule = strike_rule;
+        series.base_strike = base_strike;
+        series.strike_spread = strike_spread;
+        series.variance_cap = variance_cap;
+        series.current_market = Pubkey::default();
+        series.next_epoch = 0;
+        series.next_timestamp = start_timestamp;
+        series.last_realized_variance = 0.0;
+        series.bump = ctx.bumps.series;
+        series.dispute_window = dispute_window;
+        series.dispute_twap_window = dispute_twap_window;
+
+        emit!(SeriesInitialized {
+            series: series.key(),
+            authority: series.authority,
+            series_id,
+            volatility_stats: series.volatility_stats,
+            collateral_mint: series.collateral_mint,
+            tenor,
+            strike_rule,
+            base_strike,
+            strike_spread,
+            variance_cap,
+            start_timestamp,
+            dispute_window,
+            dispute_twap_window,
+        });
+
+        Ok(())
+    }
+}
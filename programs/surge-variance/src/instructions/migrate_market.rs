@@ -0,0 +1,80 @@
+use crate::errors::ErrorCode;
+use crate::state::*;
+use crate::events::*;
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{self, Transfer};
+use anchor_spl::token_interface::TokenAccount;
+
+#[derive(Accounts)]
+pub struct MigrateMarket<'info> {
+    /// CHECK: Market created by an earlier version of the program. It is resized
+    /// before it is deserialized, which also checks the discriminator.
+    #[account(mut, owner = crate::ID)]
+    pub market: UncheckedAccount<'info>,
+
+    /// Pays the rent for the added space
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    /// Vault of the market, its mint becomes the market's collateral mint
+    pub collateral_vault: InterfaceAccount<'info, TokenAccount>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> MigrateMarket<'info> {
+    /// Grows a market created by an earlier version of the program to the current
+    /// layout. Fields it predates read as zero, which is their default, except the
+    /// guardian and collateral mint, and an expired market counts as finalized.
+    pub fn migrate_market(ctx: Context<MigrateMarket>) -> Result<()> {
+        let info = ctx.accounts.market.to_account_info();
+        let old_size = info.data_len();
+        require!(old_size < MARKET_SPACE, ErrorCode::AlreadyMigrated);
+
+        let required_lamports = Rent::get()?
+            .minimum_balance(MARKET_SPACE)
+            .saturating_sub(info.lamports());
+        if required_lamports > 0 {
+            system_program::transfer(
+                CpiContext::new(
+                    ctx.accounts.system_program.to_account_info(),
+                    Transfer {
+                        from: ctx.accounts.authority.to_account_info(),
+                        to: info.clone(),
+                    },
+                ),
+                required_lamports,
+            )?;
+        }
+        info.realloc(MARKET_SPACE, true)?;
+
+        let mut data = info.try_borrow_mut_data()?;
+        let mut market = Market::try_deserialize(&mut &data[..])?;
+        require!(market.authority == ctx.accounts.authority.key(), ErrorCode::Unauthorized);
+        require!(
+            ctx.accounts.collateral_vault.key() == market.collateral_vault,
+            ErrorCode::InvalidCollateralAsset
+        );
+
+        if market.guardian == Pubkey::default() {
+            market.guardian = market.authority;
+        }
+        if market.collateral_mint == Pubkey::default() {
+            market.collateral_mint = ctx.accounts.collateral_vault.mint;
+        }
+        // Legacy markets paid out when they expired, there is no settlement left to dispute
+        if market.is_expired && market.settlement_proposed_at == 0 {
+            market.settlement_finalized = true;
+        }
+        market.try_serialize(&mut &mut data[..])?;
+
+        emit!(MarketMigrated {
+            market: info.key(),
+            authority: market.authority,
+            old_size: old_size as u64,
+            new_size: MARKET_SPACE as u64,
+        });
+
+        Ok(())
+    }
+}
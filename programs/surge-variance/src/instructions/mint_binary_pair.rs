@@ -0,0 +1,126 @@
+use crate::errors::ErrorCode;
+use crate::state::*;
+use crate::events::*;
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{self, Mint, MintTo, TokenAccount, TokenInterface, TransferChecked};
+
+#[derive(Accounts)]
+pub struct MintBinaryPair<'info> {
+    #[account(
+        mut,
+        constraint = market.is_derived_at(&market.key()) @ ErrorCode::InvalidMarket,
+        constraint = !market.is_paused(PAUSE_MINTS) @ ErrorCode::MintsPaused,
+    )]
+    pub market: Account<'info, Market>,
+
+    pub user_authority: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = user_collateral.mint == market.collateral_mint @ ErrorCode::InvalidCollateralAsset,
+    )]
+    pub user_collateral: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(mut, address = market.collateral_vault)]
+    pub collateral_vault: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(address = market.collateral_mint)]
+    pub collateral_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(mut, address = market.var_long_mint)]
+    pub var_long_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(mut, address = market.var_short_mint)]
+    pub var_short_mint: InterfaceAccount<'info, Mint>,
+
+    /// Receives the YES tokens
+    #[account(
+        mut,
+        constraint = user_var_long.mint == market.var_long_mint @ ErrorCode::InvalidAmount,
+    )]
+    pub user_var_long: InterfaceAccount<'info, TokenAccount>,
+
+    /// Receives the NO tokens
+    #[account(
+        mut,
+        constraint = user_var_short.mint == market.var_short_mint @ ErrorCode::InvalidAmount,
+    )]
+    pub user_var_short: InterfaceAccount<'info, TokenAccount>,
+
+    /// Token program owning the VAR mints
+    pub token_program: Interface<'info, TokenInterface>,
+
+    /// Token program owning the collateral mint
+    pub collateral_token_program: Interface<'info, TokenInterface>,
+}
+
+impl<'info> MintBinaryPair<'info> {
+    /// Deposits `amount` collateral for `amount` YES and `amount` NO tokens
+    pub fn mint_binary_pair(ctx: Context<MintBinaryPair>, amount: u64) -> Result<()> {
+        let market = &ctx.accounts.market;
+        require!(market.option_type == OptionType::Binary, ErrorCode::InvalidOptionTerms);
+        require!(
+            !market.is_expired_at(Clock::get()?.unix_timestamp),
+            ErrorCode::MarketExpired
+        );
+        require!(amount > 0, ErrorCode::InvalidAmount);
+        market.check_mint_caps(
+            amount,
+            amount,
+            amount,
+            ctx.accounts.var_long_mint.supply,
+            ctx.accounts.var_short_mint.supply,
+        )?;
+
+        token_interface::transfer_checked(
+            CpiContext::new(
+                ctx.accounts.collateral_token_program.to_account_info(),
+                TransferChecked {
+                    from: ctx.accounts.user_collateral.to_account_info(),
+                    mint: ctx.accounts.collateral_mint.to_account_info(),
+                    to: ctx.accounts.collateral_vault.to_account_info(),
+                    authority: ctx.accounts.user_authority.to_account_info(),
+                },
+            ),
+            amount,
+            ctx.accounts.collateral_mint.decimals,
+        )?;
+
+        let market_seeds = market.seeds();
+        let seeds = market_seeds.as_slices();
+        let signer = &[&seeds[..]];
+
+        for (mint, to) in [
+            (&ctx.accounts.var_long_mint, &ctx.accounts.user_var_long),
+            (&ctx.accounts.var_short_mint, &ctx.accounts.user_var_short),
+        ] {
+            token_interface::mint_to(
+                CpiContext::new_with_signer(
+                    ctx.accounts.token_program.to_account_info(),
+                    MintTo {
+                        mint: mint.to_account_info(),
+                        to: to.to_account_info(),
+                        authority: market.to_account_info(),
+                    },
+                    signer,
+                ),
+                amount,
+            )?;
+        }
+
+        let market = &mut ctx.accounts.market;
+        market.total_deposits = market
+            .total_deposits
+            .checked_add(amount)
+            .ok_or(ErrorCode::NumberOverflow)?;
+
+        emit!(BinaryPairMinted {
+            market: market.key(),
+            user: ctx.accounts.user_authority.key(),
+            amount,
+            total_deposits: market.total_deposits,
+        });
+
+        Ok(())
+    }
+}
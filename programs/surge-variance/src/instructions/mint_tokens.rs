@@ -2,99 +2,154 @@ use crate::errors::ErrorCode;
 use crate::state::*;
 use crate::events::*;
 use anchor_lang::prelude::*;
-use anchor_spl::token::{self, Mint, Token, TokenAccount, Transfer};
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked};
 
 #[derive(Accounts)]
 #[instruction(amount: u64, is_long: bool, epoch: u64, timestamp: i64, bumps: MarketBumps)]
 pub struct MintTokens<'info> {
     #[account(
         mut,
-        seeds = [
-            b"market", 
-            &epoch.to_le_bytes()[..],
-            &timestamp.to_le_bytes()[..],
-        ],
-        bump
+        constraint = market.epoch == epoch && market.timestamp == timestamp @ ErrorCode::InvalidMarket,
+        constraint = market.is_derived_at(&market.key()) @ ErrorCode::InvalidMarket,
+        constraint = !market.is_paused(PAUSE_MINTS) @ ErrorCode::MintsPaused,
     )]
     pub market: Account<'info, Market>,
 
+    #[account(mut)]
     pub user_authority: Signer<'info>,
 
-    #[account(mut)]
-    pub user_usdc: Account<'info, TokenAccount>,
+    /// VAR tokens minted by the user, checked against the per-user cap
+    #[account(
+        init_if_needed,
+        payer = user_authority,
+        space = USER_DEPOSIT_SPACE,
+        seeds = [b"user_deposit", market.key().as_ref(), user_authority.key().as_ref()],
+        bump
+    )]
+    pub user_deposit: Account<'info, UserDeposit>,
 
-    #[account(mut)]
-    pub usdc_vault: Account<'info, TokenAccount>,
+    #[account(
+        mut,
+        constraint = user_collateral.mint == market.collateral_mint @ ErrorCode::InvalidCollateralAsset,
+    )]
+    pub user_collateral: InterfaceAccount<'info, TokenAccount>,
 
-    #[account(mut)]
-    pub var_long_mint: Account<'info, Mint>,
+    #[account(
+        mut,
+        constraint = collateral_vault.key() == market.collateral_vault @ ErrorCode::InvalidCollateralAsset,
+    )]
+    pub collateral_vault: InterfaceAccount<'info, TokenAccount>,
 
-    #[account(mut)]
-    pub var_short_mint: Account<'info, Mint>,
+    #[account(
+        constraint = collateral_mint.key() == market.collateral_mint @ ErrorCode::InvalidCollateralAsset,
+    )]
+    pub collateral_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(mut, address = market.var_long_mint)]
+    pub var_long_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(mut, address = market.var_short_mint)]
+    pub var_short_mint: InterfaceAccount<'info, Mint>,
 
     #[account(mut)]
-    pub user_var_long: Account<'info, TokenAccount>,
+    pub user_var_long: InterfaceAccount<'info, TokenAccount>,
 
     #[account(mut)]
-    pub user_var_short: Account<'info, TokenAccount>,
+    pub user_var_short: InterfaceAccount<'info, TokenAccount>,
 
-    pub token_program: Program<'info, Token>,
+    /// Registry entry pricing the collateral, required once the market has one
+    pub collateral_asset: Option<Account<'info, CollateralAsset>>,
+
+    /// Pyth price update for the collateral asset
+    /// CHECK: Validated through load_pyth_price
+    pub collateral_price_feed: Option<AccountInfo<'info>>,
+
+    /// Token program owning the VAR mints
+    pub token_program: Interface<'info, TokenInterface>,
+
+    /// Token program owning the collateral mint
+    pub collateral_token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
 }
 
 impl<'info> MintTokens<'info> {
-    pub fn mint_tokens(ctx: Context<MintTokens>, amount: u64, is_long: bool, epoch: u64, timestamp: i64, bumps: MarketBumps) -> Result<()> {
+    pub fn mint_tokens(ctx: Context<MintTokens>, amount: u64, is_long: bool, _epoch: u64, _timestamp: i64, _bumps: MarketBumps) -> Result<()> {
         let market = &mut ctx.accounts.market;
-        require!(!market.is_expired, ErrorCode::MarketExpired);
+        require!(!market.is_expired_at(Clock::get()?.unix_timestamp), ErrorCode::MarketExpired);
+        require!(!market.auction_open, ErrorCode::AuctionInProgress);
+        require!(market.option_type == OptionType::None, ErrorCode::OptionMarket);
+
+        // VAR tokens are minted at the haircut USD value of the deposit
+        let var_amount = collateral_value(
+            market,
+            &ctx.accounts.collateral_asset,
+            &ctx.accounts.collateral_price_feed,
+            amount,
+        )?;
+        require!(var_amount > 0, ErrorCode::InvalidCollateralAsset);
+
+        let (long_amount, short_amount) = side_amounts(is_long, var_amount);
+        market.check_mint_caps(
+            long_amount,
+            short_amount,
+            amount,
+            ctx.accounts.var_long_mint.supply,
+            ctx.accounts.var_short_mint.supply,
+        )?;
 
-        // Transfer USDC from user to vault
-        token::transfer(
+        ctx.accounts.user_deposit.record_mint(
+            market.key(),
+            ctx.accounts.user_authority.key(),
+            ctx.bumps.user_deposit,
+            var_amount,
+            market.caps.max_user_deposits,
+        )?;
+
+        // Transfer collateral from user to vault
+        token_interface::transfer_checked(
             CpiContext::new(
-                ctx.accounts.token_program.to_account_info(),
-                Transfer {
-                    from: ctx.accounts.user_usdc.to_account_info(),
-                    to: ctx.accounts.usdc_vault.to_account_info(),
+                ctx.accounts.collateral_token_program.to_account_info(),
+                TransferChecked {
+                    from: ctx.accounts.user_collateral.to_account_info(),
+                    mint: ctx.accounts.collateral_mint.to_account_info(),
+                    to: ctx.accounts.collateral_vault.to_account_info(),
                     authority: ctx.accounts.user_authority.to_account_info(),
                 },
             ),
             amount,
+            ctx.accounts.collateral_mint.decimals,
         )?;
 
         // Mint VAR tokens to user
-        let epoch_bytes = epoch.to_le_bytes();
-        let timestamp_bytes = timestamp.to_le_bytes();
-        let seeds = &[
-            b"market".as_ref(), 
-            &epoch_bytes[..],
-            &timestamp_bytes[..],
-            &[bumps.market]
-        ];
+        let market_seeds = market.seeds();
+        let seeds = market_seeds.as_slices();
         let signer = &[&seeds[..]];
 
         if is_long {
-            token::mint_to(
+            token_interface::mint_to(
                 CpiContext::new_with_signer(
                     ctx.accounts.token_program.to_account_info(),
-                    token::MintTo {
+                    token_interface::MintTo {
                         mint: ctx.accounts.var_long_mint.to_account_info(),
                         to: ctx.accounts.user_var_long.to_account_info(),
                         authority: market.to_account_info(),
                     },
                     signer,
                 ),
-                amount,
+                var_amount,
             )?;
         } else {
-            token::mint_to(
+            token_interface::mint_to(
                 CpiContext::new_with_signer(
                     ctx.accounts.token_program.to_account_info(),
-                    token::MintTo {
+                    token_interface::MintTo {
                         mint: ctx.accounts.var_short_mint.to_account_info(),
                         to: ctx.accounts.user_var_short.to_account_info(),
                         authority: market.to_account_info(),
                     },
                     signer,
                 ),
-                amount,
+                var_amount,
             )?;
         }
 
@@ -107,7 +162,8 @@ impl<'info> MintTokens<'info> {
         emit!(TokensMinted {
             market: market.key(),
             user: ctx.accounts.user_authority.key(),
-            amount,
+            collateral_amount: amount,
+            amount: var_amount,
             is_long,
             total_deposits: market.total_deposits,
         });
@@ -1,7 +1,69 @@
 pub mod initialize;
 pub mod mint_tokens;
-pub mod redeem;
+pub mod set_guardian;
+pub mod set_pause_flags;
+pub mod add_collateral_asset;
+pub mod update_collateral_asset;
+pub mod initialize_pool;
+pub mod add_liquidity;
+pub mod remove_liquidity;
+pub mod swap;
+pub mod start_auction;
+pub mod submit_auction_bid;
+pub mod clear_auction;
+pub mod claim_auction_bid;
+pub mod initialize_series;
+pub mod roll_series;
+pub mod claim_settlement;
+pub mod roll;
+pub mod set_variance_type;
+pub mod accrue_variance;
+pub mod set_option_terms;
+pub mod write_options;
+pub mod buy_options;
+pub mod cancel_option_offer;
+pub mod settle_options;
+pub mod set_binary_terms;
+pub mod mint_binary_pair;
+pub mod redeem_binary_pair;
+pub mod set_settlement_terms;
+pub mod propose_settlement;
+pub mod dispute_settlement;
+pub mod finalize_settlement;
+pub mod set_market_caps;
+pub mod migrate_market;
 
 pub use initialize::*;
 pub use mint_tokens::*;
-pub use redeem::*;
+pub use set_guardian::*;
+pub use set_pause_flags::*;
+pub use add_collateral_asset::*;
+pub use update_collateral_asset::*;
+pub use initialize_pool::*;
+pub use add_liquidity::*;
+pub use remove_liquidity::*;
+pub use swap::*;
+pub use start_auction::*;
+pub use submit_auction_bid::*;
+pub use clear_auction::*;
+pub use claim_auction_bid::*;
+pub use initialize_series::*;
+pub use roll_series::*;
+pub use claim_settlement::*;
+pub use roll::*;
+pub use set_variance_type::*;
+pub use accrue_variance::*;
+pub use set_option_terms::*;
+pub use write_options::*;
+pub use buy_options::*;
+pub use cancel_option_offer::*;
+pub use settle_options::*;
+pub use set_binary_terms::*;
+pub use mint_binary_pair::*;
+pub use redeem_binary_pair::*;
+pub use set_settlement_terms::*;
+pub use propose_settlement::*;
+pub use dispute_settlement::*;
+pub use finalize_settlement::*;
+pub use set_market_caps::*;
+pub use migrate_market::*;
@@ -0,0 +1,55 @@
+use crate::errors::ErrorCode;
+use crate::state::*;
+use crate::events::*;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct ProposeSettlement<'info> {
+    #[account(
+        mut,
+        has_one = volatility_stats @ ErrorCode::InvalidOracle,
+        constraint = !market.is_paused(PAUSE_UPDATES) @ ErrorCode::UpdatesPaused,
+    )]
+    pub market: Account<'info, Market>,
+
+    /// The volatility stats account from the oracle program
+    /// CHECK: This account is not owned by this program, but we read from it
+    pub volatility_stats: AccountInfo<'info>,
+}
+
+impl<'info> ProposeSettlement<'info> {
+    /// Permissionless once a one-off variance market expires. Computes the
+    /// settlement from the oracle and opens its dispute window. Series epochs
+    /// are settled by roll_series and options by settle_options.
+    pub fn propose_settlement(ctx: Context<ProposeSettlement>) -> Result<()> {
+        let market = &mut ctx.accounts.market;
+        require!(market.series == Pubkey::default(), ErrorCode::SeriesMarket);
+        require!(market.option_type == OptionType::None, ErrorCode::OptionMarket);
+        require!(!market.is_expired, ErrorCode::MarketExpired);
+        require!(market.expiry_timestamp != 0, ErrorCode::InvalidSettlementTerms);
+
+        let now = Clock::get()?.unix_timestamp;
+        require!(now >= market.expiry_timestamp, ErrorCode::EpochNotEnded);
+
+        if market.variance_type != VarianceType::Standard {
+            market.accrue_returns(&read_return_observations(&ctx.accounts.volatility_stats)?);
+        }
+        // Settle on the estimate in effect at expiry, not whenever this is called
+        let annualized_volatility =
+            read_settlement_volatility(&ctx.accounts.volatility_stats, 0, market.expiry_timestamp)?;
+        market.settle(annualized_volatility)?;
+        market.propose_settlement(now);
+
+        emit!(MarketSettled {
+            market: market.key(),
+            realized_variance: market.realized_variance,
+            strike: market.strike,
+            long_payout: market.long_payout,
+            short_payout: market.short_payout,
+            total_deposits: market.total_deposits,
+            dispute_deadline: market.dispute_deadline(),
+        });
+
+        Ok(())
+    }
+}
@@ -0,0 +1,143 @@
+use crate::errors::ErrorCode;
+use crate::state::*;
+use crate::events::*;
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{self, Burn, Mint, TokenAccount, TokenInterface, TransferChecked};
+
+#[derive(Accounts)]
+pub struct RemoveLiquidity<'info> {
+    #[account(
+        constraint = !market.is_paused(PAUSE_REDEEMS) @ ErrorCode::RedeemsPaused,
+    )]
+    pub market: Account<'info, Market>,
+
+    #[account(
+        seeds = [b"pool", market.key().as_ref()],
+        bump = pool.bump,
+        has_one = market,
+        has_one = lp_mint,
+        has_one = long_vault,
+        has_one = short_vault,
+    )]
+    pub pool: Account<'info, Pool>,
+
+    pub user_authority: Signer<'info>,
+
+    #[account(address = market.var_long_mint)]
+    pub var_long_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(address = market.var_short_mint)]
+    pub var_short_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(mut)]
+    pub lp_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(mut)]
+    pub long_vault: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub short_vault: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        constraint = user_var_long.mint == market.var_long_mint @ ErrorCode::InvalidAmount,
+    )]
+    pub user_var_long: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        constraint = user_var_short.mint == market.var_short_mint @ ErrorCode::InvalidAmount,
+    )]
+    pub user_var_short: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        constraint = user_lp.mint == pool.lp_mint @ ErrorCode::InvalidAmount,
+    )]
+    pub user_lp: InterfaceAccount<'info, TokenAccount>,
+
+    /// Token program owning the VAR and LP mints
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+impl<'info> RemoveLiquidity<'info> {
+    /// Burns `lp_amount` LP tokens for a pro-rata share of both reserves
+    pub fn remove_liquidity(
+        ctx: Context<RemoveLiquidity>,
+        lp_amount: u64,
+        min_long_out: u64,
+        min_short_out: u64,
+    ) -> Result<()> {
+        require!(lp_amount > 0, ErrorCode::InvalidAmount);
+
+        let lp_supply = ctx.accounts.lp_mint.supply;
+        require!(lp_supply > 0, ErrorCode::InsufficientLiquidity);
+        let share = |reserve: u64| -> Result<u64> {
+            let value = (reserve as u128)
+                .checked_mul(lp_amount as u128)
+                .ok_or(ErrorCode::NumberOverflow)?
+                / lp_supply as u128;
+            u64::try_from(value).map_err(|_| ErrorCode::NumberOverflow.into())
+        };
+        let long_out = share(ctx.accounts.long_vault.amount)?;
+        let short_out = share(ctx.accounts.short_vault.amount)?;
+        require!(
+            long_out >= min_long_out && short_out >= min_short_out,
+            ErrorCode::SlippageExceeded
+        );
+
+        token_interface::burn(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                Burn {
+                    mint: ctx.accounts.lp_mint.to_account_info(),
+                    from: ctx.accounts.user_lp.to_account_info(),
+                    authority: ctx.accounts.user_authority.to_account_info(),
+                },
+            ),
+            lp_amount,
+        )?;
+
+        let market_key = ctx.accounts.market.key();
+        let pool_seeds = &[
+            b"pool".as_ref(),
+            market_key.as_ref(),
+            &[ctx.accounts.pool.bump]
+        ];
+        let pool_signer = &[&pool_seeds[..]];
+
+        let transfers = [
+            (&ctx.accounts.long_vault, &ctx.accounts.var_long_mint, &ctx.accounts.user_var_long, long_out),
+            (&ctx.accounts.short_vault, &ctx.accounts.var_short_mint, &ctx.accounts.user_var_short, short_out),
+        ];
+        for (from, mint, to, amount) in transfers {
+            if amount == 0 {
+                continue;
+            }
+            token_interface::transfer_checked(
+                CpiContext::new_with_signer(
+                    ctx.accounts.token_program.to_account_info(),
+                    TransferChecked {
+                        from: from.to_account_info(),
+                        mint: mint.to_account_info(),
+                        to: to.to_account_info(),
+                        authority: ctx.accounts.pool.to_account_info(),
+                    },
+                    pool_signer,
+                ),
+                amount,
+                mint.decimals,
+            )?;
+        }
+
+        emit!(LiquidityRemoved {
+            market: market_key,
+            user: ctx.accounts.user_authority.key(),
+            lp_amount,
+            long_out,
+            short_out,
+        });
+
+        Ok(())
+    }
+}
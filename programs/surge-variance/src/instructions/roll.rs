@@ -0,0 +1,237 @@
+use crate::errors::ErrorCode;
+use crate::state::*;
+use crate::events::*;
+use crate::instructions::claim_settlement::settlement_share;
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{self, Burn, Mint, MintTo, TokenAccount, TokenInterface, TransferChecked};
+
+#[derive(Accounts)]
+pub struct Roll<'info> {
+    #[account(
+        seeds = [b"series", series.authority.as_ref(), &series.series_id.to_le_bytes()],
+        bump = series.bump,
+    )]
+    pub series: Account<'info, Series>,
+
+    /// Settled market the holder is leaving
+    #[account(
+        mut,
+        constraint = market.is_derived_at(&market.key()) @ ErrorCode::InvalidMarket,
+        constraint = market.series == series.key() @ ErrorCode::InvalidSeries,
+        constraint = !market.is_paused(PAUSE_REDEEMS) @ ErrorCode::RedeemsPaused,
+    )]
+    pub market: Account<'info, Market>,
+
+    /// Open market of the same series receiving the proceeds
+    #[account(
+        mut,
+        constraint = next_market.is_derived_at(&next_market.key()) @ ErrorCode::InvalidMarket,
+        constraint = next_market.key() == series.current_market @ ErrorCode::InvalidSeries,
+        constraint = !next_market.is_paused(PAUSE_MINTS) @ ErrorCode::MintsPaused,
+    )]
+    pub next_market: Account<'info, Market>,
+
+    #[account(mut)]
+    pub user_authority: Signer<'info>,
+
+    /// VAR tokens minted by the user in the next market, checked against the per-user cap
+    #[account(
+        init_if_needed,
+        payer = user_authority,
+        space = USER_DEPOSIT_SPACE,
+        seeds = [b"user_deposit", next_market.key().as_ref(), user_authority.key().as_ref()],
+        bump
+    )]
+    pub next_user_deposit: Account<'info, UserDeposit>,
+
+    #[account(mut, address = market.collateral_vault)]
+    pub collateral_vault: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(mut, address = next_market.collateral_vault)]
+    pub next_collateral_vault: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(address = market.collateral_mint)]
+    pub collateral_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(mut, address = market.var_long_mint)]
+    pub var_long_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(mut, address = market.var_short_mint)]
+    pub var_short_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        constraint = user_var_long.mint == market.var_long_mint @ ErrorCode::InvalidAmount,
+    )]
+    pub user_var_long: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        constraint = user_var_short.mint == market.var_short_mint @ ErrorCode::InvalidAmount,
+    )]
+    pub user_var_short: InterfaceAccount<'info, TokenAccount>,
+
+    /// VAR mint of the chosen side in the next market
+    #[account(mut)]
+    pub next_var_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        constraint = user_next_var.mint == next_var_mint.key() @ ErrorCode::InvalidAmount,
+    )]
+    pub user_next_var: InterfaceAccount<'info, TokenAccount>,
+
+    /// VAR mint of the other side in the next market, read for the market caps
+    pub next_var_other_mint: InterfaceAccount<'info, Mint>,
+
+    /// Token program owning the VAR mints
+    pub token_program: Interface<'info, TokenInterface>,
+
+    /// Token program owning the collateral mint
+    pub collateral_token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> Roll<'info> {
+    /// Burns the holder's VAR tokens of a settled series market and deposits the
+    /// proceeds into the series' open market on the chosen side. The collateral
+    /// moves vault to vault and never reaches the holder.
+    pub fn roll(ctx: Context<Roll>, is_long: bool, min_amount_out: u64) -> Result<()> {
+        let market = &ctx.accounts.market;
+        let next_market = &ctx.accounts.next_market;
+        require!(market.settlement_finalized, ErrorCode::MarketNotSettled);
+        require!(
+            !next_market.is_expired_at(Clock::get()?.unix_timestamp),
+            ErrorCode::MarketExpired
+        );
+        require!(!next_market.auction_open, ErrorCode::AuctionInProgress);
+        require!(
+            market.option_type == OptionType::None && next_market.option_type == OptionType::None,
+            ErrorCode::OptionMarket
+        );
+        require!(
+            next_market.collateral_mint == market.collateral_mint,
+            ErrorCode::InvalidCollateralAsset
+        );
+        let (side_mint, other_mint) = if is_long {
+            (next_market.var_long_mint, next_market.var_short_mint)
+        } else {
+            (next_market.var_short_mint, next_market.var_long_mint)
+        };
+        require!(
+            ctx.accounts.next_var_mint.key() == side_mint
+                && ctx.accounts.next_var_other_mint.key() == other_mint,
+            ErrorCode::InvalidAmount
+        );
+
+        let long_amount = ctx.accounts.user_var_long.amount;
+        let short_amount = ctx.accounts.user_var_short.amount;
+        let long_supply = market.outstanding_supply(true, ctx.accounts.var_long_mint.supply)?;
+        let short_supply = market.outstanding_supply(false, ctx.accounts.var_short_mint.supply)?;
+        let long_payout = settlement_share(long_amount, long_supply, market.long_payout)?;
+        let short_payout = settlement_share(short_amount, short_supply, market.short_payout)?;
+        let amount = long_payout.checked_add(short_payout).ok_or(ErrorCode::NumberOverflow)?;
+        require!(amount > 0, ErrorCode::InvalidAmount);
+        require!(amount >= min_amount_out, ErrorCode::SlippageExceeded);
+
+        let (next_long_supply, next_short_supply) = if is_long {
+            (ctx.accounts.next_var_mint.supply, ctx.accounts.next_var_other_mint.supply)
+        } else {
+            (ctx.accounts.next_var_other_mint.supply, ctx.accounts.next_var_mint.supply)
+        };
+        let (long_amount_in, short_amount_in) = side_amounts(is_long, amount);
+        next_market.check_mint_caps(
+            long_amount_in,
+            short_amount_in,
+            amount,
+            next_long_supply,
+            next_short_supply,
+        )?;
+        ctx.accounts.next_user_deposit.record_mint(
+            next_market.key(),
+            ctx.accounts.user_authority.key(),
+            ctx.bumps.next_user_deposit,
+            amount,
+            next_market.caps.max_user_deposits,
+        )?;
+
+        for (mint, from, burn_amount) in [
+            (&ctx.accounts.var_long_mint, &ctx.accounts.user_var_long, long_amount),
+            (&ctx.accounts.var_short_mint, &ctx.accounts.user_var_short, short_amount),
+        ] {
+            if burn_amount == 0 {
+                continue;
+            }
+            token_interface::burn(
+                CpiContext::new(
+                    ctx.accounts.token_program.to_account_info(),
+                    Burn {
+                        mint: mint.to_account_info(),
+                        from: from.to_account_info(),
+                        authority: ctx.accounts.user_authority.to_account_info(),
+                    },
+                ),
+                burn_amount,
+            )?;
+        }
+
+        // Move the proceeds straight into the next market's vault
+        let market_seeds = market.seeds();
+        let seeds = market_seeds.as_slices();
+
+        token_interface::transfer_checked(
+            CpiContext::new_with_signer(
+                ctx.accounts.collateral_token_program.to_account_info(),
+                TransferChecked {
+                    from: ctx.accounts.collateral_vault.to_account_info(),
+                    mint: ctx.accounts.collateral_mint.to_account_info(),
+                    to: ctx.accounts.next_collateral_vault.to_account_info(),
+                    authority: market.to_account_info(),
+                },
+                &[&seeds[..]],
+            ),
+            amount,
+            ctx.accounts.collateral_mint.decimals,
+        )?;
+
+        let next_market_seeds = next_market.seeds();
+        let next_seeds = next_market_seeds.as_slices();
+
+        token_interface::mint_to(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                MintTo {
+                    mint: ctx.accounts.next_var_mint.to_account_info(),
+                    to: ctx.accounts.user_next_var.to_account_info(),
+                    authority: next_market.to_account_info(),
+                },
+                &[&next_seeds[..]],
+            ),
+            amount,
+        )?;
+
+        let market = &mut ctx.accounts.market;
+        market.long_payout -= long_payout;
+        market.short_payout -= short_payout;
+
+        let next_market = &mut ctx.accounts.next_market;
+        next_market.total_deposits = next_market
+            .total_deposits
+            .checked_add(amount)
+            .ok_or(ErrorCode::NumberOverflow)?;
+
+        emit!(PositionRolled {
+            series: ctx.accounts.series.key(),
+            market: market.key(),
+            next_market: next_market.key(),
+            user: ctx.accounts.user_authority.key(),
+            long_amount,
+            short_amount,
+            amount,
+            is_long,
+            total_deposits: next_market.total_deposits,
+        });
+
+        Ok(())
+    }
+}
@@ -0,0 +1,203 @@
+use crate::errors::ErrorCode;
+use crate::state::*;
+use crate::events::*;
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+#[derive(Accounts)]
+pub struct RollSeries<'info> {
+    #[account(
+        mut,
+        seeds = [b"series", series.authority.as_ref(), &series.series_id.to_le_bytes()],
+        bump = series.bump,
+        has_one = volatility_stats,
+        has_one = collateral_mint,
+    )]
+    pub series: Account<'info, Series>,
+
+    /// Market being settled, omitted on the first roll
+    #[account(
+        mut,
+        constraint = expiring_market.key() == series.current_market @ ErrorCode::InvalidSeries,
+        constraint = !expiring_market.is_paused(PAUSE_UPDATES) @ ErrorCode::UpdatesPaused,
+    )]
+    pub expiring_market: Option<Account<'info, Market>>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = MARKET_SPACE,
+        seeds = [
+            b"market",
+            series.key().as_ref(),
+            &series.next_epoch.to_le_bytes()[..],
+            &series.next_timestamp.to_le_bytes()[..],
+        ],
+        bump
+    )]
+    pub market: Account<'info, Market>,
+
+    #[account(
+        init,
+        payer = payer,
+        seeds = [b"var_long", market.key().as_ref()],
+        bump,
+        mint::decimals = 6,
+        mint::authority = market,
+        mint::token_program = token_program,
+    )]
+    pub var_long_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        init,
+        payer = payer,
+        seeds = [b"var_short", market.key().as_ref()],
+        bump,
+        mint::decimals = 6,
+        mint::authority = market,
+        mint::token_program = token_program,
+    )]
+    pub var_short_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        init,
+        payer = payer,
+        seeds = [b"collateral_vault", market.key().as_ref()],
+        bump,
+        token::mint = collateral_mint,
+        token::authority = market,
+        token::token_program = collateral_token_program,
+    )]
+    pub collateral_vault: InterfaceAccount<'info, TokenAccount>,
+
+    pub collateral_mint: InterfaceAccount<'info, Mint>,
+
+    /// The volatility stats account from the oracle program
+    /// CHECK: This account is not owned by this program, but we read from it
+    pub volatility_stats: AccountInfo<'info>,
+
+    /// Anyone may crank the series and pays the rent of the new epoch
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// Token program owning the VAR mints
+    pub token_program: Interface<'info, TokenInterface>,
+
+    /// Token program owning the collateral mint
+    pub collateral_token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> RollSeries<'info> {
+    /// Settles the series' current market once its epoch is over and opens the
+    /// next epoch with fresh mints and vault.
+    pub fn roll_series(ctx: Context<RollSeries>) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        let series = &ctx.accounts.series;
+        require!(now >= series.next_timestamp, ErrorCode::EpochNotEnded);
+
+        // The epoch boundary both settles the expiring market and starts the next one
+        let annualized_volatility =
+            read_settlement_volatility(&ctx.accounts.volatility_stats, 0, series.next_timestamp)?;
+
+        // Settle the expiring market, unless it was already settled manually
+        let mut settled_market = Pubkey::default();
+        let mut realized_variance = series.last_realized_variance;
+        if series.current_market != Pubkey::default() {
+            let expiring_market = ctx
+                .accounts
+                .expiring_market
+                .as_mut()
+                .ok_or(ErrorCode::InvalidSeries)?;
+            if !expiring_market.is_expired {
+                if expiring_market.variance_type != VarianceType::Standard {
+                    expiring_market.accrue_returns(&read_return_observations(&ctx.accounts.volatility_stats)?);
+                }
+                expiring_market.settle(annualized_volatility)?;
+                expiring_market.propose_settlement(now);
+
+                emit!(MarketSettled {
+                    market: expiring_market.key(),
+                    realized_variance: expiring_market.realized_variance,
+                    strike: expiring_market.strike,
+                    long_payout: expiring_market.long_payout,
+                    short_payout: expiring_market.short_payout,
+                    total_deposits: expiring_market.total_deposits,
+                    dispute_deadline: expiring_market.dispute_deadline(),
+                });
+            }
+            settled_market = expiring_market.key();
+            realized_variance = expiring_market.realized_variance;
+        }
+
+        let series = &mut ctx.accounts.series;
+        series.last_realized_variance = realized_variance;
+        let strike = series.next_strike();
+        let epoch = series.next_epoch;
+        let timestamp = series.next_timestamp;
+        let expiry_timestamp = timestamp
+            .checked_add(series.tenor)
+            .ok_or(ErrorCode::NumberOverflow)?;
+
+        let market = &mut ctx.accounts.market;
+        market.epoch = epoch;
+        market.strike = strike;
+        market.realized_variance = 0.0;
+        market.timestamp = timestamp;
+        market.authority = series.authority;
+        market.collateral_vault = ctx.accounts.collateral_vault.key();
+        market.collateral_mint = ctx.accounts.collateral_mint.key();
+        market.collateral_asset = Pubkey::default();
+        market.var_long_mint = ctx.accounts.var_long_mint.key();
+        market.var_short_mint = ctx.accounts.var_short_mint.key();
+        market.volatility_stats = series.volatility_stats;
+        market.start_volatility = annualized_volatility;
+        market.bumps = MarketBumps { market: ctx.bumps.market };
+        market.is_initialized = true;
+        market.is_expired = false;
+        market.total_deposits = 0;
+        market.guardian = series.authority;
+        market.pause_flags = 0;
+        market.auction = Pubkey::default();
+        market.auction_open = false;
+        market.series = series.key();
+        market.expiry_timestamp = expiry_timestamp;
+        market.long_payout = 0;
+        market.short_payout = 0;
+        market.variance_type = VarianceType::Standard;
+        market.reference_price = 0;
+        market.last_return_sequence = 0;
+        market.observed_returns = 0;
+        market.conditional_sum = 0.0;
+        market.option_type = OptionType::None;
+        market.payout_per_point = 0;
+        market.max_payoff = 0;
+        market.variance_cap = series.variance_cap;
+        market.first_return_sequence = 0;
+        market.dispute_window = series.dispute_window;
+        market.dispute_twap_window = series.dispute_twap_window;
+        market.settlement_proposed_at = 0;
+        market.settlement_finalized = false;
+        market.caps = MarketCaps::default();
+        market.unclaimed_long_fills = 0;
+        market.unclaimed_short_fills = 0;
+
+        series.current_market = market.key();
+        series.next_epoch = epoch.checked_add(1).ok_or(ErrorCode::NumberOverflow)?;
+        series.next_timestamp = expiry_timestamp;
+
+        emit!(SeriesRolled {
+            series: series.key(),
+            settled_market,
+            realized_variance,
+            market: market.key(),
+            epoch,
+            timestamp,
+            expiry_timestamp,
+            strike,
+            start_volatility: annualized_volatility,
+        });
+
+        Ok(())
+    }
+}
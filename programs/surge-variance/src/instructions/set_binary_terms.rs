@@ -0,0 +1,52 @@
+use crate::errors::ErrorCode;
+use crate::state::*;
+use crate::events::*;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct SetBinaryTerms<'info> {
+    #[account(
+        mut,
+        has_one = authority @ ErrorCode::Unauthorized,
+        constraint = !market.is_paused(PAUSE_UPDATES) @ ErrorCode::UpdatesPaused,
+    )]
+    pub market: Account<'info, Market>,
+
+    pub authority: Signer<'info>,
+}
+
+impl<'info> SetBinaryTerms<'info> {
+    /// Turns a market without deposits into a binary market on whether realized
+    /// volatility ends above the strike, in vol points. VAR-long tokens are YES
+    /// and VAR-short tokens NO.
+    pub fn set_binary_terms(ctx: Context<SetBinaryTerms>, expiry_timestamp: i64) -> Result<()> {
+        let market = &mut ctx.accounts.market;
+        let now = Clock::get()?.unix_timestamp;
+        require!(!market.is_expired_at(now), ErrorCode::MarketExpired);
+        require!(
+            market.total_deposits == 0 && !market.auction_open,
+            ErrorCode::InvalidOptionTerms
+        );
+
+        // Series settle linear payoffs and pairs are minted 1:1
+        require!(market.series == Pubkey::default(), ErrorCode::SeriesMarket);
+        require!(
+            market.collateral_asset == Pubkey::default(),
+            ErrorCode::UnsupportedCollateral
+        );
+        require!(market.variance_type == VarianceType::Standard, ErrorCode::InvalidOptionTerms);
+        require!(market.option_type == OptionType::None, ErrorCode::OptionMarket);
+        require!(expiry_timestamp > now, ErrorCode::InvalidOptionTerms);
+
+        market.option_type = OptionType::Binary;
+        market.expiry_timestamp = expiry_timestamp;
+
+        emit!(BinaryTermsSet {
+            market: market.key(),
+            strike: market.strike,
+            expiry_timestamp,
+        });
+
+        Ok(())
+    }
+}
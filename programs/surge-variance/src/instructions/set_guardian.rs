@@ -0,0 +1,32 @@
+use crate::errors::ErrorCode;
+use crate::state::*;
+use crate::events::*;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct SetGuardian<'info> {
+    #[account(
+        mut,
+        has_one = authority @ ErrorCode::Unauthorized,
+    )]
+    pub market: Account<'info, Market>,
+
+    pub authority: Signer<'info>,
+}
+
+impl<'info> SetGuardian<'info> {
+    pub fn set_guardian(ctx: Context<SetGuardian>, new_guardian: Pubkey) -> Result<()> {
+        let market = &mut ctx.accounts.market;
+        let old_guardian = market.guardian;
+        market.guardian = new_guardian;
+
+        emit!(GuardianUpdated {
+            market: market.key(),
+            authority: ctx.accounts.authority.key(),
+            old_guardian,
+            new_guardian,
+        });
+
+        Ok(())
+    }
+}
@@ -0,0 +1,44 @@
+use crate::errors::ErrorCode;
+use crate::state::*;
+use crate::events::*;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct SetMarketCaps<'info> {
+    #[account(
+        mut,
+        has_one = authority @ ErrorCode::Unauthorized,
+        constraint = !market.is_paused(PAUSE_UPDATES) @ ErrorCode::UpdatesPaused,
+    )]
+    pub market: Account<'info, Market>,
+
+    pub authority: Signer<'info>,
+}
+
+impl<'info> SetMarketCaps<'info> {
+    /// Sets the limits checked on every mint. Lowering a cap below the
+    /// current level only blocks further mints.
+    pub fn set_market_caps(ctx: Context<SetMarketCaps>, caps: MarketCaps) -> Result<()> {
+        // Without a floor the first one-sided mint into an empty market could never pass
+        require!(
+            caps.max_imbalance_bps == 0
+                || (caps.max_imbalance_bps >= 10000 && caps.imbalance_floor > 0),
+            ErrorCode::InvalidMarketCaps
+        );
+
+        let market = &mut ctx.accounts.market;
+        market.caps = caps;
+
+        emit!(MarketCapsUpdated {
+            market: market.key(),
+            authority: ctx.accounts.authority.key(),
+            max_total_deposits: caps.max_total_deposits,
+            max_side_supply: caps.max_side_supply,
+            max_user_deposits: caps.max_user_deposits,
+            max_imbalance_bps: caps.max_imbalance_bps,
+            imbalance_floor: caps.imbalance_floor,
+        });
+
+        Ok(())
+    }
+}
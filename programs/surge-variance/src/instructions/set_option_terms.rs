@@ -0,0 +1,65 @@
+use crate::errors::ErrorCode;
+use crate::state::*;
+use crate::events::*;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct SetOptionTerms<'info> {
+    #[account(
+        mut,
+        has_one = authority @ ErrorCode::Unauthorized,
+        constraint = !market.is_paused(PAUSE_UPDATES) @ ErrorCode::UpdatesPaused,
+    )]
+    pub market: Account<'info, Market>,
+
+    pub authority: Signer<'info>,
+}
+
+impl<'info> SetOptionTerms<'info> {
+    /// Turns a market without deposits into a call or put on realized volatility,
+    /// struck at the market strike in vol points. VAR-long tokens become the
+    /// options and VAR-short tokens the writers' claims on the collateral.
+    pub fn set_option_terms(
+        ctx: Context<SetOptionTerms>,
+        option_type: OptionType,
+        payout_per_point: u64,
+        max_payoff: u64,
+        expiry_timestamp: i64,
+    ) -> Result<()> {
+        let market = &mut ctx.accounts.market;
+        let now = Clock::get()?.unix_timestamp;
+        require!(!market.is_expired_at(now), ErrorCode::MarketExpired);
+        require!(
+            market.total_deposits == 0 && !market.auction_open,
+            ErrorCode::InvalidOptionTerms
+        );
+
+        // Series settle linear payoffs and writers post collateral 1:1
+        require!(market.series == Pubkey::default(), ErrorCode::SeriesMarket);
+        require!(
+            market.collateral_asset == Pubkey::default(),
+            ErrorCode::UnsupportedCollateral
+        );
+        require!(market.variance_type == VarianceType::Standard, ErrorCode::InvalidOptionTerms);
+
+        require!(option_type.is_written(), ErrorCode::InvalidOptionTerms);
+        require!(payout_per_point > 0 && max_payoff > 0, ErrorCode::InvalidOptionTerms);
+        require!(expiry_timestamp > now, ErrorCode::InvalidOptionTerms);
+
+        market.option_type = option_type;
+        market.payout_per_point = payout_per_point;
+        market.max_payoff = max_payoff;
+        market.expiry_timestamp = expiry_timestamp;
+
+        emit!(OptionTermsSet {
+            market: market.key(),
+            option_type,
+            strike: market.strike,
+            payout_per_point,
+            max_payoff,
+            expiry_timestamp,
+        });
+
+        Ok(())
+    }
+}
@@ -0,0 +1,36 @@
+use crate::errors::ErrorCode;
+use crate::state::*;
+use crate::events::*;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct SetPauseFlags<'info> {
+    #[account(
+        mut,
+        constraint = signer.key() == market.guardian
+            || signer.key() == market.authority @ ErrorCode::Unauthorized,
+    )]
+    pub market: Account<'info, Market>,
+
+    /// Guardian or authority
+    pub signer: Signer<'info>,
+}
+
+impl<'info> SetPauseFlags<'info> {
+    pub fn set_pause_flags(ctx: Context<SetPauseFlags>, pause_flags: u8) -> Result<()> {
+        require!(pause_flags & !PAUSE_ALL == 0, ErrorCode::InvalidPauseFlags);
+
+        let market = &mut ctx.accounts.market;
+        let old_pause_flags = market.pause_flags;
+        market.pause_flags = pause_flags;
+
+        emit!(PauseFlagsUpdated {
+            market: market.key(),
+            signer: ctx.accounts.signer.key(),
+            old_pause_flags,
+            new_pause_flags: pause_flags,
+        });
+
+        Ok(())
+    }
+}
@@ -0,0 +1,54 @@
+use crate::errors::ErrorCode;
+use crate::state::*;
+use crate::events::*;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct SetSettlementTerms<'info> {
+    #[account(
+        mut,
+        has_one = authority @ ErrorCode::Unauthorized,
+        constraint = !market.is_paused(PAUSE_UPDATES) @ ErrorCode::UpdatesPaused,
+    )]
+    pub market: Account<'info, Market>,
+
+    pub authority: Signer<'info>,
+}
+
+impl<'info> SetSettlementTerms<'info> {
+    /// Sets how long the guardian may override a proposed settlement and the TWAP
+    /// window an override settles on. One-off markets may also set the expiry after
+    /// which anyone can propose it; pass 0 to keep the current expiry.
+    pub fn set_settlement_terms(
+        ctx: Context<SetSettlementTerms>,
+        dispute_window: i64,
+        dispute_twap_window: i64,
+        expiry_timestamp: i64,
+    ) -> Result<()> {
+        let market = &mut ctx.accounts.market;
+        let now = Clock::get()?.unix_timestamp;
+        require!(!market.is_expired_at(now), ErrorCode::MarketExpired);
+        require!(
+            Market::valid_dispute_terms(dispute_window, dispute_twap_window),
+            ErrorCode::InvalidSettlementTerms
+        );
+
+        if expiry_timestamp != 0 {
+            // Series epochs keep the tenor of their series
+            require!(market.series == Pubkey::default(), ErrorCode::SeriesMarket);
+            require!(expiry_timestamp > now, ErrorCode::InvalidSettlementTerms);
+            market.expiry_timestamp = expiry_timestamp;
+        }
+        market.dispute_window = dispute_window;
+        market.dispute_twap_window = dispute_twap_window;
+
+        emit!(SettlementTermsSet {
+            market: market.key(),
+            dispute_window,
+            dispute_twap_window,
+            expiry_timestamp: market.expiry_timestamp,
+        });
+
+        Ok(())
+    }
+}
@@ -0,0 +1,60 @@
+use crate::errors::ErrorCode;
+use crate::state::*;
+use crate::events::*;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct SetVarianceType<'info> {
+    #[account(
+        mut,
+        has_one = authority @ ErrorCode::Unauthorized,
+        has_one = volatility_stats @ ErrorCode::InvalidOracle,
+        constraint = !market.is_paused(PAUSE_UPDATES) @ ErrorCode::UpdatesPaused,
+    )]
+    pub market: Account<'info, Market>,
+
+    pub authority: Signer<'info>,
+
+    /// The volatility stats account from the oracle program
+    /// CHECK: This account is not owned by this program, but we read from it
+    pub volatility_stats: AccountInfo<'info>,
+}
+
+impl<'info> SetVarianceType<'info> {
+    /// Turns a market without deposits into a corridor or up/down variance market.
+    /// Only oracle returns recorded after this call count towards settlement.
+    pub fn set_variance_type(ctx: Context<SetVarianceType>, variance_type: VarianceType) -> Result<()> {
+        let market = &mut ctx.accounts.market;
+        require!(
+            !market.is_expired_at(Clock::get()?.unix_timestamp),
+            ErrorCode::MarketExpired
+        );
+        require!(
+            market.total_deposits == 0 && !market.auction_open,
+            ErrorCode::InvalidVarianceType
+        );
+        require!(market.option_type == OptionType::None, ErrorCode::OptionMarket);
+        if let VarianceType::Corridor { lower, upper } = variance_type {
+            require!(lower < upper, ErrorCode::InvalidVarianceType);
+        }
+
+        let (reference_price, return_count) = read_price_and_return_count(&ctx.accounts.volatility_stats)?;
+        require!(reference_price > 0, ErrorCode::InvalidOracle);
+
+        market.variance_type = variance_type;
+        market.reference_price = reference_price;
+        market.last_return_sequence = return_count;
+        market.first_return_sequence = return_count;
+        market.observed_returns = 0;
+        market.conditional_sum = 0.0;
+
+        emit!(VarianceTypeSet {
+            market: market.key(),
+            variance_type,
+            reference_price,
+            return_sequence: return_count,
+        });
+
+        Ok(())
+    }
+}
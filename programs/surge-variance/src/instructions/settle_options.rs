@@ -0,0 +1,52 @@
+use crate::errors::ErrorCode;
+use crate::state::*;
+use crate::events::*;
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::Mint;
+
+#[derive(Accounts)]
+pub struct SettleOptions<'info> {
+    #[account(
+        mut,
+        has_one = volatility_stats @ ErrorCode::InvalidOracle,
+        has_one = var_long_mint,
+        constraint = !market.is_paused(PAUSE_UPDATES) @ ErrorCode::UpdatesPaused,
+    )]
+    pub market: Account<'info, Market>,
+
+    /// Option mint, every outstanding option shares the payoff
+    pub var_long_mint: InterfaceAccount<'info, Mint>,
+
+    /// The volatility stats account from the oracle program
+    /// CHECK: This account is not owned by this program, but we read from it
+    pub volatility_stats: AccountInfo<'info>,
+}
+
+impl<'info> SettleOptions<'info> {
+    /// Permissionless once the options or binary market expire. Settles on the
+    /// estimate in effect at expiry; holders then exit through claim_settlement.
+    pub fn settle_options(ctx: Context<SettleOptions>) -> Result<()> {
+        let market = &mut ctx.accounts.market;
+        require!(market.option_type != OptionType::None, ErrorCode::InvalidOptionTerms);
+        require!(!market.is_expired, ErrorCode::MarketExpired);
+        let now = Clock::get()?.unix_timestamp;
+        require!(now >= market.expiry_timestamp, ErrorCode::EpochNotEnded);
+
+        let annualized_volatility =
+            read_settlement_volatility(&ctx.accounts.volatility_stats, 0, market.expiry_timestamp)?;
+        market.settle_option(annualized_volatility, ctx.accounts.var_long_mint.supply)?;
+        market.propose_settlement(now);
+
+        emit!(MarketSettled {
+            market: market.key(),
+            realized_variance: market.realized_variance,
+            strike: market.strike,
+            long_payout: market.long_payout,
+            short_payout: market.short_payout,
+            total_deposits: market.total_deposits,
+            dispute_deadline: market.dispute_deadline(),
+        });
+
+        Ok(())
+    }
+}
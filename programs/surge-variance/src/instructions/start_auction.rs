@@ -0,0 +1,90 @@
+use crate::errors::ErrorCode;
+use crate::state::*;
+use crate::events::*;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct StartAuction<'info> {
+    #[account(
+        mut,
+        has_one = authority @ ErrorCode::Unauthorized,
+        constraint = !market.is_paused(PAUSE_UPDATES) @ ErrorCode::UpdatesPaused,
+    )]
+    pub market: Account<'info, Market>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = AUCTION_SPACE,
+        seeds = [b"auction", market.key().as_ref()],
+        bump
+    )]
+    pub auction: Account<'info, Auction>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> StartAuction<'info> {
+    /// Puts a freshly initialized market into its strike auction. Trading stays
+    /// closed until clear_auction sets the strike.
+    pub fn start_auction(
+        ctx: Context<StartAuction>,
+        min_strike: f64,
+        max_strike: f64,
+        bucket_count: u8,
+        end_time: i64,
+    ) -> Result<()> {
+        let market = &mut ctx.accounts.market;
+        require!(!market.is_expired_at(Clock::get()?.unix_timestamp), ErrorCode::MarketExpired);
+        require!(market.total_deposits == 0, ErrorCode::InvalidAuction);
+        require!(market.option_type == OptionType::None, ErrorCode::OptionMarket);
+
+        // Deposits are converted into VAR tokens 1:1
+        require!(
+            market.collateral_asset == Pubkey::default(),
+            ErrorCode::UnsupportedCollateral
+        );
+
+        require!(
+            min_strike.is_finite() && max_strike.is_finite() && min_strike >= 0.0 && max_strike > min_strike,
+            ErrorCode::InvalidAuction
+        );
+        require!(
+            bucket_count >= 2 && bucket_count as usize <= MAX_AUCTION_BUCKETS,
+            ErrorCode::InvalidAuction
+        );
+        require!(end_time > Clock::get()?.unix_timestamp, ErrorCode::InvalidAuction);
+
+        let auction = &mut ctx.accounts.auction;
+        auction.market = market.key();
+        auction.min_strike = min_strike;
+        auction.max_strike = max_strike;
+        auction.bucket_count = bucket_count;
+        auction.end_time = end_time;
+        auction.long_deposits = [0; MAX_AUCTION_BUCKETS];
+        auction.short_deposits = [0; MAX_AUCTION_BUCKETS];
+        auction.is_cleared = false;
+        auction.clearing_bucket = 0;
+        auction.long_notional = 0;
+        auction.short_notional = 0;
+        auction.matched = 0;
+        auction.bump = ctx.bumps.auction;
+
+        market.auction = auction.key();
+        market.auction_open = true;
+
+        emit!(AuctionStarted {
+            market: market.key(),
+            auction: auction.key(),
+            min_strike,
+            max_strike,
+            bucket_count,
+            end_time,
+        });
+
+        Ok(())
+    }
+}
@@ -0,0 +1,118 @@
+use crate::errors::ErrorCode;
+use crate::state::*;
+use crate::events::*;
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked};
+
+#[derive(Accounts)]
+#[instruction(is_long: bool, bucket: u8)]
+pub struct SubmitAuctionBid<'info> {
+    #[account(
+        constraint = !market.is_paused(PAUSE_MINTS) @ ErrorCode::MintsPaused,
+    )]
+    pub market: Account<'info, Market>,
+
+    #[account(
+        mut,
+        seeds = [b"auction", market.key().as_ref()],
+        bump = auction.bump,
+        has_one = market,
+    )]
+    pub auction: Account<'info, Auction>,
+
+    #[account(
+        init_if_needed,
+        payer = user_authority,
+        space = AUCTION_BID_SPACE,
+        seeds = [
+            b"auction_bid",
+            auction.key().as_ref(),
+            user_authority.key().as_ref(),
+            &[is_long as u8],
+            &[bucket],
+        ],
+        bump
+    )]
+    pub bid: Account<'info, AuctionBid>,
+
+    #[account(mut)]
+    pub user_authority: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = user_collateral.mint == market.collateral_mint @ ErrorCode::InvalidCollateralAsset,
+    )]
+    pub user_collateral: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(mut, address = market.collateral_vault)]
+    pub collateral_vault: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(address = market.collateral_mint)]
+    pub collateral_mint: InterfaceAccount<'info, Mint>,
+
+    /// Token program owning the collateral mint
+    pub collateral_token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> SubmitAuctionBid<'info> {
+    /// Deposits `amount` collateral on one side of the auction. A long bid
+    /// accepts any strike up to `bucket`'s, a short bid any strike from it.
+    pub fn submit_auction_bid(ctx: Context<SubmitAuctionBid>, is_long: bool, bucket: u8, amount: u64) -> Result<()> {
+        let auction = &ctx.accounts.auction;
+        require!(
+            !auction.is_cleared && Clock::get()?.unix_timestamp < auction.end_time,
+            ErrorCode::AuctionClosed
+        );
+        require!(bucket < auction.bucket_count, ErrorCode::InvalidAuction);
+        require!(amount > 0, ErrorCode::InvalidAmount);
+
+        token_interface::transfer_checked(
+            CpiContext::new(
+                ctx.accounts.collateral_token_program.to_account_info(),
+                TransferChecked {
+                    from: ctx.accounts.user_collateral.to_account_info(),
+                    mint: ctx.accounts.collateral_mint.to_account_info(),
+                    to: ctx.accounts.collateral_vault.to_account_info(),
+                    authority: ctx.accounts.user_authority.to_account_info(),
+                },
+            ),
+            amount,
+            ctx.accounts.collateral_mint.decimals,
+        )?;
+
+        let bid = &mut ctx.accounts.bid;
+        if bid.owner == Pubkey::default() {
+            bid.auction = ctx.accounts.auction.key();
+            bid.owner = ctx.accounts.user_authority.key();
+            bid.is_long = is_long;
+            bid.bucket = bucket;
+            bid.bump = ctx.bumps.bid;
+        }
+        bid.amount = bid.amount.checked_add(amount).ok_or(ErrorCode::NumberOverflow)?;
+
+        let auction = &mut ctx.accounts.auction;
+        let deposits = if is_long {
+            &mut auction.long_deposits[bucket as usize]
+        } else {
+            &mut auction.short_deposits[bucket as usize]
+        };
+        *deposits = deposits.checked_add(amount).ok_or(ErrorCode::NumberOverflow)?;
+
+        // Claims cannot fail on the caps, the deposits are capped up front
+        let (long_deposits, short_deposits) = auction.deposits()?;
+        ctx.accounts.market.check_bid_caps(long_deposits, short_deposits)?;
+
+        emit!(AuctionBidSubmitted {
+            market: ctx.accounts.market.key(),
+            auction: auction.key(),
+            user: ctx.accounts.user_authority.key(),
+            is_long,
+            bucket,
+            strike: auction.strike(bucket),
+            amount,
+        });
+
+        Ok(())
+    }
+}
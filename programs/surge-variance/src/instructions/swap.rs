@@ -0,0 +1,272 @@
+use crate::errors::ErrorCode;
+use crate::state::*;
+use crate::events::*;
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{self, Burn, Mint, MintTo, TokenAccount, TokenInterface, TransferChecked};
+
+#[derive(Accounts)]
+pub struct Swap<'info> {
+    #[account(
+        mut,
+        constraint = market.is_derived_at(&market.key()) @ ErrorCode::InvalidMarket,
+    )]
+    pub market: Account<'info, Market>,
+
+    #[account(
+        seeds = [b"pool", market.key().as_ref()],
+        bump = pool.bump,
+        has_one = market,
+        has_one = long_vault,
+        has_one = short_vault,
+    )]
+    pub pool: Account<'info, Pool>,
+
+    pub user_authority: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = user_collateral.mint == market.collateral_mint @ ErrorCode::InvalidCollateralAsset,
+    )]
+    pub user_collateral: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(mut, address = market.collateral_vault)]
+    pub collateral_vault: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(address = market.collateral_mint)]
+    pub collateral_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(mut, address = market.var_long_mint)]
+    pub var_long_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(mut, address = market.var_short_mint)]
+    pub var_short_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(mut)]
+    pub long_vault: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub short_vault: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        constraint = user_var_long.mint == market.var_long_mint @ ErrorCode::InvalidAmount,
+    )]
+    pub user_var_long: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        constraint = user_var_short.mint == market.var_short_mint @ ErrorCode::InvalidAmount,
+    )]
+    pub user_var_short: InterfaceAccount<'info, TokenAccount>,
+
+    /// Registry entry pricing the collateral, required once the market has one
+    pub collateral_asset: Option<Account<'info, CollateralAsset>>,
+
+    /// Pyth price update for the collateral asset
+    /// CHECK: Validated through load_pyth_price
+    pub collateral_price_feed: Option<AccountInfo<'info>>,
+
+    /// Token program owning the VAR mints
+    pub token_program: Interface<'info, TokenInterface>,
+
+    /// Token program owning the collateral mint
+    pub collateral_token_program: Interface<'info, TokenInterface>,
+}
+
+impl<'info> Swap<'info> {
+    /// Buys (`is_buy`) or sells VAR-long (`is_long`) or VAR-short against collateral.
+    /// `amount_in` is collateral for buys and VAR tokens for sells.
+    pub fn swap(ctx: Context<Swap>, is_long: bool, is_buy: bool, amount_in: u64, min_amount_out: u64) -> Result<()> {
+        let market = &ctx.accounts.market;
+        require!(!market.is_expired_at(Clock::get()?.unix_timestamp), ErrorCode::MarketExpired);
+        require!(amount_in > 0, ErrorCode::InvalidAmount);
+        if is_buy {
+            require!(!market.is_paused(PAUSE_MINTS), ErrorCode::MintsPaused);
+        } else {
+            require!(!market.is_paused(PAUSE_REDEEMS), ErrorCode::RedeemsPaused);
+        }
+
+        let accounts = &ctx.accounts;
+        let (side_mint, side_vault, other_mint, other_vault, user_side) = if is_long {
+            (&accounts.var_long_mint, &accounts.long_vault, &accounts.var_short_mint, &accounts.short_vault, &accounts.user_var_long)
+        } else {
+            (&accounts.var_short_mint, &accounts.short_vault, &accounts.var_long_mint, &accounts.long_vault, &accounts.user_var_short)
+        };
+        let side_reserve = side_vault.amount;
+        let other_reserve = other_vault.amount;
+
+        let seeds = market.seeds();
+        let market_seeds = seeds.as_slices();
+        let market_signer = &[&market_seeds[..]];
+
+        let market_key = market.key();
+        let pool_seeds = &[
+            b"pool".as_ref(),
+            market_key.as_ref(),
+            &[accounts.pool.bump]
+        ];
+        let pool_signer = &[&pool_seeds[..]];
+
+        let (amount_out, fee, deposits_delta) = if is_buy {
+            // Pairs are minted at the haircut USD value of the collateral, like mint_tokens
+            let pairs = pairs_for_value(collateral_value(
+                market,
+                &accounts.collateral_asset,
+                &accounts.collateral_price_feed,
+                amount_in,
+            )?);
+            require!(pairs > 0, ErrorCode::InvalidCollateralAsset);
+            market.check_mint_caps(
+                pairs,
+                pairs,
+                amount_in,
+                accounts.var_long_mint.supply,
+                accounts.var_short_mint.supply,
+            )?;
+
+            // The fee is charged on the way in and stays in the reserves as extra pairs
+            let fee = accounts.pool.fee(pairs)?;
+            let amount_out = buy_amount_out(
+                side_reserve,
+                other_reserve,
+                pairs.checked_sub(fee).ok_or(ErrorCode::NumberOverflow)?,
+            )?;
+            require!(amount_out >= min_amount_out, ErrorCode::SlippageExceeded);
+
+            // Split the collateral into pairs held by the pool
+            token_interface::transfer_checked(
+                CpiContext::new(
+                    accounts.collateral_token_program.to_account_info(),
+                    TransferChecked {
+                        from: accounts.user_collateral.to_account_info(),
+                        mint: accounts.collateral_mint.to_account_info(),
+                        to: accounts.collateral_vault.to_account_info(),
+                        authority: accounts.user_authority.to_account_info(),
+                    },
+                ),
+                amount_in,
+                accounts.collateral_mint.decimals,
+            )?;
+
+            for (mint, to) in [(side_mint, side_vault), (other_mint, other_vault)] {
+                token_interface::mint_to(
+                    CpiContext::new_with_signer(
+                        accounts.token_program.to_account_info(),
+                        MintTo {
+                            mint: mint.to_account_info(),
+                            to: to.to_account_info(),
+                            authority: market.to_account_info(),
+                        },
+                        market_signer,
+                    ),
+                    pairs,
+                )?;
+            }
+
+            // Pay out the bought side
+            token_interface::transfer_checked(
+                CpiContext::new_with_signer(
+                    accounts.token_program.to_account_info(),
+                    TransferChecked {
+                        from: side_vault.to_account_info(),
+                        mint: side_mint.to_account_info(),
+                        to: user_side.to_account_info(),
+                        authority: accounts.pool.to_account_info(),
+                    },
+                    pool_signer,
+                ),
+                amount_out,
+                side_mint.decimals,
+            )?;
+
+            (amount_out, fee, amount_in as i128)
+        } else {
+            // Merge pairs back into collateral, the fee stays in the reserves as tokens
+            let pairs = sell_amount_out(side_reserve, other_reserve, amount_in)?;
+            let fee = accounts.pool.fee(pairs)?;
+            let merged = pairs.checked_sub(fee).ok_or(ErrorCode::NumberOverflow)?;
+            let amount_out = collateral_for_value(
+                market,
+                &accounts.collateral_asset,
+                &accounts.collateral_price_feed,
+                pair_value(merged)?,
+            )?;
+            require!(amount_out > 0, ErrorCode::InsufficientLiquidity);
+            require!(amount_out >= min_amount_out, ErrorCode::SlippageExceeded);
+
+            token_interface::transfer_checked(
+                CpiContext::new(
+                    accounts.token_program.to_account_info(),
+                    TransferChecked {
+                        from: user_side.to_account_info(),
+                        mint: side_mint.to_account_info(),
+                        to: side_vault.to_account_info(),
+                        authority: accounts.user_authority.to_account_info(),
+                    },
+                ),
+                amount_in,
+                side_mint.decimals,
+            )?;
+
+            for (mint, from) in [(side_mint, side_vault), (other_mint, other_vault)] {
+                token_interface::burn(
+                    CpiContext::new_with_signer(
+                        accounts.token_program.to_account_info(),
+                        Burn {
+                            mint: mint.to_account_info(),
+                            from: from.to_account_info(),
+                            authority: accounts.pool.to_account_info(),
+                        },
+                        pool_signer,
+                    ),
+                    merged,
+                )?;
+            }
+
+            token_interface::transfer_checked(
+                CpiContext::new_with_signer(
+                    accounts.collateral_token_program.to_account_info(),
+                    TransferChecked {
+                        from: accounts.collateral_vault.to_account_info(),
+                        mint: accounts.collateral_mint.to_account_info(),
+                        to: accounts.user_collateral.to_account_info(),
+                        authority: market.to_account_info(),
+                    },
+                    market_signer,
+                ),
+                amount_out,
+                accounts.collateral_mint.decimals,
+            )?;
+
+            (amount_out, fee, -(amount_out as i128))
+        };
+
+        let total_deposits = (market.total_deposits as i128)
+            .checked_add(deposits_delta)
+            .ok_or(ErrorCode::NumberOverflow)?;
+        ctx.accounts.market.total_deposits =
+            u64::try_from(total_deposits).map_err(|_| ErrorCode::NumberOverflow)?;
+
+        // Implied price of VAR-long after the trade, in collateral per token
+        ctx.accounts.long_vault.reload()?;
+        ctx.accounts.short_vault.reload()?;
+        let long_reserve = ctx.accounts.long_vault.amount;
+        let short_reserve = ctx.accounts.short_vault.amount;
+        let long_price = short_reserve as f64 / (long_reserve as f64 + short_reserve as f64);
+
+        emit!(Swapped {
+            market: market_key,
+            user: ctx.accounts.user_authority.key(),
+            is_long,
+            is_buy,
+            amount_in,
+            amount_out,
+            fee,
+            long_reserve,
+            short_reserve,
+            long_price,
+        });
+
+        Ok(())
+    }
+}
@@ -0,0 +1,58 @@
+use crate::errors::ErrorCode;
+use crate::state::*;
+use crate::events::*;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct UpdateCollateralAsset<'info> {
+    #[account(
+        mut,
+        has_one = authority @ ErrorCode::Unauthorized,
+    )]
+    pub collateral_asset: Account<'info, CollateralAsset>,
+
+    pub authority: Signer<'info>,
+
+    /// Pyth price update account, omit for a stablecoin pegged at $1
+    /// CHECK: Validated through load_pyth_price
+    pub price_feed: Option<AccountInfo<'info>>,
+}
+
+impl<'info> UpdateCollateralAsset<'info> {
+    pub fn update_collateral_asset(
+        ctx: Context<UpdateCollateralAsset>,
+        haircut_bps: u16,
+        feed_id: [u8; 32],
+        max_price_age: i64,
+    ) -> Result<()> {
+        require!(haircut_bps < 10000, ErrorCode::InvalidCollateralAsset);
+        require!(max_price_age > 0, ErrorCode::InvalidCollateralAsset);
+
+        let price_feed = ctx
+            .accounts
+            .price_feed
+            .as_ref()
+            .map(|price_feed| price_feed.key())
+            .unwrap_or_default();
+
+        let collateral_asset = &mut ctx.accounts.collateral_asset;
+        collateral_asset.haircut_bps = haircut_bps;
+        collateral_asset.price_feed = price_feed;
+        collateral_asset.feed_id = feed_id;
+        collateral_asset.max_price_age = max_price_age;
+
+        // Make sure the new feed can be read
+        collateral_asset.usd_value(0, ctx.accounts.price_feed.as_ref())?;
+
+        emit!(CollateralAssetUpdated {
+            collateral_asset: collateral_asset.key(),
+            authority: collateral_asset.authority,
+            mint: collateral_asset.mint,
+            haircut_bps,
+            price_feed,
+            max_price_age,
+        });
+
+        Ok(())
+    }
+}
@@ -0,0 +1,160 @@
+use crate::errors::ErrorCode;
+use crate::state::*;
+use crate::events::*;
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{self, Mint, MintTo, TokenAccount, TokenInterface, TransferChecked};
+
+#[derive(Accounts)]
+pub struct WriteOptions<'info> {
+    #[account(
+        mut,
+        constraint = market.is_derived_at(&market.key()) @ ErrorCode::InvalidMarket,
+        constraint = !market.is_paused(PAUSE_MINTS) @ ErrorCode::MintsPaused,
+    )]
+    pub market: Account<'info, Market>,
+
+    #[account(
+        init_if_needed,
+        payer = writer,
+        space = 8 + 32 + 32 + 32 + 8 + 1 + 1,
+        seeds = [b"option_offer", market.key().as_ref(), writer.key().as_ref()],
+        bump
+    )]
+    pub offer: Account<'info, OptionOffer>,
+
+    /// Holds the offer's unsold option tokens
+    #[account(
+        init_if_needed,
+        payer = writer,
+        seeds = [b"option_escrow", offer.key().as_ref()],
+        bump,
+        token::mint = var_long_mint,
+        token::authority = offer,
+        token::token_program = token_program,
+    )]
+    pub offer_escrow: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub writer: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = writer_collateral.mint == market.collateral_mint @ ErrorCode::InvalidCollateralAsset,
+    )]
+    pub writer_collateral: InterfaceAccount<'info, TokenAccount>,
+
+    /// Receives the writer tokens
+    #[account(
+        mut,
+        constraint = writer_var_short.mint == market.var_short_mint @ ErrorCode::InvalidAmount,
+    )]
+    pub writer_var_short: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(mut, address = market.collateral_vault)]
+    pub collateral_vault: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(address = market.collateral_mint)]
+    pub collateral_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(mut, address = market.var_long_mint)]
+    pub var_long_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(mut, address = market.var_short_mint)]
+    pub var_short_mint: InterfaceAccount<'info, Mint>,
+
+    /// Token program owning the VAR mints
+    pub token_program: Interface<'info, TokenInterface>,
+
+    /// Token program owning the collateral mint
+    pub collateral_token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> WriteOptions<'info> {
+    /// Posts max_payoff collateral per option, mints `amount` writer tokens to the
+    /// writer and lists `amount` options at `premium_per_option`. The premium
+    /// applies to every option still in the writer's offer.
+    pub fn write_options(ctx: Context<WriteOptions>, amount: u64, premium_per_option: u64) -> Result<()> {
+        let market = &ctx.accounts.market;
+        require!(market.option_type.is_written(), ErrorCode::InvalidOptionTerms);
+        require!(
+            !market.is_expired_at(Clock::get()?.unix_timestamp),
+            ErrorCode::MarketExpired
+        );
+        require!(amount > 0, ErrorCode::InvalidAmount);
+
+        let collateral = amount
+            .checked_mul(market.max_payoff)
+            .ok_or(ErrorCode::NumberOverflow)?;
+        market.check_mint_caps(
+            amount,
+            amount,
+            collateral,
+            ctx.accounts.var_long_mint.supply,
+            ctx.accounts.var_short_mint.supply,
+        )?;
+
+        token_interface::transfer_checked(
+            CpiContext::new(
+                ctx.accounts.collateral_token_program.to_account_info(),
+                TransferChecked {
+                    from: ctx.accounts.writer_collateral.to_account_info(),
+                    mint: ctx.accounts.collateral_mint.to_account_info(),
+                    to: ctx.accounts.collateral_vault.to_account_info(),
+                    authority: ctx.accounts.writer.to_account_info(),
+                },
+            ),
+            collateral,
+            ctx.accounts.collateral_mint.decimals,
+        )?;
+
+        let market_seeds = market.seeds();
+        let seeds = market_seeds.as_slices();
+        let signer = &[&seeds[..]];
+
+        for (mint, to) in [
+            (&ctx.accounts.var_long_mint, ctx.accounts.offer_escrow.to_account_info()),
+            (&ctx.accounts.var_short_mint, ctx.accounts.writer_var_short.to_account_info()),
+        ] {
+            token_interface::mint_to(
+                CpiContext::new_with_signer(
+                    ctx.accounts.token_program.to_account_info(),
+                    MintTo {
+                        mint: mint.to_account_info(),
+                        to,
+                        authority: market.to_account_info(),
+                    },
+                    signer,
+                ),
+                amount,
+            )?;
+        }
+
+        let offer = &mut ctx.accounts.offer;
+        if offer.writer == Pubkey::default() {
+            offer.market = market.key();
+            offer.writer = ctx.accounts.writer.key();
+            offer.escrow = ctx.accounts.offer_escrow.key();
+            offer.bump = ctx.bumps.offer;
+            offer.escrow_bump = ctx.bumps.offer_escrow;
+        }
+        offer.premium_per_option = premium_per_option;
+
+        let market = &mut ctx.accounts.market;
+        market.total_deposits = market
+            .total_deposits
+            .checked_add(collateral)
+            .ok_or(ErrorCode::NumberOverflow)?;
+
+        emit!(OptionsWritten {
+            market: market.key(),
+            writer: ctx.accounts.writer.key(),
+            offer: offer.key(),
+            amount,
+            collateral,
+            premium_per_option,
+        });
+
+        Ok(())
+    }
+}
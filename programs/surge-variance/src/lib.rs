@@ -21,15 +21,201 @@ pub mod surge_variance {
         strike: f64,
         timestamp: i64,
         bumps: MarketBumps,
+        long_metadata: VarTokenMetadata,
+        short_metadata: VarTokenMetadata,
     ) -> Result<()> {
-        InitializeMarket::initialize_market(ctx, epoch, strike, timestamp, bumps)
+        InitializeMarket::initialize_market(ctx, epoch, strike, timestamp, bumps, long_metadata, short_metadata)
     }
 
     pub fn mint_tokens(ctx: Context<MintTokens>, amount: u64, is_long: bool, epoch: u64, timestamp: i64, bumps: MarketBumps) -> Result<()> {
         MintTokens::mint_tokens(ctx, amount, is_long, epoch, timestamp, bumps)
     }
 
-    pub fn redeem(ctx: Context<Redeem>, epoch: u64, timestamp: i64, bumps: MarketBumps) -> Result<()> {
-        Redeem::redeem(ctx, epoch, timestamp, bumps)
+    pub fn set_guardian(ctx: Context<SetGuardian>, new_guardian: Pubkey) -> Result<()> {
+        SetGuardian::set_guardian(ctx, new_guardian)
+    }
+
+    pub fn set_pause_flags(ctx: Context<SetPauseFlags>, pause_flags: u8) -> Result<()> {
+        SetPauseFlags::set_pause_flags(ctx, pause_flags)
+    }
+
+    pub fn add_collateral_asset(
+        ctx: Context<AddCollateralAsset>,
+        haircut_bps: u16,
+        feed_id: [u8; 32],
+        max_price_age: i64,
+    ) -> Result<()> {
+        AddCollateralAsset::add_collateral_asset(ctx, haircut_bps, feed_id, max_price_age)
+    }
+
+    pub fn update_collateral_asset(
+        ctx: Context<UpdateCollateralAsset>,
+        haircut_bps: u16,
+        feed_id: [u8; 32],
+        max_price_age: i64,
+    ) -> Result<()> {
+        UpdateCollateralAsset::update_collateral_asset(ctx, haircut_bps, feed_id, max_price_age)
+    }
+
+    pub fn initialize_pool(ctx: Context<InitializePool>, fee_bps: u16, seed_amount: u64) -> Result<()> {
+        InitializePool::initialize_pool(ctx, fee_bps, seed_amount)
+    }
+
+    pub fn add_liquidity(ctx: Context<AddLiquidity>, amount: u64, min_lp_out: u64) -> Result<()> {
+        AddLiquidity::add_liquidity(ctx, amount, min_lp_out)
+    }
+
+    pub fn remove_liquidity(
+        ctx: Context<RemoveLiquidity>,
+        lp_amount: u64,
+        min_long_out: u64,
+        min_short_out: u64,
+    ) -> Result<()> {
+        RemoveLiquidity::remove_liquidity(ctx, lp_amount, min_long_out, min_short_out)
+    }
+
+    pub fn swap(ctx: Context<Swap>, is_long: bool, is_buy: bool, amount_in: u64, min_amount_out: u64) -> Result<()> {
+        Swap::swap(ctx, is_long, is_buy, amount_in, min_amount_out)
+    }
+
+    pub fn start_auction(
+        ctx: Context<StartAuction>,
+        min_strike: f64,
+        max_strike: f64,
+        bucket_count: u8,
+        end_time: i64,
+    ) -> Result<()> {
+        StartAuction::start_auction(ctx, min_strike, max_strike, bucket_count, end_time)
+    }
+
+    pub fn submit_auction_bid(ctx: Context<SubmitAuctionBid>, is_long: bool, bucket: u8, amount: u64) -> Result<()> {
+        SubmitAuctionBid::submit_auction_bid(ctx, is_long, bucket, amount)
+    }
+
+    pub fn clear_auction(ctx: Context<ClearAuction>) -> Result<()> {
+        ClearAuction::clear_auction(ctx)
+    }
+
+    pub fn claim_auction_bid(ctx: Context<ClaimAuctionBid>) -> Result<()> {
+        ClaimAuctionBid::claim_auction_bid(ctx)
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn initialize_series(
+        ctx: Context<InitializeSeries>,
+        series_id: u64,
+        tenor: i64,
+        strike_rule: StrikeRule,
+        base_strike: f64,
+        strike_spread: f64,
+        variance_cap: f64,
+        start_timestamp: i64,
+        dispute_window: i64,
+        dispute_twap_window: i64,
+    ) -> Result<()> {
+        InitializeSeries::initialize_series(
+            ctx,
+            series_id,
+            tenor,
+            strike_rule,
+            base_strike,
+            strike_spread,
+            variance_cap,
+            start_timestamp,
+            dispute_window,
+            dispute_twap_window,
+        )
+    }
+
+    pub fn roll_series(ctx: Context<RollSeries>) -> Result<()> {
+        RollSeries::roll_series(ctx)
+    }
+
+    pub fn claim_settlement(ctx: Context<ClaimSettlement>) -> Result<()> {
+        ClaimSettlement::claim_settlement(ctx)
+    }
+
+    pub fn roll(ctx: Context<Roll>, is_long: bool, min_amount_out: u64) -> Result<()> {
+        Roll::roll(ctx, is_long, min_amount_out)
+    }
+
+    pub fn set_variance_type(ctx: Context<SetVarianceType>, variance_type: VarianceType) -> Result<()> {
+        SetVarianceType::set_variance_type(ctx, variance_type)
+    }
+
+    pub fn accrue_variance(ctx: Context<AccrueVariance>) -> Result<()> {
+        AccrueVariance::accrue_variance(ctx)
+    }
+
+    pub fn set_option_terms(
+        ctx: Context<SetOptionTerms>,
+        option_type: OptionType,
+        payout_per_point: u64,
+        max_payoff: u64,
+        expiry_timestamp: i64,
+    ) -> Result<()> {
+        SetOptionTerms::set_option_terms(ctx, option_type, payout_per_point, max_payoff, expiry_timestamp)
+    }
+
+    pub fn write_options(ctx: Context<WriteOptions>, amount: u64, premium_per_option: u64) -> Result<()> {
+        WriteOptions::write_options(ctx, amount, premium_per_option)
+    }
+
+    pub fn buy_options(ctx: Context<BuyOptions>, amount: u64, max_premium: u64) -> Result<()> {
+        BuyOptions::buy_options(ctx, amount, max_premium)
+    }
+
+    pub fn cancel_option_offer(ctx: Context<CancelOptionOffer>) -> Result<()> {
+        CancelOptionOffer::cancel_option_offer(ctx)
+    }
+
+    pub fn settle_options(ctx: Context<SettleOptions>) -> Result<()> {
+        SettleOptions::settle_options(ctx)
+    }
+
+    pub fn set_binary_terms(ctx: Context<SetBinaryTerms>, expiry_timestamp: i64) -> Result<()> {
+        SetBinaryTerms::set_binary_terms(ctx, expiry_timestamp)
+    }
+
+    pub fn mint_binary_pair(ctx: Context<MintBinaryPair>, amount: u64) -> Result<()> {
+        MintBinaryPair::mint_binary_pair(ctx, amount)
+    }
+
+    pub fn redeem_binary_pair(ctx: Context<RedeemBinaryPair>, amount: u64) -> Result<()> {
+        RedeemBinaryPair::redeem_binary_pair(ctx, amount)
+    }
+
+    pub fn set_settlement_terms(
+        ctx: Context<SetSettlementTerms>,
+        dispute_window: i64,
+        dispute_twap_window: i64,
+        expiry_timestamp: i64,
+    ) -> Result<()> {
+        SetSettlementTerms::set_settlement_terms(
+            ctx,
+            dispute_window,
+            dispute_twap_window,
+            expiry_timestamp,
+        )
+    }
+
+    pub fn propose_settlement(ctx: Context<ProposeSettlement>) -> Result<()> {
+        ProposeSettlement::propose_settlement(ctx)
+    }
+
+    pub fn dispute_settlement(ctx: Context<DisputeSettlement>) -> Result<()> {
+        DisputeSettlement::dispute_settlement(ctx)
+    }
+
+    pub fn finalize_settlement(ctx: Context<FinalizeSettlement>) -> Result<()> {
+        FinalizeSettlement::finalize_settlement(ctx)
+    }
+
+    pub fn set_market_caps(ctx: Context<SetMarketCaps>, caps: MarketCaps) -> Result<()> {
+        SetMarketCaps::set_market_caps(ctx, caps)
+    }
+
+    pub fn migrate_market(ctx: Context<MigrateMarket>) -> Result<()> {
+        MigrateMarket::migrate_market(ctx)
     }
 }
@@ -0,0 +1,253 @@
+use crate::errors::ErrorCode;
+use anchor_lang::prelude::*;
+
+pub const MAX_AUCTION_BUCKETS: usize = 16;
+
+/// Account size of an auction, discriminator included
+pub const AUCTION_SPACE: usize = 8 // discriminator
+    + 32                           // market
+    + 8                            // min_strike
+    + 8                            // max_strike
+    + 1                            // bucket_count
+    + 8                            // end_time
+    + 8 * MAX_AUCTION_BUCKETS      // long_deposits
+    + 8 * MAX_AUCTION_BUCKETS      // short_deposits
+    + 1                            // is_cleared
+    + 1                            // clearing_bucket
+    + 8                            // long_notional
+    + 8                            // short_notional
+    + 8                            // matched
+    + 1;                           // bump
+
+/// Strike discovery run before a market opens. Bids are placed on a fixed grid
+/// of strikes between min_strike and max_strike: a long bid accepts any strike
+/// at or below its bucket, a short bid any strike at or above it. Clearing scans
+/// the grid from the top down and opens the market at the strike that matches
+/// the most long and short notional.
+#[account]
+pub struct Auction {
+    pub market: Pubkey,
+    pub min_strike: f64,
+    pub max_strike: f64,
+    pub bucket_count: u8,
+    pub end_time: i64,        // Bids are accepted until this timestamp
+    pub long_deposits: [u64; MAX_AUCTION_BUCKETS],
+    pub short_deposits: [u64; MAX_AUCTION_BUCKETS],
+    pub is_cleared: bool,
+    pub clearing_bucket: u8,
+    pub long_notional: u64,   // Long deposits eligible at the clearing strike
+    pub short_notional: u64,  // Short deposits eligible at the clearing strike
+    pub matched: u64,         // Notional filled on each side
+    pub bump: u8,
+}
+
+/// Account size of an auction bid, discriminator included
+pub const AUCTION_BID_SPACE: usize = 8 // discriminator
+    + 32                               // auction
+    + 32                               // owner
+    + 1                                // is_long
+    + 1                                // bucket
+    + 8                                // amount
+    + 1;                               // bump
+
+/// A user's deposit on one side of one auction bucket
+#[account]
+pub struct AuctionBid {
+    pub auction: Pubkey,
+    pub owner: Pubkey,
+    pub is_long: bool,
+    pub bucket: u8,
+    pub amount: u64,
+    pub bump: u8,
+}
+
+impl Auction {
+    pub fn strike(&self, bucket: u8) -> f64 {
+        if self.bucket_count <= 1 {
+            return self.min_strike;
+        }
+        self.min_strike
+            + (self.max_strike - self.min_strike) * bucket as f64 / (self.bucket_count - 1) as f64
+    }
+
+    /// Collateral deposited on each side across all buckets
+    pub fn deposits(&self) -> Result<(u64, u64)> {
+        let count = self.bucket_count as usize;
+        let long = self.long_deposits[..count]
+            .iter()
+            .try_fold(0u64, |sum, amount| sum.checked_add(*amount))
+            .ok_or(ErrorCode::NumberOverflow)?;
+        let short = self.short_deposits[..count]
+            .iter()
+            .try_fold(0u64, |sum, amount| sum.checked_add(*amount))
+            .ok_or(ErrorCode::NumberOverflow)?;
+        Ok((long, short))
+    }
+
+    /// Long and short notional willing to trade at `bucket`'s strike
+    pub fn notionals(&self, bucket: u8) -> Result<(u64, u64)> {
+        let bucket = bucket as usize;
+        let count = self.bucket_count as usize;
+        let long = self.long_deposits[bucket..count]
+            .iter()
+            .try_fold(0u64, |sum, amount| sum.checked_add(*amount))
+            .ok_or(ErrorCode::NumberOverflow)?;
+        let short = self.short_deposits[..=bucket]
+            .iter()
+            .try_fold(0u64, |sum, amount| sum.checked_add(*amount))
+            .ok_or(ErrorCode::NumberOverflow)?;
+        Ok((long, short))
+    }
+
+    /// Bucket maximizing matched notional, ties going to the smaller imbalance.
+    /// Without any crossing bids this is the most balanced bucket.
+    pub fn clearing_bucket(&self) -> Result<u8> {
+        let mut best = (self.bucket_count - 1) / 2;
+        let mut best_matched = 0u64;
+        let mut best_imbalance = u64::MAX;
+        for bucket in (0..self.bucket_count).rev() {
+            let (long, short) = self.notionals(bucket)?;
+            let matched = long.min(short);
+            let imbalance = long.abs_diff(short);
+            if (long > 0 || short > 0)
+                && (matched > best_matched || (matched == best_matched && imbalance < best_imbalance))
+            {
+                best = bucket;
+                best_matched = matched;
+                best_imbalance = imbalance;
+            }
+        }
+        Ok(best)
+    }
+}
+
+impl AuctionBid {
+    /// Collateral of this bid converted into VAR tokens once the auction cleared
+    pub fn filled_amount(&self, auction: &Auction) -> Result<u64> {
+        let (in_the_money, notional) = if self.is_long {
+            (self.bucket >= auction.clearing_bucket, auction.long_notional)
+        } else {
+            (self.bucket <= auction.clearing_bucket, auction.short_notional)
+        };
+        if !in_the_money || notional == 0 {
+            return Ok(0);
+        }
+
+        let filled = (self.amount as u128)
+            .checked_mul(auction.matched as u128)
+            .ok_or(ErrorCode::NumberOverflow)?
+            / notional as u128;
+        Ok(filled as u64)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn auction(long_deposits: &[u64], short_deposits: &[u64]) -> Auction {
+        let mut auction = Auction {
+            market: Pubkey::default(),
+            min_strike: 10.0,
+            max_strike: 50.0,
+            bucket_count: long_deposits.len() as u8,
+            end_time: 0,
+            long_deposits: [0; MAX_AUCTION_BUCKETS],
+            short_deposits: [0; MAX_AUCTION_BUCKETS],
+            is_cleared: false,
+            clearing_bucket: 0,
+            long_notional: 0,
+            short_notional: 0,
+            matched: 0,
+            bump: 0,
+        };
+        auction.long_deposits[..long_deposits.len()].copy_from_slice(long_deposits);
+        auction.short_deposits[..short_deposits.len()].copy_from_slice(short_deposits);
+        auction
+    }
+
+    #[test]
+    fn auction_space_matches_the_layout() {
+        assert_eq!(8 + auction(&[], &[]).try_to_vec().unwrap().len(), AUCTION_SPACE);
+    }
+
+    fn clear(auction: &mut Auction) {
+        let bucket = auction.clearing_bucket().unwrap();
+        let (long, short) = auction.notionals(bucket).unwrap();
+        auction.is_cleared = true;
+        auction.clearing_bucket = bucket;
+        auction.long_notional = long;
+        auction.short_notional = short;
+        auction.matched = long.min(short);
+    }
+
+    fn bid(is_long: bool, bucket: u8, amount: u64) -> AuctionBid {
+        AuctionBid {
+            auction: Pubkey::default(),
+            owner: Pubkey::default(),
+            is_long,
+            bucket,
+            amount,
+            bump: 0,
+        }
+    }
+
+    #[test]
+    fn strikes_span_the_grid() {
+        assert_eq!(auction(&[0], &[0]).strike(0), 10.0);
+
+        let auction = auction(&[0; 5], &[0; 5]);
+        assert_eq!(auction.strike(0), 10.0);
+        assert_eq!(auction.strike(2), 30.0);
+        assert_eq!(auction.strike(4), 50.0);
+    }
+
+    #[test]
+    fn longs_accept_lower_strikes_and_shorts_higher() {
+        let auction = auction(&[0, 0, 100, 0, 0], &[0, 0, 40, 0, 0]);
+        assert_eq!(auction.notionals(1).unwrap(), (100, 0));
+        assert_eq!(auction.notionals(2).unwrap(), (100, 40));
+        assert_eq!(auction.notionals(3).unwrap(), (0, 40));
+    }
+
+    #[test]
+    fn clears_where_the_most_notional_matches() {
+        // Longs up to bucket 3, shorts from bucket 1, both cross on buckets 1 to 3
+        let auction = auction(&[0, 0, 0, 100, 0], &[0, 60, 30, 0, 0]);
+        // Buckets 2 and 3 both match 90 with the same imbalance, the scan keeps the higher strike
+        assert_eq!(auction.notionals(2).unwrap(), (100, 90));
+        assert_eq!(auction.notionals(3).unwrap(), (100, 90));
+        assert_eq!(auction.notionals(1).unwrap(), (100, 60));
+        assert_eq!(auction.clearing_bucket().unwrap(), 3);
+    }
+
+    #[test]
+    fn ties_go_to_the_smaller_imbalance() {
+        // Buckets 1 and 2 both match 50, bucket 2 with 40 short left over
+        let auction = auction(&[0, 0, 50, 0], &[50, 0, 40, 0]);
+        assert_eq!(auction.notionals(2).unwrap(), (50, 90));
+        assert_eq!(auction.notionals(1).unwrap(), (50, 50));
+        assert_eq!(auction.clearing_bucket().unwrap(), 1);
+    }
+
+    #[test]
+    fn clears_mid_grid_without_bids() {
+        assert_eq!(auction(&[0; 5], &[0; 5]).clearing_bucket().unwrap(), 2);
+    }
+
+    #[test]
+    fn fills_are_pro_rata_on_the_larger_side() {
+        let mut auction = auction(&[0, 0, 150, 50, 0], &[0, 100, 0, 0, 0]);
+        clear(&mut auction);
+        assert_eq!(auction.clearing_bucket, 2);
+        assert_eq!((auction.long_notional, auction.short_notional), (200, 100));
+
+        // The long side is larger and is scaled down
+        assert_eq!(bid(true, 2, 150).filled_amount(&auction).unwrap(), 75);
+        assert_eq!(bid(true, 3, 50).filled_amount(&auction).unwrap(), 25);
+        assert_eq!(bid(true, 1, 50).filled_amount(&auction).unwrap(), 0);
+        // Only short bids at or below the clearing strike fill
+        assert_eq!(bid(false, 1, 100).filled_amount(&auction).unwrap(), 100);
+        assert_eq!(bid(false, 3, 100).filled_amount(&auction).unwrap(), 0);
+    }
+}
@@ -0,0 +1,194 @@
+use crate::errors::ErrorCode;
+use crate::state::Market;
+use anchor_lang::prelude::*;
+
+/// Pyth receiver program owning PriceUpdateV2 accounts
+pub const PYTH_RECEIVER_PROGRAM_ID: Pubkey = pubkey!("rec5EKMGg6MxZYaMdyBfgwp4d5rB9T1VQH5pJv5LtFJ");
+
+/// Prices and USD amounts are expressed with 6 decimals
+pub const USD_DECIMALS: u32 = 6;
+
+#[account]
+pub struct CollateralAsset {
+    pub authority: Pubkey,    // Authority maintaining this registry entry
+    pub mint: Pubkey,         // Collateral mint
+    pub decimals: u8,         // Decimals of the collateral mint
+    pub haircut_bps: u16,     // Discount applied when valuing deposits of this asset
+    pub price_feed: Pubkey,   // Pyth price update account (default for a $1 peg)
+    pub feed_id: [u8; 32],    // Pyth feed id expected in the price update
+    pub max_price_age: i64,   // Max age in seconds of the price used for conversion
+    pub bump: u8,
+}
+
+impl CollateralAsset {
+    /// USD price of one whole collateral token, with 6 decimals
+    pub fn price(&self, price_feed: Option<&AccountInfo>) -> Result<u64> {
+        if self.price_feed == Pubkey::default() {
+            return Ok(10u64.pow(USD_DECIMALS));
+        }
+
+        let price_feed = price_feed
+            .filter(|price_feed| price_feed.key() == self.price_feed)
+            .ok_or(ErrorCode::InvalidCollateralPrice)?;
+        load_pyth_price(price_feed, &self.feed_id, self.max_price_age)
+    }
+
+    /// USD value of `amount` base units after the haircut, with 6 decimals
+    pub fn usd_value(&self, amount: u64, price_feed: Option<&AccountInfo>) -> Result<u64> {
+        let price = self.price(price_feed)?;
+
+        let value = (amount as u128)
+            .checked_mul(price as u128)
+            .and_then(|value| value.checked_mul(10000 - self.haircut_bps as u128))
+            .ok_or(ErrorCode::NumberOverflow)?
+            / 10000
+            / 10u128.pow(self.decimals as u32);
+        u64::try_from(value).map_err(|_| ErrorCode::NumberOverflow.into())
+    }
+
+    /// Base units paid out for `usd` with 6 decimals, rounded down and without the haircut
+    pub fn collateral_amount(&self, usd: u64, price_feed: Option<&AccountInfo>) -> Result<u64> {
+        let price = self.price(price_feed)?;
+
+        let amount = (usd as u128)
+            .checked_mul(10u128.pow(self.decimals as u32))
+            .ok_or(ErrorCode::NumberOverflow)?
+            / price as u128;
+        u64::try_from(amount).map_err(|_| ErrorCode::NumberOverflow.into())
+    }
+}
+
+/// Registry entry of a market priced in a non-USDC collateral, None for USDC markets
+/// which mint VAR tokens 1:1 against collateral
+fn market_collateral_asset<'a, 'info>(
+    market: &Market,
+    collateral_asset: &'a Option<Account<'info, CollateralAsset>>,
+) -> Result<Option<&'a Account<'info, CollateralAsset>>> {
+    if market.collateral_asset == Pubkey::default() {
+        return Ok(None);
+    }
+
+    collateral_asset
+        .as_ref()
+        .filter(|collateral_asset| collateral_asset.key() == market.collateral_asset)
+        .map(Some)
+        .ok_or(ErrorCode::InvalidCollateralAsset.into())
+}
+
+/// VAR tokens minted for depositing `amount` of the market's collateral
+pub fn collateral_value(
+    market: &Market,
+    collateral_asset: &Option<Account<CollateralAsset>>,
+    price_feed: &Option<AccountInfo>,
+    amount: u64,
+) -> Result<u64> {
+    match market_collateral_asset(market, collateral_asset)? {
+        Some(collateral_asset) => collateral_asset.usd_value(amount, price_feed.as_ref()),
+        None => Ok(amount),
+    }
+}
+
+/// Collateral paid out for merging `value` of VAR pairs back, the inverse of
+/// `collateral_value` without the haircut
+pub fn collateral_for_value(
+    market: &Market,
+    collateral_asset: &Option<Account<CollateralAsset>>,
+    price_feed: &Option<AccountInfo>,
+    value: u64,
+) -> Result<u64> {
+    match market_collateral_asset(market, collateral_asset)? {
+        Some(collateral_asset) => collateral_asset.collateral_amount(value, price_feed.as_ref()),
+        None => Ok(value),
+    }
+}
+
+/// Reads a fully verified Pyth PriceUpdateV2 account and returns the price in USD with 6 decimals
+pub fn load_pyth_price(account_info: &AccountInfo, feed_id: &[u8; 32], max_age: i64) -> Result<u64> {
+    require!(
+        account_info.owner == &PYTH_RECEIVER_PROGRAM_ID,
+        ErrorCode::InvalidCollateralPrice
+    );
+
+    let data = account_info.try_borrow_data()?;
+
+    // Skip the 8-byte discriminator and write_authority, then require the Full verification level
+    let mut offset = 8 + 32;
+    require!(data.get(offset) == Some(&1), ErrorCode::InvalidCollateralPrice);
+    offset += 1;
+
+    // feed_id (32), price (i64), conf (u64), exponent (i32), publish_time (i64)
+    if data.len() < offset + 32 + 8 + 8 + 4 + 8 {
+        return Err(ErrorCode::InvalidCollateralPrice.into());
+    }
+    require!(&data[offset..offset + 32] == feed_id, ErrorCode::InvalidCollateralPrice);
+    offset += 32;
+
+    let price = i64::from_le_bytes(data[offset..offset + 8].try_into().unwrap());
+    offset += 8 + 8;
+    let exponent = i32::from_le_bytes(data[offset..offset + 4].try_into().unwrap());
+    offset += 4;
+    let publish_time = i64::from_le_bytes(data[offset..offset + 8].try_into().unwrap());
+
+    let age = Clock::get()?
+        .unix_timestamp
+        .checked_sub(publish_time)
+        .ok_or(ErrorCode::NumberOverflow)?;
+    require!(age <= max_age, ErrorCode::StalePrice);
+    require!(price > 0, ErrorCode::InvalidCollateralPrice);
+
+    // Rescale price * 10^exponent to 6 decimals
+    let scale = exponent + USD_DECIMALS as i32;
+    let price = if scale >= 0 {
+        (price as u128)
+            .checked_mul(10u128.pow(scale as u32))
+            .ok_or(ErrorCode::NumberOverflow)?
+    } else {
+        (price as u128) / 10u128.pow(scale.unsigned_abs())
+    };
+    require!(price > 0, ErrorCode::InvalidCollateralPrice);
+
+    u64::try_from(price).map_err(|_| ErrorCode::NumberOverflow.into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn asset(decimals: u8, haircut_bps: u16) -> CollateralAsset {
+        CollateralAsset {
+            authority: Pubkey::default(),
+            mint: Pubkey::default(),
+            decimals,
+            haircut_bps,
+            price_feed: Pubkey::default(),
+            feed_id: [0; 32],
+            max_price_age: 60,
+            bump: 0,
+        }
+    }
+
+    #[test]
+    fn pegged_value_scales_by_decimals_and_haircut() {
+        // 1 whole token of an 8-decimal asset pegged at $1, with a 5% haircut
+        assert_eq!(asset(8, 500).usd_value(100_000_000, None).unwrap(), 950_000);
+        assert_eq!(asset(8, 0).collateral_amount(950_000, None).unwrap(), 95_000_000);
+    }
+
+    #[test]
+    fn payouts_do_not_apply_the_haircut() {
+        let asset = asset(6, 2000);
+        let value = asset.usd_value(1_000_000, None).unwrap();
+        assert_eq!(value, 800_000);
+        assert_eq!(asset.collateral_amount(value, None).unwrap(), 800_000);
+    }
+
+    #[test]
+    fn feed_priced_assets_need_their_feed() {
+        let asset = CollateralAsset {
+            price_feed: Pubkey::new_unique(),
+            ..asset(6, 0)
+        };
+        assert!(asset.usd_value(1_000_000, None).is_err());
+        assert!(asset.collateral_amount(1_000_000, None).is_err());
+    }
+}
@@ -1,3 +1,5 @@
+use crate::errors::ErrorCode;
+use crate::state::{OptionType, ReturnObservation};
 use anchor_lang::prelude::*;
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default)]
@@ -5,6 +7,14 @@ pub struct MarketBumps {
     pub market: u8,
 }
 
+/// Wallet-facing metadata written into a Token-2022 VAR mint
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, Default)]
+pub struct VarTokenMetadata {
+    pub name: String,
+    pub symbol: String,
+    pub uri: String,
+}
+
 #[account]
 pub struct Market {
     pub epoch: u64,
@@ -12,7 +22,7 @@ pub struct Market {
     pub realized_variance: f64,
     pub var_long_mint: Pubkey,
     pub var_short_mint: Pubkey,
-    pub usdc_vault: Pubkey,
+    pub collateral_vault: Pubkey,
     pub authority: Pubkey,
     pub volatility_stats: Pubkey,
     pub timestamp: i64,       // Unix timestamp when market was created
@@ -21,4 +31,671 @@ pub struct Market {
     pub is_initialized: bool,
     pub is_expired: bool,
     pub total_deposits: u64,
+    pub guardian: Pubkey,     // Key allowed to pause the market
+    pub pause_flags: u8,      // Bitmask of PAUSE_* flags
+    pub collateral_mint: Pubkey, // Mint deposited into collateral_vault
+    pub collateral_asset: Pubkey, // Registry entry pricing collateral_mint (default for 6-decimal USDC)
+    pub auction: Pubkey,      // Strike auction, default when the strike was set by the authority
+    pub auction_open: bool,   // Trading is blocked until the auction clears
+    pub series: Pubkey,       // Series that created this market, default for one-off markets
+    pub expiry_timestamp: i64, // Trading stops at this time, 0 when settled manually
+    pub long_payout: u64,     // Collateral still owed to VAR-long holders after settlement
+    pub short_payout: u64,    // Collateral still owed to VAR-short holders after settlement
+    pub variance_type: VarianceType,
+    pub reference_price: u64, // Underlying price (1e6) when the variance type was set
+    pub last_return_sequence: u64, // Last oracle return accrued into conditional_sum
+    pub observed_returns: u64, // Oracle returns seen since the variance type was set
+    pub conditional_sum: f64, // Σ r² over the returns that met the variance condition
+    pub option_type: OptionType,
+    pub payout_per_point: u64, // Option payoff per vol point beyond the strike, per option token
+    pub max_payoff: u64,      // Collateral posted by writers per option token
+    pub variance_cap: f64,    // Cap on realized variance at settlement, 0 for none
+    pub first_return_sequence: u64, // Last oracle return before conditional accrual started
+    pub dispute_window: i64,  // Seconds the guardian may override a proposed settlement, 0 for immediate
+    pub settlement_proposed_at: i64,
+    pub settlement_finalized: bool, // Payouts may only be claimed once final
+    pub caps: MarketCaps,
+    pub dispute_twap_window: i64, // Seconds of oracle history a disputed settlement is averaged over
+    pub unclaimed_long_fills: u64, // Auction fills owed to VAR-long bidders, minted when claimed
+    pub unclaimed_short_fills: u64, // Auction fills owed to VAR-short bidders, minted when claimed
+}
+
+/// Limits enforced when VAR tokens are minted on one side, 0 disables a limit
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default)]
+pub struct MarketCaps {
+    pub max_total_deposits: u64,  // Collateral held by the market
+    pub max_side_supply: u64,     // VAR tokens outstanding on either side
+    pub max_user_deposits: u64,   // VAR tokens a single user may mint
+    pub max_imbalance_bps: u32,   // Max ratio of the minted side to the other side, 10000 = 1:1
+    pub imbalance_floor: u64,     // The other side counts as at least this much for the ratio, required with an imbalance limit
+}
+
+/// Account size of a market, discriminator included. Enums take their largest variant.
+pub const MARKET_SPACE: usize = 8 // discriminator
+    + 8                           // epoch
+    + 8                           // strike
+    + 8                           // realized_variance
+    + 32                          // var_long_mint
+    + 32                          // var_short_mint
+    + 32                          // collateral_vault
+    + 32                          // authority
+    + 32                          // volatility_stats
+    + 8                           // timestamp
+    + 8                           // start_volatility
+    + 1                           // bumps
+    + 1                           // is_initialized
+    + 1                           // is_expired
+    + 8                           // total_deposits
+    + 32                          // guardian
+    + 1                           // pause_flags
+    + 32                          // collateral_mint
+    + 32                          // collateral_asset
+    + 32                          // auction
+    + 1                           // auction_open
+    + 32                          // series
+    + 8                           // expiry_timestamp
+    + 8                           // long_payout
+    + 8                           // short_payout
+    + (1 + 8 + 8)                 // variance_type, largest variant is Corridor
+    + 8                           // reference_price
+    + 8                           // last_return_sequence
+    + 8                           // observed_returns
+    + 8                           // conditional_sum
+    + 1                           // option_type
+    + 8                           // payout_per_point
+    + 8                           // max_payoff
+    + 8                           // variance_cap
+    + 8                           // first_return_sequence
+    + 8                           // dispute_window
+    + 8                           // settlement_proposed_at
+    + 1                           // settlement_finalized
+    + (8 + 8 + 8 + 4 + 8)         // caps
+    + 8                           // dispute_twap_window
+    + 8                           // unclaimed_long_fills
+    + 8;                          // unclaimed_short_fills
+
+/// Long and short amounts of a mint on one side
+pub fn side_amounts(is_long: bool, amount: u64) -> (u64, u64) {
+    if is_long {
+        (amount, 0)
+    } else {
+        (0, amount)
+    }
+}
+
+/// Longest TWAP a disputed settlement may be averaged over
+pub const MAX_DISPUTE_TWAP_WINDOW: i64 = 7 * 24 * 60 * 60;
+
+/// Which oracle returns count towards a market's realized variance.
+/// Conditional types test the price a return started from.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum VarianceType {
+    Standard,                            // Settles on the oracle's annualized volatility
+    Corridor { lower: u64, upper: u64 }, // Returns starting inside [lower, upper] (1e6 prices)
+    Up,                                  // Returns starting at or above reference_price
+    Down,                                // Returns starting below reference_price
+}
+
+/// Returns per year used by the oracle to annualize
+pub const RETURNS_PER_YEAR: f64 = 252.0;
+
+pub const PAUSE_MINTS: u8 = 1 << 0;
+pub const PAUSE_REDEEMS: u8 = 1 << 1;
+pub const PAUSE_UPDATES: u8 = 1 << 2;
+pub const PAUSE_ALL: u8 = PAUSE_MINTS | PAUSE_REDEEMS | PAUSE_UPDATES;
+pub const REDEMPTIONS_ONLY: u8 = PAUSE_MINTS | PAUSE_UPDATES;
+
+/// PDA seeds of a market, bump included. Series markets are namespaced by their
+/// series so nobody can create an epoch's market before the series rolls into it.
+pub struct MarketSeeds {
+    series: Option<Pubkey>,
+    epoch: [u8; 8],
+    timestamp: [u8; 8],
+    bump: [u8; 1],
+}
+
+impl MarketSeeds {
+    pub fn as_slices(&self) -> Vec<&[u8]> {
+        let mut seeds: Vec<&[u8]> = vec![b"market"];
+        if let Some(series) = &self.series {
+            seeds.push(series.as_ref());
+        }
+        seeds.extend([&self.epoch[..], &self.timestamp[..], &self.bump[..]]);
+        seeds
+    }
+}
+
+impl Market {
+    pub fn is_paused(&self, flag: u8) -> bool {
+        self.pause_flags & flag != 0
+    }
+
+    /// Seeds the market signs its mints and vault transfers with
+    pub fn seeds(&self) -> MarketSeeds {
+        MarketSeeds {
+            series: (self.series != Pubkey::default()).then_some(self.series),
+            epoch: self.epoch.to_le_bytes(),
+            timestamp: self.timestamp.to_le_bytes(),
+            bump: [self.bumps.market],
+        }
+    }
+
+    /// VAR tokens outstanding on one side given the `minted` supply, counting
+    /// auction fills that have not been claimed yet
+    pub fn outstanding_supply(&self, is_long: bool, minted: u64) -> Result<u64> {
+        let unclaimed = if is_long {
+            self.unclaimed_long_fills
+        } else {
+            self.unclaimed_short_fills
+        };
+        Ok(minted.checked_add(unclaimed).ok_or(ErrorCode::NumberOverflow)?)
+    }
+
+    /// Whether `key` is the PDA derived from this market's seeds
+    pub fn is_derived_at(&self, key: &Pubkey) -> bool {
+        Pubkey::create_program_address(&self.seeds().as_slices(), &crate::ID)
+            .is_ok_and(|address| address == *key)
+    }
+
+    /// Checks the market caps against the collateral deposited in an auction.
+    /// A bid is filled at most for its deposit and clearing matches both sides
+    /// equally, so bounding the deposits bounds what the auction can add.
+    pub fn check_bid_caps(&self, long_deposits: u64, short_deposits: u64) -> Result<()> {
+        let caps = &self.caps;
+        let total_deposits = long_deposits
+            .checked_add(short_deposits)
+            .and_then(|deposits| deposits.checked_add(self.total_deposits))
+            .ok_or(ErrorCode::NumberOverflow)?;
+        require!(
+            caps.max_total_deposits == 0 || total_deposits <= caps.max_total_deposits,
+            ErrorCode::MarketCapExceeded
+        );
+        for deposits in [long_deposits, short_deposits] {
+            require!(
+                caps.max_side_supply == 0 || deposits <= caps.max_side_supply,
+                ErrorCode::SideCapExceeded
+            );
+        }
+        Ok(())
+    }
+
+    /// Checks the market caps for minting `long_amount` and `short_amount` VAR tokens,
+    /// given the minted supplies of both sides before the mint and the collateral
+    /// deposited. Unclaimed auction fills count as supply. The imbalance limit only
+    /// applies to the side that grows more.
+    pub fn check_mint_caps(
+        &self,
+        long_amount: u64,
+        short_amount: u64,
+        collateral_amount: u64,
+        long_supply: u64,
+        short_supply: u64,
+    ) -> Result<()> {
+        let caps = &self.caps;
+        let total_deposits = self
+            .total_deposits
+            .checked_add(collateral_amount)
+            .ok_or(ErrorCode::NumberOverflow)?;
+        require!(
+            caps.max_total_deposits == 0 || total_deposits <= caps.max_total_deposits,
+            ErrorCode::MarketCapExceeded
+        );
+
+        let long_supply = self
+            .outstanding_supply(true, long_supply)?
+            .checked_add(long_amount)
+            .ok_or(ErrorCode::NumberOverflow)?;
+        let short_supply = self
+            .outstanding_supply(false, short_supply)?
+            .checked_add(short_amount)
+            .ok_or(ErrorCode::NumberOverflow)?;
+        for (amount, supply) in [(long_amount, long_supply), (short_amount, short_supply)] {
+            require!(
+                amount == 0 || caps.max_side_supply == 0 || supply <= caps.max_side_supply,
+                ErrorCode::SideCapExceeded
+            );
+        }
+
+        if caps.max_imbalance_bps != 0 && long_amount != short_amount {
+            let (side_supply, other_supply) = if long_amount > short_amount {
+                (long_supply, short_supply)
+            } else {
+                (short_supply, long_supply)
+            };
+            let other_supply = other_supply.max(caps.imbalance_floor) as u128;
+            require!(
+                (side_supply as u128) * 10000 <= other_supply * caps.max_imbalance_bps as u128,
+                ErrorCode::ImbalanceLimitExceeded
+            );
+        }
+
+        Ok(())
+    }
+
+    /// Whether the market is settled or past its expiry time
+    pub fn is_expired_at(&self, now: i64) -> bool {
+        self.is_expired || (self.expiry_timestamp != 0 && now >= self.expiry_timestamp)
+    }
+
+    /// Whether a return starting at `price` counts towards realized variance
+    pub fn counts_return(&self, price: u64) -> bool {
+        match self.variance_type {
+            VarianceType::Standard => true,
+            VarianceType::Corridor { lower, upper } => price >= lower && price <= upper,
+            VarianceType::Up => price >= self.reference_price,
+            VarianceType::Down => price < self.reference_price,
+        }
+    }
+
+    /// Accrues oracle returns not seen yet, up to the market's expiry.
+    /// Returns that already left the oracle's ring buffer are skipped and their
+    /// number is returned.
+    pub fn accrue_returns(&mut self, returns: &[ReturnObservation]) -> u64 {
+        let mut skipped = 0;
+        for observation in returns {
+            if observation.sequence <= self.last_return_sequence {
+                continue;
+            }
+            if self.expiry_timestamp != 0 && observation.timestamp > self.expiry_timestamp {
+                break;
+            }
+
+            skipped += observation.sequence - self.last_return_sequence - 1;
+            self.observed_returns += 1;
+            if self.counts_return(observation.start_price) {
+                self.conditional_sum += observation.log_return * observation.log_return;
+            }
+            self.last_return_sequence = observation.sequence;
+        }
+        skipped
+    }
+
+    /// Discards the accrued returns and accrues them again from the oracle's ring
+    /// buffer, which must still hold every return since accrual started
+    pub fn reaccrue_returns(&mut self, returns: &[ReturnObservation]) -> Result<()> {
+        self.last_return_sequence = self.first_return_sequence;
+        self.observed_returns = 0;
+        self.conditional_sum = 0.0;
+        require!(self.accrue_returns(returns) == 0, ErrorCode::InsufficientOracleHistory);
+        Ok(())
+    }
+
+    /// Annualized conditional variance in variance points (σ² × 100), averaged
+    /// over every observed return as in a corridor variance swap
+    pub fn conditional_variance(&self) -> f64 {
+        if self.observed_returns == 0 {
+            return 0.0;
+        }
+        self.conditional_sum / self.observed_returns as f64 * RETURNS_PER_YEAR * 100.0
+    }
+
+    /// Settles the market at the given annualized volatility and records what each
+    /// side is owed. Realized variance is floored at zero and capped when
+    /// `variance_cap` is positive. Conditional markets must be accrued first.
+    pub fn settle(&mut self, annualized_volatility: f64) -> Result<()> {
+        let variance_cap = self.variance_cap;
+        let mut realized_variance = match self.variance_type {
+            VarianceType::Standard => ((annualized_volatility * 100.0) - (self.start_volatility * 100.0)).max(0.0),
+            _ => self.conditional_variance(),
+        };
+        if variance_cap > 0.0 {
+            realized_variance = realized_variance.min(variance_cap);
+        }
+
+        let long_payout = if realized_variance > self.strike {
+            let variance_diff = realized_variance - self.strike;
+            ((variance_diff * (self.total_deposits as f64) / 100.0) as u64).min(self.total_deposits)
+        } else {
+            0
+        };
+
+        self.realized_variance = realized_variance;
+        self.is_expired = true;
+        self.long_payout = long_payout;
+        self.short_payout = self
+            .total_deposits
+            .checked_sub(long_payout)
+            .ok_or(ErrorCode::NumberOverflow)?;
+
+        Ok(())
+    }
+
+    /// Settles an option market: each of the `option_supply` option tokens is owed
+    /// its capped intrinsic value, writers keep the rest of the collateral.
+    /// Binary markets pay every deposit to YES or to NO.
+    pub fn settle_option(&mut self, annualized_volatility: f64, option_supply: u64) -> Result<()> {
+        let realized_volatility = (annualized_volatility * 100.0).max(0.0);
+        let intrinsic = match self.option_type {
+            OptionType::Call => realized_volatility - self.strike,
+            OptionType::Put => self.strike - realized_volatility,
+            // YES and NO are minted in pairs, so one side takes every deposit
+            OptionType::Binary if realized_volatility > self.strike => {
+                return self.set_option_payouts(realized_volatility, self.total_deposits);
+            }
+            OptionType::Binary => return self.set_option_payouts(realized_volatility, 0),
+            OptionType::None => return Err(ErrorCode::InvalidOptionTerms.into()),
+        }
+        .max(0.0);
+        let payoff = ((intrinsic * self.payout_per_point as f64) as u64).min(self.max_payoff);
+
+        let long_payout = payoff
+            .checked_mul(option_supply)
+            .ok_or(ErrorCode::NumberOverflow)?
+            .min(self.total_deposits);
+        self.set_option_payouts(realized_volatility, long_payout)
+    }
+
+    /// Starts the dispute window of a settlement, or finalizes it right away
+    /// when the market has none
+    pub fn propose_settlement(&mut self, now: i64) {
+        self.settlement_proposed_at = now;
+        self.settlement_finalized = self.dispute_window == 0;
+    }
+
+    /// Whether a dispute window and the TWAP a dispute settles on are usable together.
+    /// A market with a dispute window needs a TWAP of at most MAX_DISPUTE_TWAP_WINDOW.
+    pub fn valid_dispute_terms(dispute_window: i64, dispute_twap_window: i64) -> bool {
+        dispute_window >= 0
+            && (0..=MAX_DISPUTE_TWAP_WINDOW).contains(&dispute_twap_window)
+            && (dispute_window == 0 || dispute_twap_window > 0)
+    }
+
+    /// End of the window in which the guardian may override the settlement
+    pub fn dispute_deadline(&self) -> i64 {
+        self.settlement_proposed_at.saturating_add(self.dispute_window)
+    }
+
+    /// Re-settles at the given volatility, or from the accrued returns for
+    /// conditional markets
+    pub fn resettle(&mut self, annualized_volatility: f64, option_supply: u64) -> Result<()> {
+        if self.option_type == OptionType::None {
+            self.settle(annualized_volatility)
+        } else {
+            self.settle_option(annualized_volatility, option_supply)
+        }
+    }
+
+    fn set_option_payouts(&mut self, realized_volatility: f64, long_payout: u64) -> Result<()> {
+        self.realized_variance = realized_volatility;
+        self.is_expired = true;
+        self.long_payout = long_payout;
+        self.short_payout = self.total_deposits - long_payout;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn market() -> Market {
+        Market::deserialize(&mut &[0u8; MARKET_SPACE][..]).unwrap()
+    }
+
+    #[test]
+    fn market_space_fits_the_largest_layout() {
+        let mut market = market();
+        market.variance_type = VarianceType::Corridor { lower: 0, upper: 0 };
+        assert_eq!(8 + market.try_to_vec().unwrap().len(), MARKET_SPACE);
+    }
+
+    #[test]
+    fn long_is_paid_variance_above_the_strike() {
+        let mut market = market();
+        market.start_volatility = 0.1;
+        market.strike = 20.0;
+        market.total_deposits = 1_000_000;
+
+        // Realized variance is 50 - 10 = 40 points, 20 above the strike
+        market.settle(0.5).unwrap();
+        assert_eq!(market.realized_variance, 40.0);
+        assert_eq!(market.long_payout, 200_000);
+        assert_eq!(market.short_payout, 800_000);
+        assert!(market.is_expired);
+    }
+
+    #[test]
+    fn short_keeps_everything_below_the_strike() {
+        let mut market = market();
+        market.start_volatility = 0.3;
+        market.strike = 20.0;
+        market.total_deposits = 1_000_000;
+
+        // Volatility fell, realized variance floors at zero
+        market.settle(0.2).unwrap();
+        assert_eq!(market.realized_variance, 0.0);
+        assert_eq!(market.long_payout, 0);
+        assert_eq!(market.short_payout, 1_000_000);
+    }
+
+    #[test]
+    fn long_payout_is_capped() {
+        let mut uncapped = market();
+        uncapped.total_deposits = 1_000_000;
+
+        // Without a cap the long side takes at most every deposit
+        uncapped.settle(5.0).unwrap();
+        assert_eq!(uncapped.long_payout, 1_000_000);
+        assert_eq!(uncapped.short_payout, 0);
+
+        // With one, realized variance stops at the cap
+        let mut capped = market();
+        capped.total_deposits = 1_000_000;
+        capped.variance_cap = 30.0;
+        capped.settle(5.0).unwrap();
+        assert_eq!(capped.realized_variance, 30.0);
+        assert_eq!(capped.long_payout, 300_000);
+        assert_eq!(capped.short_payout, 700_000);
+    }
+
+    #[test]
+    fn conditional_markets_settle_on_accrued_returns() {
+        let mut market = market();
+        market.variance_type = VarianceType::Up;
+        market.total_deposits = 1_000_000;
+        market.observed_returns = 252;
+        market.conditional_sum = 0.1;
+
+        // The oracle volatility is ignored
+        market.settle(5.0).unwrap();
+        assert!((market.realized_variance - 10.0).abs() < 1e-9);
+        assert_eq!(market.long_payout, 100_000);
+    }
+
+    fn observation(sequence: u64, timestamp: i64, start_price: u64, log_return: f64) -> ReturnObservation {
+        ReturnObservation {
+            sequence,
+            timestamp,
+            start_price,
+            log_return,
+        }
+    }
+
+    #[test]
+    fn corridor_counts_returns_starting_inside_it() {
+        let mut market = market();
+        market.variance_type = VarianceType::Corridor { lower: 90_000_000, upper: 110_000_000 };
+
+        let returns = [
+            observation(1, 100, 100_000_000, 0.1),
+            observation(2, 200, 120_000_000, 0.2),
+            observation(3, 300, 90_000_000, 0.3),
+        ];
+        assert_eq!(market.accrue_returns(&returns), 0);
+        assert_eq!(market.observed_returns, 3);
+        assert_eq!(market.last_return_sequence, 3);
+        assert!((market.conditional_sum - 0.1).abs() < 1e-12);
+    }
+
+    #[test]
+    fn up_and_down_split_on_the_reference_price() {
+        let returns = [
+            observation(1, 100, 100_000_000, 0.1),
+            observation(2, 200, 99_999_999, 0.2),
+        ];
+
+        let mut up = market();
+        up.variance_type = VarianceType::Up;
+        up.reference_price = 100_000_000;
+        up.accrue_returns(&returns);
+        assert!((up.conditional_sum - 0.01).abs() < 1e-12);
+
+        let mut down = market();
+        down.variance_type = VarianceType::Down;
+        down.reference_price = 100_000_000;
+        down.accrue_returns(&returns);
+        assert!((down.conditional_sum - 0.04).abs() < 1e-12);
+    }
+
+    #[test]
+    fn accrual_skips_seen_returns_and_stops_at_expiry() {
+        let mut market = market();
+        market.variance_type = VarianceType::Up;
+        market.last_return_sequence = 1;
+        market.expiry_timestamp = 250;
+
+        let returns = [
+            observation(1, 100, 1, 0.5),
+            observation(2, 200, 1, 0.1),
+            observation(3, 300, 1, 0.5),
+        ];
+        assert_eq!(market.accrue_returns(&returns), 0);
+        assert_eq!(market.observed_returns, 1);
+        assert_eq!(market.last_return_sequence, 2);
+        assert!((market.conditional_sum - 0.01).abs() < 1e-12);
+
+        // Accruing the same buffer again changes nothing
+        market.accrue_returns(&returns);
+        assert_eq!(market.observed_returns, 1);
+    }
+
+    #[test]
+    fn accrual_reports_returns_lost_from_the_ring_buffer() {
+        let mut market = market();
+        market.variance_type = VarianceType::Up;
+
+        // Returns 1 to 3 were overwritten before they were accrued
+        let returns = [observation(4, 400, 1, 0.1), observation(5, 500, 1, 0.1)];
+        assert_eq!(market.accrue_returns(&returns), 3);
+        assert_eq!(market.observed_returns, 2);
+
+        // Reaccruing needs every return since accrual started
+        assert!(market.reaccrue_returns(&returns).is_err());
+        market.first_return_sequence = 3;
+        market.reaccrue_returns(&returns).unwrap();
+        assert_eq!(market.observed_returns, 2);
+    }
+
+    #[test]
+    fn disputes_need_a_bounded_twap_window() {
+        // Without a dispute window the TWAP window is unused
+        assert!(Market::valid_dispute_terms(0, 0));
+        assert!(Market::valid_dispute_terms(3600, 3600));
+        assert!(Market::valid_dispute_terms(3600, MAX_DISPUTE_TWAP_WINDOW));
+
+        assert!(!Market::valid_dispute_terms(3600, 0));
+        assert!(!Market::valid_dispute_terms(3600, MAX_DISPUTE_TWAP_WINDOW + 1));
+        assert!(!Market::valid_dispute_terms(-1, 3600));
+        assert!(!Market::valid_dispute_terms(3600, -1));
+    }
+
+    #[test]
+    fn mint_caps_limit_deposits_and_side_supply() {
+        let mut market = market();
+        market.total_deposits = 900;
+        market.caps.max_total_deposits = 1_000;
+        market.caps.max_side_supply = 500;
+
+        market.check_mint_caps(100, 0, 100, 400, 0).unwrap();
+        assert!(market.check_mint_caps(100, 0, 101, 0, 0).is_err());
+        assert!(market.check_mint_caps(101, 0, 100, 400, 0).is_err());
+        // Pairs count against both sides, a side already over the cap only blocks its own mints
+        assert!(market.check_mint_caps(50, 50, 100, 0, 460).is_err());
+        market.check_mint_caps(0, 50, 50, 600, 0).unwrap();
+    }
+
+    #[test]
+    fn bid_caps_limit_auction_deposits() {
+        let mut market = market();
+        market.total_deposits = 200;
+        market.caps.max_total_deposits = 1_000;
+        market.caps.max_side_supply = 500;
+
+        market.check_bid_caps(500, 300).unwrap();
+        assert!(market.check_bid_caps(501, 0).is_err());
+        assert!(market.check_bid_caps(0, 501).is_err());
+        assert!(market.check_bid_caps(500, 301).is_err());
+    }
+
+    #[test]
+    fn imbalance_limit_uses_the_floor_and_ignores_pairs() {
+        let mut market = market();
+        market.caps.max_imbalance_bps = 20_000;
+        market.caps.imbalance_floor = 100;
+
+        // The first mint into an empty market is measured against the floor
+        market.check_mint_caps(200, 0, 200, 0, 0).unwrap();
+        assert!(market.check_mint_caps(201, 0, 201, 0, 0).is_err());
+        assert!(market.check_mint_caps(0, 201, 201, 0, 0).is_err());
+
+        // Past the floor the other side's supply sets the limit
+        market.check_mint_caps(100, 0, 100, 500, 300).unwrap();
+        assert!(market.check_mint_caps(101, 0, 101, 500, 300).is_err());
+
+        // Equal pairs never worsen the ratio, uneven ones are checked on the larger side
+        market.check_mint_caps(1_000, 1_000, 1_000, 900, 100).unwrap();
+        assert!(market.check_mint_caps(1_000, 100, 1_000, 900, 100).is_err());
+    }
+
+    #[test]
+    fn series_markets_are_namespaced_by_their_series() {
+        let epoch = 3u64;
+        let timestamp = 1_700_000_000i64;
+
+        // A squatter creates the one-off market at the series' next epoch and start time
+        let (squatted, _) = Pubkey::find_program_address(
+            &[b"market", &epoch.to_le_bytes(), &timestamp.to_le_bytes()],
+            &crate::ID,
+        );
+
+        let series = Pubkey::new_unique();
+        let (address, bump) = Pubkey::find_program_address(
+            &[b"market", series.as_ref(), &epoch.to_le_bytes(), &timestamp.to_le_bytes()],
+            &crate::ID,
+        );
+        assert_ne!(address, squatted);
+
+        let mut market = market();
+        market.series = series;
+        market.epoch = epoch;
+        market.timestamp = timestamp;
+        market.bumps.market = bump;
+        assert!(market.is_derived_at(&address));
+        assert!(!market.is_derived_at(&squatted));
+
+        // A second series on the same schedule gets its own market
+        market.series = Pubkey::new_unique();
+        assert!(!market.is_derived_at(&address));
+    }
+
+    #[test]
+    fn unclaimed_auction_fills_keep_their_settlement_share() {
+        use crate::instructions::claim_settlement::settlement_share;
+
+        let mut market = market();
+        market.unclaimed_long_fills = 600;
+        market.long_payout = 1_000;
+
+        // 400 long tokens are minted, the first claimer cannot take the whole payout
+        let supply = market.outstanding_supply(true, 400).unwrap();
+        assert_eq!(supply, 1_000);
+        assert_eq!(settlement_share(400, supply, market.long_payout).unwrap(), 400);
+
+        // The late bidder claims the fill, which moves into the minted supply
+        market.long_payout -= 400;
+        market.unclaimed_long_fills = 0;
+        let supply = market.outstanding_supply(true, 600).unwrap();
+        assert_eq!(settlement_share(600, supply, market.long_payout).unwrap(), 600);
+    }
 }
@@ -1,5 +1,17 @@
+pub mod auction;
+pub mod collateral_asset;
 pub mod market;
+pub mod option;
 pub mod oracle;
+pub mod pool;
+pub mod series;
+pub mod user_deposit;
 
+pub use auction::*;
+pub use collateral_asset::*;
 pub use market::*;
+pub use option::*;
 pub use oracle::*;
+pub use pool::*;
+pub use series::*;
+pub use user_deposit::*;
@@ -0,0 +1,30 @@
+use anchor_lang::prelude::*;
+
+/// Payoff of an option market. Options settle on the oracle's annualized
+/// volatility in points (σ × 100) against the market strike.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum OptionType {
+    None, // Linear variance market
+    Call,
+    Put,
+    Binary, // YES (VAR-long) pays one collateral unit per token if volatility ends above the strike
+}
+
+impl OptionType {
+    /// Calls and puts are collateralized by writers, binaries are minted as pairs
+    pub fn is_written(&self) -> bool {
+        matches!(self, OptionType::Call | OptionType::Put)
+    }
+}
+
+/// Option tokens a writer has listed at a fixed premium. Unsold options sit in
+/// the offer's escrow until bought or the offer is cancelled.
+#[account]
+pub struct OptionOffer {
+    pub market: Pubkey,
+    pub writer: Pubkey,
+    pub escrow: Pubkey,
+    pub premium_per_option: u64,     // Collateral paid to the writer per option token
+    pub bump: u8,
+    pub escrow_bump: u8,
+}
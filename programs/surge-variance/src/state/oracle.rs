@@ -1,11 +1,119 @@
+use crate::errors::ErrorCode;
 use anchor_lang::prelude::*;
+pub use surge_oracle::state::{
+    OracleHistory, ReturnObservation, VolatilityStats, OBSERVATION_COUNT, RETURN_COUNT,
+};
 
-#[account]
-pub struct VolatilityStats {
-    pub authority: Pubkey,
-    pub last_price: u64,            // Fixed-point price (1e6)
-    pub mean: f64,                  // Mean of log-returns
-    pub m2: f64,                    // Running Σ(r - mean)^2
-    pub count: u64,                 // Number of returns seen
-    pub annualized_volatility: f64, // Annualized σ estimate
+fn read_u64(data: &[u8], offset: usize) -> u64 {
+    u64::from_le_bytes(data[offset..offset + 8].try_into().unwrap())
+}
+
+/// Reads annualized_volatility from a raw VolatilityStats account owned by the oracle program
+pub fn read_annualized_volatility(volatility_stats: &AccountInfo) -> Result<f64> {
+    let data = volatility_stats.try_borrow_data()?;
+    let offset = VolatilityStats::ANNUALIZED_VOLATILITY_OFFSET;
+    if data.len() < offset + 8 {
+        return Err(ProgramError::InvalidAccountData.into());
+    }
+
+    Ok(f64::from_bits(read_u64(&data, offset)))
+}
+
+/// Reads last_price and the number of returns seen from a raw VolatilityStats account
+pub fn read_price_and_return_count(volatility_stats: &AccountInfo) -> Result<(u64, u64)> {
+    let data = volatility_stats.try_borrow_data()?;
+    if data.len() < VolatilityStats::COUNT_OFFSET + 8 {
+        return Err(ProgramError::InvalidAccountData.into());
+    }
+
+    let last_price = read_u64(&data, VolatilityStats::LAST_PRICE_OFFSET);
+    let count = read_u64(&data, VolatilityStats::COUNT_OFFSET);
+
+    // count includes the first price, which has no return
+    Ok((last_price, count.saturating_sub(1)))
+}
+
+/// Reads the return ring buffer from a raw VolatilityStats account, oldest first
+pub fn read_return_observations(volatility_stats: &AccountInfo) -> Result<Vec<ReturnObservation>> {
+    let data = volatility_stats.try_borrow_data()?;
+    if data.len() < VolatilityStats::RETURNS_OFFSET + 32 * RETURN_COUNT {
+        return Err(ProgramError::InvalidAccountData.into());
+    }
+
+    let mut returns = Vec::with_capacity(RETURN_COUNT);
+    for slot in 0..RETURN_COUNT {
+        let offset = VolatilityStats::RETURNS_OFFSET + 32 * slot;
+        let observation = ReturnObservation {
+            sequence: read_u64(&data, offset),
+            timestamp: read_u64(&data, offset + 8) as i64,
+            start_price: read_u64(&data, offset + 16),
+            log_return: f64::from_bits(read_u64(&data, offset + 24)),
+        };
+        if observation.sequence > 0 {
+            returns.push(observation);
+        }
+    }
+    returns.sort_by_key(|observation| observation.sequence);
+
+    Ok(returns)
+}
+
+/// Volatility at `expiry` read from the oracle's cumulative volatility history:
+/// the estimate in effect when `twap_window` is 0, otherwise the TWAP ending
+/// there. Falls back to the oldest observations once expiry has rotated out.
+pub fn read_settlement_volatility(volatility_stats: &AccountInfo, twap_window: i64, expiry: i64) -> Result<f64> {
+    require!(twap_window >= 0, ErrorCode::InvalidSettlementTerms);
+
+    OracleHistory::load(&volatility_stats.try_borrow_data()?)
+        .ok_or(ProgramError::InvalidAccountData)?
+        .settlement_volatility(twap_window, expiry)
+        .ok_or(ErrorCode::InsufficientOracleHistory.into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use surge_oracle::state::VolatilityObservation;
+
+    #[test]
+    fn rotated_history_settles_on_the_oldest_observations() {
+        let mut stats = VolatilityStats {
+            authority: Pubkey::default(),
+            last_price: 0,
+            mean: 0.0,
+            m2: 0.0,
+            count: 0,
+            annualized_volatility: 30.0,
+            last_update_timestamp: 0,
+            guardian: Pubkey::default(),
+            pause_flags: 0,
+            cumulative_volatility: 0.0,
+            observation_index: 0,
+            observations: [VolatilityObservation::default(); OBSERVATION_COUNT],
+            return_index: 0,
+            returns: [ReturnObservation::default(); RETURN_COUNT],
+        };
+        // Expiry at 1_000, followed by hourly updates stepping the estimate up by 1
+        // until only hours 7.. are left in the ring buffer
+        for hour in 0..=OBSERVATION_COUNT as i64 + 6 {
+            let slot = hour as usize % OBSERVATION_COUNT;
+            stats.observations[slot] = VolatilityObservation {
+                timestamp: 1_000 + hour * 3_600,
+                cumulative_volatility: (hour * (hour - 1) / 2 * 3_600) as f64,
+            };
+            stats.observation_index = slot as u8;
+            stats.last_update_timestamp = stats.observations[slot].timestamp;
+            stats.cumulative_volatility = stats.observations[slot].cumulative_volatility;
+        }
+
+        let mut data = vec![0u8; 8];
+        stats.serialize(&mut data).unwrap();
+        let key = Pubkey::default();
+        let mut lamports = 0;
+        let account_info = AccountInfo::new(&key, false, false, &mut lamports, &mut data, &key, false, 0);
+
+        assert_eq!(read_settlement_volatility(&account_info, 3_600, 1_000).unwrap(), 7.0);
+        assert_eq!(read_settlement_volatility(&account_info, 7_200, 1_000).unwrap(), 7.5);
+        assert_eq!(read_settlement_volatility(&account_info, 3_600, 1_000 + 11 * 3_600).unwrap(), 10.0);
+    }
 }
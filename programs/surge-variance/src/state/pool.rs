@@ -0,0 +1,196 @@
+use crate::errors::ErrorCode;
+use anchor_lang::prelude::*;
+
+/// Constant-product pool trading a market's VAR-long and VAR-short tokens.
+/// Collateral entering the pool is split into long/short pairs at `PAIR_VALUE`
+/// of its USD value each, the same valuation mint_tokens uses, and collateral
+/// leaving it merges pairs back.
+#[account]
+pub struct Pool {
+    pub market: Pubkey,
+    pub lp_mint: Pubkey,
+    pub long_vault: Pubkey,   // VAR-long reserve owned by the pool
+    pub short_vault: Pubkey,  // VAR-short reserve owned by the pool
+    pub fee_bps: u16,         // Swap fee kept in the reserves for LPs
+    pub bump: u8,
+}
+
+pub const MAX_POOL_FEE_BPS: u16 = 1000;
+
+/// USD value of one long/short pair. mint_tokens charges one unit per token of
+/// either side, so a pair costs two.
+pub const PAIR_VALUE: u64 = 2;
+
+/// Pairs `value` splits into, the odd unit stays in the vault
+pub fn pairs_for_value(value: u64) -> u64 {
+    value / PAIR_VALUE
+}
+
+/// USD value of `pairs` merged back into collateral
+pub fn pair_value(pairs: u64) -> Result<u64> {
+    pairs.checked_mul(PAIR_VALUE).ok_or(ErrorCode::NumberOverflow.into())
+}
+
+impl Pool {
+    /// Swap fee on `amount`
+    pub fn fee(&self, amount: u64) -> Result<u64> {
+        let fee = (amount as u128)
+            .checked_mul(self.fee_bps as u128)
+            .ok_or(ErrorCode::NumberOverflow)?
+            / 10000;
+        Ok(fee as u64)
+    }
+}
+
+/// Tokens of the bought side paid out when `amount_in` pairs are added to the
+/// reserves, keeping `out_reserve * other_reserve` constant
+pub fn buy_amount_out(out_reserve: u64, other_reserve: u64, amount_in: u64) -> Result<u64> {
+    let k = (out_reserve as u128)
+        .checked_mul(other_reserve as u128)
+        .ok_or(ErrorCode::NumberOverflow)?;
+    let other_after = (other_reserve as u128)
+        .checked_add(amount_in as u128)
+        .ok_or(ErrorCode::NumberOverflow)?;
+    require!(other_after > 0, ErrorCode::InsufficientLiquidity);
+
+    // Round the remaining reserve up so the product never shrinks
+    let out_after = k
+        .checked_add(other_after - 1)
+        .ok_or(ErrorCode::NumberOverflow)?
+        / other_after;
+    let amount_out = (out_reserve as u128)
+        .checked_add(amount_in as u128)
+        .and_then(|reserve| reserve.checked_sub(out_after))
+        .ok_or(ErrorCode::NumberOverflow)?;
+    u64::try_from(amount_out).map_err(|_| ErrorCode::NumberOverflow.into())
+}
+
+/// Pairs that can be merged back into collateral when `amount_in` tokens of
+/// one side are added, keeping `in_reserve * other_reserve` constant.
+/// Solves (in + a - c)(other - c) = in * other for c.
+pub fn sell_amount_out(in_reserve: u64, other_reserve: u64, amount_in: u64) -> Result<u64> {
+    let sum = (in_reserve as u128)
+        .checked_add(amount_in as u128)
+        .and_then(|sum| sum.checked_add(other_reserve as u128))
+        .ok_or(ErrorCode::NumberOverflow)?;
+    let discriminant = sum
+        .checked_mul(sum)
+        .and_then(|square| {
+            square.checked_sub((amount_in as u128).checked_mul(other_reserve as u128)?.checked_mul(4)?)
+        })
+        .ok_or(ErrorCode::NumberOverflow)?;
+
+    // Round the square root up so the smaller root, and the payout, round down
+    let root = isqrt(discriminant);
+    let root = if root * root < discriminant { root + 1 } else { root };
+    let amount_out = sum.checked_sub(root).ok_or(ErrorCode::NumberOverflow)? / 2;
+    u64::try_from(amount_out).map_err(|_| ErrorCode::NumberOverflow.into())
+}
+
+fn isqrt(value: u128) -> u128 {
+    if value < 2 {
+        return value;
+    }
+    let mut x = value;
+    let mut y = x.div_ceil(2);
+    while y < x {
+        x = y;
+        y = (x + value / x) / 2;
+    }
+    x
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pool(fee_bps: u16) -> Pool {
+        Pool {
+            market: Pubkey::default(),
+            lp_mint: Pubkey::default(),
+            long_vault: Pubkey::default(),
+            short_vault: Pubkey::default(),
+            fee_bps,
+            bump: 0,
+        }
+    }
+
+    #[test]
+    fn fee_rounds_down() {
+        assert_eq!(pool(30).fee(1_000_000).unwrap(), 3_000);
+        assert_eq!(pool(30).fee(333).unwrap(), 0);
+        assert_eq!(pool(0).fee(1_000_000).unwrap(), 0);
+    }
+
+    #[test]
+    fn buy_keeps_the_product_constant() {
+        let (out_reserve, other_reserve, amount_in) = (1_000_000, 1_000_000, 100_000);
+        let amount_out = buy_amount_out(out_reserve, other_reserve, amount_in).unwrap();
+        // Each pair adds one token to both sides, the pool pays out the bought side
+        let out_after = out_reserve + amount_in - amount_out;
+        let other_after = other_reserve + amount_in;
+        assert!(out_after as u128 * other_after as u128 >= out_reserve as u128 * other_reserve as u128);
+        // 100k pairs plus the 90,909 swapped out of the reserves, less rounding
+        assert_eq!(amount_out, 190_909);
+    }
+
+    #[test]
+    fn buy_pays_more_of_the_cheap_side() {
+        // Long is scarce, so long is expensive and short is cheap
+        let long_out = buy_amount_out(500_000, 2_000_000, 100_000).unwrap();
+        let short_out = buy_amount_out(2_000_000, 500_000, 100_000).unwrap();
+        assert!(short_out > long_out);
+    }
+
+    #[test]
+    fn sell_keeps_the_product_constant() {
+        // Reserves after the buy above, selling back what it paid out
+        let (in_reserve, other_reserve, amount_in) = (909_091u64, 1_100_000u64, 190_909u64);
+        let pairs = sell_amount_out(in_reserve, other_reserve, amount_in).unwrap();
+        let in_after = (in_reserve + amount_in - pairs) as u128;
+        let other_after = (other_reserve - pairs) as u128;
+        assert!(in_after * other_after >= in_reserve as u128 * other_reserve as u128);
+        // Returns the 100k pairs, less rounding
+        assert!((99_999..=100_000).contains(&pairs));
+    }
+
+    #[test]
+    fn sell_into_an_empty_side_returns_nothing() {
+        assert_eq!(sell_amount_out(1_000_000, 0, 1_000).unwrap(), 0);
+        assert_eq!(sell_amount_out(1_000_000, 1_000_000, 0).unwrap(), 0);
+    }
+
+    #[test]
+    fn pool_pairs_pay_out_like_minted_tokens() {
+        use crate::instructions::claim_settlement::settlement_share;
+        use crate::state::{Market, MARKET_SPACE};
+
+        // A minter pays one unit per token, 2_000 for 1_000 of each side
+        let (minted, minter_collateral) = (1_000, 2_000);
+        // The pool turns the same collateral into as many pairs, and back
+        let pool_collateral = 2_000;
+        let pairs = pairs_for_value(pool_collateral);
+        assert_eq!(pairs, minted);
+        assert_eq!(pair_value(pairs).unwrap(), pool_collateral);
+
+        let mut market = Market::deserialize(&mut &[0u8; MARKET_SPACE][..]).unwrap();
+        market.start_volatility = 0.1;
+        market.strike = 20.0;
+        market.total_deposits = minter_collateral + pool_collateral;
+        market.settle(0.5).unwrap();
+
+        // Both sides hold the same tokens per unit of collateral, so settle equally
+        let supply = minted + pairs;
+        let payout = |amount| {
+            settlement_share(amount, supply, market.long_payout).unwrap()
+                + settlement_share(amount, supply, market.short_payout).unwrap()
+        };
+        assert_eq!(payout(minted), minter_collateral);
+        assert_eq!(payout(pairs), pool_collateral);
+    }
+
+    #[test]
+    fn buy_from_empty_reserves_returns_the_pairs() {
+        assert_eq!(buy_amount_out(0, 0, 1_000).unwrap(), 1_000);
+    }
+}
@@ -0,0 +1,60 @@
+use anchor_lang::prelude::*;
+
+/// How the strike of each new epoch in a series is chosen
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum StrikeRule {
+    Fixed,        // Every epoch uses base_strike
+    LastRealized, // Previous epoch's realized variance plus strike_spread
+}
+
+/// Account size of a series, discriminator included
+pub const SERIES_SPACE: usize = 8 // discriminator
+    + 32                          // authority
+    + 8                           // series_id
+    + 32                          // volatility_stats
+    + 32                          // collateral_mint
+    + 8                           // tenor
+    + 1                           // strike_rule
+    + 8                           // base_strike
+    + 8                           // strike_spread
+    + 8                           // variance_cap
+    + 32                          // current_market
+    + 8                           // next_epoch
+    + 8                           // next_timestamp
+    + 8                           // last_realized_variance
+    + 1                           // bump
+    + 8                           // dispute_window
+    + 8;                          // dispute_twap_window
+
+/// A continuous series of variance markets on one underlying. Each roll settles
+/// the expiring market and opens the next epoch, `tenor` seconds long.
+#[account]
+pub struct Series {
+    pub authority: Pubkey,
+    pub series_id: u64,
+    pub volatility_stats: Pubkey,     // Oracle account of the underlying
+    pub collateral_mint: Pubkey,
+    pub tenor: i64,                   // Length of each epoch in seconds
+    pub strike_rule: StrikeRule,
+    pub base_strike: f64,             // Fixed strike, or the first strike under LastRealized
+    pub strike_spread: f64,           // Added to the last realized variance, may be negative
+    pub variance_cap: f64,            // Cap on realized variance at settlement, 0 for none
+    pub current_market: Pubkey,       // Open market, default before the first roll
+    pub next_epoch: u64,
+    pub next_timestamp: i64,          // Start of the next epoch and expiry of the current one
+    pub last_realized_variance: f64,
+    pub bump: u8,
+    pub dispute_window: i64,          // Dispute window given to each epoch's settlement
+    pub dispute_twap_window: i64,     // TWAP window given to each epoch's disputes
+}
+
+impl Series {
+    pub fn next_strike(&self) -> f64 {
+        let strike = match self.strike_rule {
+            StrikeRule::Fixed => self.base_strike,
+            StrikeRule::LastRealized if self.current_market == Pubkey::default() => self.base_strike,
+            StrikeRule::LastRealized => self.last_realized_variance + self.strike_spread,
+        };
+        strike.max(0.0)
+    }
+}
@@ -0,0 +1,39 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::ErrorCode;
+
+pub const USER_DEPOSIT_SPACE: usize = 8 + 32 + 32 + 8 + 1;
+
+/// VAR tokens a user has minted in a market, checked against the per-user cap
+#[account]
+pub struct UserDeposit {
+    pub market: Pubkey,
+    pub owner: Pubkey,
+    pub minted: u64,
+    pub bump: u8,
+}
+
+impl UserDeposit {
+    /// Records `amount` VAR tokens minted by `owner`, filling in a freshly created
+    /// account, and checks the total against `max_user_deposits` (0 disables it)
+    pub fn record_mint(
+        &mut self,
+        market: Pubkey,
+        owner: Pubkey,
+        bump: u8,
+        amount: u64,
+        max_user_deposits: u64,
+    ) -> Result<()> {
+        if self.owner == Pubkey::default() {
+            self.market = market;
+            self.owner = owner;
+            self.bump = bump;
+        }
+        self.minted = self.minted.checked_add(amount).ok_or(ErrorCode::NumberOverflow)?;
+        require!(
+            max_user_deposits == 0 || self.minted <= max_user_deposits,
+            ErrorCode::UserCapExceeded
+        );
+        Ok(())
+    }
+}